@@ -2,24 +2,33 @@
 // File utama yang mengkoordinasikan seluruh aplikasi
 // FIXED VERSION dengan optimasi untuk handle 48,000+ games
 
-use crate::mame::GameScanner;
+use crate::mame::{
+    FinishedSession, GameScanner, HookFailure, HookRun, HookVars, LaunchLogger, ListXmlCache,
+    ListXmlCacheKey, SessionManager,
+};
 use crate::models::*;
 use crate::ui::components::mame_finder::MameFinderDialog;
 use crate::ui::dock::{DockTab, MameTabViewer, create_default_layout, dock_style};
 use crate::ui::notifications::NotificationManager;
 use crate::ui::panels::{
     ArtworkPanel, GameIndexManager, GameList, GameListView, HistoryPanel, IconManager,
-    PerformanceManager, Sidebar, SoftwareListPanel,
+    LaunchLogPanel, PerformanceManager, RecordingRequest, RecordingsPanel, SessionPanel, Sidebar,
+    SoftwareListPanel,
 };
 use crate::ui::redesign::{RedesignShell, tokens::RedesignTokens};
 use crate::ui::{DialogAction, DialogManager, DialogType};
+use crate::utils::fs_watcher::{self, FsWatcher, WATCH_DEBOUNCE};
 use crate::utils::hardware_filter::HardwareFilter;
-use crate::utils::rom_utils::RomLoader;
+use crate::utils::rom_utils::{
+    RomLoader, RomScanCache, RomSetDetection, detect_rom_set_type, samples, scan_cache, set_type,
+    verify_history::{self, ChangeKind},
+};
 use eframe::egui;
 use egui_dock::DockState;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::mem;
-use std::sync::mpsc;
+use std::path::PathBuf;
+use std::sync::{Arc, mpsc};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -39,6 +48,40 @@ impl PerPassRenderGuard {
     }
 }
 
+/// A game or software item to start once its pre-launch hooks are done
+enum LaunchRequest {
+    Game(String),
+    Software(crate::mame::SoftwareLaunch),
+    /// A game with extra switches, such as `-record` or `-playback`
+    GameWithArgs {
+        game: String,
+        args: Vec<String>,
+        note: String,
+    },
+}
+
+impl LaunchRequest {
+    /// The machine MAME runs
+    fn system(&self) -> &str {
+        match self {
+            LaunchRequest::Game(name) | LaunchRequest::GameWithArgs { game: name, .. } => name,
+            LaunchRequest::Software(launch) => &launch.system,
+        }
+    }
+
+    fn description(&self, system: &Game) -> String {
+        match self {
+            LaunchRequest::Game(_) => system.description.clone(),
+            LaunchRequest::Software(launch) => {
+                format!("{} on {}", launch.description, launch.system)
+            }
+            LaunchRequest::GameWithArgs { note, .. } => {
+                format!("{} ({})", system.description, note)
+            }
+        }
+    }
+}
+
 pub struct MameApp {
     // Core data
     pub config: AppConfig,
@@ -56,10 +99,16 @@ pub struct MameApp {
     pub artwork_panel: ArtworkPanel,
     pub history_panel: HistoryPanel,
     pub software_list_panel: SoftwareListPanel,
+    pub launch_log_panel: LaunchLogPanel,
+    pub session_panel: SessionPanel,
+    pub recordings_panel: RecordingsPanel,
 
     // Data organization
     pub all_manufacturers: Vec<String>,
-    pub running_games: HashMap<String, (std::process::Child, Instant)>,
+    pub sessions: SessionManager, // MAME processes started from here
+    pending_launches: Vec<(HookRun, LaunchRequest)>, // Waiting for pre-launch hooks
+    exit_hooks: Vec<HookRun>,     // Post-exit hooks still running
+    pub launch_log: LaunchLogger, // Output of every MAME run this session
     pub expanded_parents: HashMap<String, bool>,
 
     // Icon management
@@ -73,6 +122,12 @@ pub struct MameApp {
     pub need_reload_after_dialog: bool,
     pub roms_loading: bool,
     pub roms_tx: Option<mpsc::Sender<LoadingMessage>>,
+    pub rom_set_detection: Option<RomSetDetection>, // Layout found on disk by the last ROM scan
+    pub rom_scan_cache: Option<RomScanCache>, // Fingerprints and statuses from the last ROM scan
+    verification_was_running: bool,
+    fs_watcher: Option<FsWatcher>, // Live updates from the ROM and artwork folders
+    fs_watcher_started: bool,
+    ini_export_confirm: Option<Vec<PathBuf>>, // Existing ini files the pending export would replace
 
     // Performance optimization fields
     pub game_index_manager: GameIndexManager, // Game indexing, filtering, and search management
@@ -144,11 +199,23 @@ impl MameApp {
             artwork_panel: ArtworkPanel::new(),
             history_panel: HistoryPanel::new(),
             software_list_panel: SoftwareListPanel::new(),
+            launch_log_panel: LaunchLogPanel::new(),
+            session_panel: SessionPanel::new(),
+            recordings_panel: RecordingsPanel::new(),
             all_manufacturers: Vec::new(),
-            running_games: HashMap::new(),
+            sessions: SessionManager::new(),
+            pending_launches: Vec::new(),
+            exit_hooks: Vec::new(),
+            launch_log: LaunchLogger::new(),
             icon_manager: IconManager::new(&config),
             roms_loading: false,
             roms_tx: None,
+            rom_set_detection: None,
+            rom_scan_cache: None,
+            verification_was_running: false,
+            fs_watcher: None,
+            fs_watcher_started: false,
+            ini_export_confirm: None,
             expanded_parents: HashMap::new(),
             loading_rx: None,
             loading_stage: LoadingStage::Idle,
@@ -250,18 +317,71 @@ impl MameApp {
     }
 
     pub fn launch_game_at_index(&mut self, idx: usize) {
-        let Some(game) = self.games.get(idx) else {
+        if let Some(game) = self.games.get(idx) {
+            self.request_launch(LaunchRequest::Game(game.name.clone()));
+        }
+    }
+
+    /// Run a software-list item; the system's properties apply and its
+    /// play time is tracked like a game launched from the list
+    pub fn launch_software(&mut self, launch: crate::mame::SoftwareLaunch) {
+        self.request_launch(LaunchRequest::Software(launch));
+    }
+
+    /// Start a launch, first running its pre-launch hooks in the background
+    /// when there are any
+    fn request_launch(&mut self, request: LaunchRequest) {
+        if !self.may_launch(request.system()) {
+            return;
+        }
+        let Some(system) = self.games.iter().find(|game| game.name == request.system()) else {
             return;
         };
-        let game_name = game.name.clone();
-        let game_description = game.description.clone();
+        let hooks = &self.config.launch_hooks;
+        let commands = hooks.commands(system, HookStage::BeforeLaunch);
+        if commands.is_empty() {
+            self.start_launch(request);
+            return;
+        }
 
-        match crate::mame::launch_game(&game_name, &self.config) {
-            Ok(child) => {
-                self.running_games
-                    .insert(game_name, (child, Instant::now()));
+        let vars = HookVars::new(system, &request.description(system), &self.config);
+        let run = HookRun::start(
+            &system.name,
+            HookStage::BeforeLaunch,
+            commands
+                .iter()
+                .map(|command| vars.expand(command))
+                .collect(),
+            Duration::from_secs(hooks.timeout_secs),
+        );
+        self.pending_launches.push((run, request));
+    }
+
+    fn start_launch(&mut self, request: LaunchRequest) {
+        let Some(system) = self.games.iter().find(|game| game.name == request.system()) else {
+            return;
+        };
+        let description = request.description(system);
+        let result = match &request {
+            LaunchRequest::Game(_) => crate::mame::launch_game(system, &self.config),
+            LaunchRequest::Software(launch) => {
+                crate::mame::launch_software(launch, system, &self.config)
+            }
+            LaunchRequest::GameWithArgs { args, .. } => {
+                crate::mame::launch_game_with_args(system, args, &self.config)
+            }
+        };
+        match result {
+            Ok(mut process) => {
+                let id = self.launch_log.attach(
+                    request.system(),
+                    &process.command_line,
+                    &mut process.child,
+                );
+                self.sessions
+                    .start(id, request.system(), &description, process.child);
                 if self.config.preferences.enable_toast_notifications {
-                    self.notifications.info("Launching", game_description);
+                    self.notifications.info("Launching", description);
                 }
             }
             Err(error) => {
@@ -272,6 +392,88 @@ impl MameApp {
         }
     }
 
+    /// Launch what waited for its pre-launch hooks, and report hooks that
+    /// failed
+    fn process_launch_hooks(&mut self) {
+        for (run, request) in mem::take(&mut self.pending_launches) {
+            let Some(failures) = run.try_finish() else {
+                self.pending_launches.push((run, request));
+                continue;
+            };
+            let abort = !failures.is_empty() && self.config.launch_hooks.abort_on_failure;
+            self.report_hook_failures(&run, &failures, abort);
+            if !abort {
+                self.start_launch(request);
+            }
+        }
+
+        for run in mem::take(&mut self.exit_hooks) {
+            match run.try_finish() {
+                Some(failures) => self.report_hook_failures(&run, &failures, false),
+                None => self.exit_hooks.push(run),
+            }
+        }
+    }
+
+    fn report_hook_failures(&mut self, run: &HookRun, failures: &[HookFailure], aborted: bool) {
+        if failures.is_empty() {
+            return;
+        }
+        let mut message = failures
+            .iter()
+            .map(|failure| format!("{} — {}", failure.command, failure.reason))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if aborted {
+            message.push_str("\nThe game was not launched.");
+        }
+        eprintln!(
+            "{} hook failed for {}: {}",
+            run.stage.label(),
+            run.game,
+            message
+        );
+        if self.config.preferences.enable_toast_notifications {
+            self.notifications.error(
+                format!("{} hook failed for {}", run.stage.label(), run.game),
+                message,
+            );
+        }
+    }
+
+    /// Whether the running-games policy lets this game start now. Games
+    /// waiting for their pre-launch hooks count as running.
+    fn may_launch(&mut self, game: &str) -> bool {
+        let policy = self.config.preferences.instance_policy;
+        let starting = self
+            .pending_launches
+            .iter()
+            .find(|(run, _)| run.game == game || policy == InstancePolicy::Single);
+        let allowed = match starting {
+            Some((run, _)) => Err(format!("{} is still starting", run.game)),
+            None => self.sessions.check_policy(game, policy),
+        };
+        match allowed {
+            Ok(()) => true,
+            Err(reason) => {
+                if self.config.preferences.enable_toast_notifications {
+                    self.notifications.warning("Not launched", reason);
+                }
+                false
+            }
+        }
+    }
+
+    /// Stop a running game from the Sessions panel
+    pub fn stop_session(&mut self, id: u64) {
+        if let Err(e) = self.sessions.stop(id)
+            && self.config.preferences.enable_toast_notifications
+        {
+            self.notifications
+                .error("Could not stop game", e.to_string());
+        }
+    }
+
     /// Process pending search after debounce delay
     pub fn process_pending_search(&mut self) {
         if let Some(search_text) = self.game_index_manager.process_pending_search() {
@@ -304,8 +506,26 @@ impl MameApp {
     }
 
     pub fn load_mame_data_threaded(&mut self) {
-        if self.loading_stage != LoadingStage::Idle && self.loading_stage != LoadingStage::Complete
-        {
+        self.start_mame_load(false);
+    }
+
+    /// Discard the cached -listxml data for the selected MAME and rescan it.
+    pub fn rebuild_mame_database(&mut self) {
+        match self.loading_stage {
+            LoadingStage::Idle | LoadingStage::Complete | LoadingStage::Error => {
+                self.start_mame_load(true);
+            }
+            _ => {
+                println!("Load already in progress, please wait...");
+            }
+        }
+    }
+
+    fn start_mame_load(&mut self, force_rescan: bool) {
+        if !matches!(
+            self.loading_stage,
+            LoadingStage::Idle | LoadingStage::Complete | LoadingStage::Error
+        ) {
             println!("Load already in progress, skipping...");
             return;
         }
@@ -347,6 +567,33 @@ impl MameApp {
                 None
             };
 
+            // Reuse the parsed -listxml data while the MAME binary is unchanged
+            let cache = ListXmlCache::new();
+            let cache_key = match ListXmlCacheKey::from_executable(&mame, catver_path.as_deref()) {
+                Ok(key) => Some(key),
+                Err(e) => {
+                    eprintln!("listxml cache disabled: {}", e);
+                    None
+                }
+            };
+
+            if let (Some(cache), Some(key)) = (&cache, &cache_key) {
+                if force_rescan {
+                    cache.invalidate(key);
+                } else if let Some(cached) = cache.load(key) {
+                    println!("Loaded {} games from listxml cache", cached.games.len());
+                    let _ = tx.send(LoadingMessage::MameLoadProgress(format!(
+                        "Loaded {} games from cache",
+                        cached.games.len()
+                    )));
+                    let _ = tx.send(LoadingMessage::MameLoadComplete(
+                        cached.games,
+                        cached.manufacturers,
+                    ));
+                    return;
+                }
+            }
+
             // Create scanner with category loader if available
             let mut scanner = GameScanner::new(&mame.path);
             if let Some(ref loader) = category_loader {
@@ -369,6 +616,12 @@ impl MameApp {
                     manufacturers.sort();
                     manufacturers.dedup();
 
+                    if let (Some(cache), Some(key)) = (&cache, &cache_key)
+                        && let Err(e) = cache.store(key, &games, &manufacturers)
+                    {
+                        eprintln!("Failed to write listxml cache: {}", e);
+                    }
+
                     let _ = tx.send(LoadingMessage::MameLoadComplete(games, manufacturers));
                 }
                 Err(e) => {
@@ -408,6 +661,12 @@ impl MameApp {
         println!("Starting ROM scan with {} directories", valid_dirs.len());
 
         let metadata = self.game_metadata.clone();
+        let sample_paths = self.config.sample_paths.clone();
+        // Reuse the in-memory cache from the last scan of these folders
+        let cache = self
+            .rom_scan_cache
+            .take()
+            .filter(|cache| cache.rom_dirs() == valid_dirs.as_slice());
         let (tx, rx) = mpsc::channel();
         self.loading_rx = Some(rx);
         self.loading_stage = LoadingStage::ScanningRoms;
@@ -416,11 +675,47 @@ impl MameApp {
         thread::spawn(move || {
             let _ = tx.send(LoadingMessage::RomScanStarted);
 
-            let loader = RomLoader::new(valid_dirs);
             let progress_tx = tx.clone();
-            let games = loader.load_roms_with_progress(metadata, move |current, total| {
+            let on_progress = move |current, total| {
                 let _ = progress_tx.send(LoadingMessage::RomScanProgress(current, total));
-            });
+            };
+            let mut cache = cache.or_else(|| RomScanCache::load(valid_dirs.clone()));
+            let (loader, mut games) = match cache.as_mut() {
+                Some(cache) => {
+                    let games = RomLoader::new(valid_dirs.clone()).load_roms_incremental(
+                        metadata,
+                        cache,
+                        on_progress,
+                    );
+                    let loader =
+                        RomLoader::new(valid_dirs).with_listings(Arc::new(cache.listings()));
+                    (loader, games)
+                }
+                None => {
+                    let loader = RomLoader::new(valid_dirs);
+                    let games = loader.load_roms_with_progress(metadata, on_progress);
+                    (loader, games)
+                }
+            };
+
+            let detection = detect_rom_set_type(&loader, &games, set_type::DEFAULT_SAMPLE_SIZE);
+            println!("ROM set layout: {}", detection.summary());
+            if detection.set_type == RomSetType::Merged {
+                set_type::apply_merged_availability(&loader, &mut games);
+            }
+            let _ = tx.send(LoadingMessage::RomSetDetected(detection));
+
+            let samples_missing = samples::audit_samples(&mut games, &sample_paths);
+            println!("Sample audit: {} games missing samples", samples_missing);
+
+            if let Some(mut cache) = cache {
+                // Record the statuses after merged clones were resolved
+                cache.record_statuses(&games);
+                if let Err(e) = cache.save() {
+                    eprintln!("Failed to save ROM scan cache: {:#}", e);
+                }
+                let _ = tx.send(LoadingMessage::RomScanCached(Box::new(cache)));
+            }
 
             println!("ROM scan complete in thread: {} games", games.len());
             let _ = tx.send(LoadingMessage::RomScanComplete(games));
@@ -486,6 +781,7 @@ impl MameApp {
                     LoadingMessage::RomScanComplete(games) => {
                         // Categories are now handled during MAME scanning
                         self.games = games;
+                        self.restore_verification_history();
                         self.loading_stage = LoadingStage::Complete;
                         self.loading_start_time = None;
                         should_keep_receiver = false;
@@ -500,6 +796,40 @@ impl MameApp {
                         }
                     }
 
+                    LoadingMessage::RomSetDetected(detection) => {
+                        let filters = &mut self.config.filter_settings;
+                        if detection.set_type != RomSetType::Unknown
+                            && detection.set_type != filters.rom_set_type
+                        {
+                            filters.rom_set_type = detection.set_type;
+                            // Clones are only playable from their own archives
+                            // or from the parent they were merged into. Only
+                            // a newly detected layout shows them, so later
+                            // scans leave the user's toggles alone.
+                            match detection.set_type {
+                                RomSetType::Merged => filters.show_clones_in_merged = true,
+                                RomSetType::Split | RomSetType::NonMerged => {
+                                    filters.show_clones_in_split = true
+                                }
+                                RomSetType::Unknown => {}
+                            }
+                        }
+                        if detection.mixed {
+                            self.notifications.warning(
+                                "Mixed ROM collection",
+                                format!(
+                                    "ROM sets use more than one layout: {}",
+                                    detection.summary()
+                                ),
+                            );
+                        }
+                        self.rom_set_detection = Some(detection);
+                    }
+
+                    LoadingMessage::RomScanCached(cache) => {
+                        self.rom_scan_cache = Some(*cache);
+                    }
+
                     LoadingMessage::RomScanFailed(error) => {
                         eprintln!("UI: ROM scan failed: {}", error);
                         self.loading_stage = LoadingStage::Error;
@@ -547,6 +877,9 @@ impl MameApp {
         self.save_config();
         self.reload_hardware_filter();
         self.software_list_panel.invalidate();
+        // Watch the new folders once the reload completes
+        self.fs_watcher = None;
+        self.fs_watcher_started = false;
         self.games.clear();
         self.game_index_manager.reset();
 
@@ -588,43 +921,72 @@ impl MameApp {
         self.save_config();
     }
 
-    pub fn update_game_stats(&mut self, rom_name: &str, play_time: u32) {
+    pub fn record_session_stats(&mut self, session: &FinishedSession) {
         let stats = self
             .config
             .game_stats
-            .entry(rom_name.to_string())
+            .entry(session.game.clone())
             .or_default();
 
         stats.play_count += 1;
         stats.last_played = Some(chrono::Local::now().to_rfc3339());
-        stats.total_play_time += play_time;
+        stats.total_play_time += session.play_time;
+        stats.last_exit = Some(session.outcome());
+        stats.last_crashed = session.crashed();
+        if session.crashed() {
+            stats.crash_count += 1;
+        }
 
         self.save_config();
     }
 
-    pub fn check_running_games(&mut self) {
-        let mut finished_games = Vec::new();
-        let mut still_running = HashMap::new();
-
-        let running_games = std::mem::take(&mut self.running_games);
-
-        for (rom_name, (mut child, start_time)) in running_games {
-            match child.try_wait() {
-                Ok(Some(_)) => {
-                    let play_time = start_time.elapsed().as_secs() as u32;
-                    finished_games.push((rom_name, play_time));
-                }
-                Ok(None) => {
-                    still_running.insert(rom_name, (child, start_time));
-                }
-                Err(_) => {}
-            }
+    fn run_exit_hooks(&mut self, session: &FinishedSession) {
+        let Some(game) = self.games.iter().find(|game| game.name == session.game) else {
+            return;
+        };
+        let hooks = &self.config.launch_hooks;
+        let commands = hooks.commands(game, HookStage::AfterExit);
+        if commands.is_empty() {
+            return;
         }
+        let vars = HookVars::new(game, &session.description, &self.config);
+        self.exit_hooks.push(HookRun::start(
+            &game.name,
+            HookStage::AfterExit,
+            commands
+                .iter()
+                .map(|command| vars.expand(command))
+                .collect(),
+            Duration::from_secs(hooks.timeout_secs),
+        ));
+    }
 
-        self.running_games = still_running;
+    pub fn check_running_games(&mut self) {
+        for session in self.sessions.poll() {
+            self.launch_log
+                .finish(session.id, session.exit, session.stopped_by_user);
+            self.record_session_stats(&session);
+            self.run_exit_hooks(&session);
+            self.recordings_panel.invalidate();
+        }
 
-        for (rom_name, play_time) in finished_games {
-            self.update_game_stats(&rom_name, play_time);
+        // Output keeps arriving after the exit, so failures are reported
+        // once a finished launch has been read to the end
+        for failure in self.launch_log.poll() {
+            let game = self
+                .games
+                .iter()
+                .find(|game| game.name == failure.game)
+                .map_or(failure.game.clone(), |game| {
+                    format!("{} ({})", game.description, game.name)
+                });
+            self.launch_log_panel.select(failure.launch_id);
+            if self.config.preferences.enable_toast_notifications {
+                self.notifications.error(
+                    format!("{} failed to start", game),
+                    format!("{} — see the MAME Log tab", failure.message()),
+                );
+            }
         }
     }
 
@@ -655,6 +1017,333 @@ impl MameApp {
         }
     }
 
+    /// Persist verification statuses once a run ends so they survive restarts
+    fn save_statuses_after_verification(&mut self) {
+        let running = self.dialog_manager.rom_verify_dialog().is_verifying();
+        if self.verification_was_running && !running {
+            self.record_verification_history();
+            if let Some(cache) = self.rom_scan_cache.as_mut() {
+                cache.record_statuses(&self.games);
+                if let Err(e) = cache.save_statuses() {
+                    eprintln!("Failed to save ROM statuses: {:#}", e);
+                }
+            }
+        }
+        self.verification_was_running = running;
+    }
+
+    /// Add the finished run to the verification history and report what
+    /// changed since the previous audit
+    fn record_verification_history(&mut self) {
+        let set_fingerprints = self
+            .rom_scan_cache
+            .as_ref()
+            .map(|cache| cache.set_fingerprints(&self.games))
+            .unwrap_or_default();
+        let Some(run) = self
+            .dialog_manager
+            .rom_verify_dialog()
+            .record_history_run(&self.config, &set_fingerprints)
+        else {
+            return;
+        };
+
+        let broken = run.count(ChangeKind::NewlyBroken);
+        let fixed = run.count(ChangeKind::NewlyFixed);
+        if self.config.preferences.enable_toast_notifications && (broken > 0 || fixed > 0) {
+            let message = format!(
+                "{} newly broken, {} newly fixed since the last audit",
+                broken, fixed
+            );
+            if broken > 0 {
+                self.notifications.warning("Verification changes", message);
+            } else {
+                self.notifications.success("Verification changes", message);
+            }
+        }
+    }
+
+    /// Restore the verification results stored by earlier sessions
+    fn restore_verification_history(&mut self) {
+        let mame_version = self
+            .config
+            .mame_executables
+            .get(self.config.selected_mame_index)
+            .map(|mame| mame.version.clone())
+            .unwrap_or_default();
+        let fingerprint = verify_history::rom_fingerprint(&self.config);
+        // From the scan cache the loader just refreshed, so no file is
+        // touched here on the UI thread
+        let set_fingerprints = self
+            .rom_scan_cache
+            .as_ref()
+            .map(|cache| cache.set_fingerprints(&self.games))
+            .unwrap_or_default();
+        let restored = self.dialog_manager.rom_verify_dialog().history().apply_to(
+            &mut self.games,
+            &mame_version,
+            &fingerprint,
+            &set_fingerprints,
+        );
+        if restored > 0 {
+            println!("Restored {} stored verification results", restored);
+        }
+    }
+
+    /// Apply file changes reported by the folder watcher. The watcher starts
+    /// once the game list is loaded.
+    fn process_fs_changes(&mut self, ctx: &egui::Context) {
+        if !self.fs_watcher_started && self.loading_stage == LoadingStage::Complete {
+            self.fs_watcher_started = true;
+            let repaint_ctx = ctx.clone();
+            match FsWatcher::from_config(&self.config, move || {
+                repaint_ctx.request_repaint_after(WATCH_DEBOUNCE)
+            }) {
+                Ok(watcher) => self.fs_watcher = Some(watcher),
+                Err(e) => eprintln!("Folder watching unavailable: {:#}", e),
+            }
+        }
+
+        let Some(watcher) = self.fs_watcher.as_mut() else {
+            return;
+        };
+        let Some(batch) = watcher.poll() else {
+            if let Some(wait) = watcher.time_until_ready() {
+                ctx.request_repaint_after(wait);
+            }
+            return;
+        };
+
+        if !batch.roms.is_empty() {
+            self.apply_rom_changes(&batch.roms);
+        }
+        if !batch.software_roms.is_empty() {
+            self.software_list_panel
+                .media_paths_changed(&batch.software_roms, ctx);
+        }
+        if !batch.artwork.is_empty() {
+            let roots: Vec<PathBuf> = [
+                &self.config.snap_path,
+                &self.config.cabinet_path,
+                &self.config.marquee_path,
+                &self.config.title_path,
+                &self.config.flyer_path,
+                &self.config.pcb_path,
+            ]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+            for path in &batch.artwork {
+                for name in roots
+                    .iter()
+                    .filter_map(|root| fs_watcher::changed_name(root, path))
+                {
+                    self.artwork_panel.forget_game(&name);
+                    self.redesign_shell.state.artwork_loader.forget_game(&name);
+                }
+            }
+        }
+        if let Some(icons_path) = self.config.icons_path.clone() {
+            for path in &batch.icons {
+                match fs_watcher::changed_name(&icons_path, path) {
+                    // A changed icons.zip can hold any game's icon
+                    Some(name) if name.eq_ignore_ascii_case("icons") => {
+                        self.icon_manager.clear_cache()
+                    }
+                    Some(name) => self.icon_manager.remove_from_cache(&name),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    /// Recompute only the games whose ROM archives or CHDs changed
+    fn apply_rom_changes(&mut self, paths: &[PathBuf]) {
+        // A running scan picks the changes up itself
+        let Some(cache) = self.rom_scan_cache.as_mut() else {
+            return;
+        };
+
+        let mut delta = cache.refresh_paths(paths);
+        // Extra ROM folders are not scanned, but verification reads them
+        for path in paths {
+            if let Some(dir) = self
+                .config
+                .extra_rom_dirs
+                .iter()
+                .find(|dir| path.starts_with(dir))
+            {
+                delta.changed_sets.extend(scan_cache::set_names(dir, path));
+            }
+        }
+        if delta.changed_sets.is_empty() {
+            return;
+        }
+
+        let merged = self
+            .rom_set_detection
+            .as_ref()
+            .is_some_and(|detection| detection.set_type == RomSetType::Merged);
+        let changed = RomLoader::new(cache.rom_dirs().to_vec()).refresh_statuses(
+            &mut self.games,
+            cache,
+            &delta,
+            merged,
+        );
+        if changed.is_empty() {
+            return;
+        }
+        println!("Folder watcher: {} games updated", changed.len());
+
+        cache.record_statuses(&self.games);
+        if let Err(e) = cache.save() {
+            eprintln!("Failed to save ROM scan cache: {:#}", e);
+        }
+        self.game_index_manager.update_games(&self.games, &changed);
+        self.game_list.invalidate_cache();
+        self.redesign_shell.state.mark_table_dirty();
+        self.redesign_shell.state.mark_sidebar_stats_dirty();
+    }
+
+    /// Bring the options of MAME's ini folder into the default properties
+    /// and the orientation, source and game levels
+    fn import_mame_ini(&mut self) {
+        use crate::utils::ini_utils::options_ini;
+
+        let Some(dir) = self.config.ini_path.clone() else {
+            self.notifications.warning(
+                "INI import",
+                "Set the INI folder in Directories & Paths first",
+            );
+            return;
+        };
+        match options_ini::import_ini_folder(&dir, &mut self.config, &self.games) {
+            Ok(summary) => {
+                self.save_config();
+                if self.config.preferences.enable_toast_notifications {
+                    self.notifications
+                        .success("INI files imported", summary.describe());
+                }
+            }
+            Err(e) => self
+                .notifications
+                .error("INI import failed", format!("{:#}", e)),
+        }
+    }
+
+    /// Start an ini export, asking first when it would replace files
+    fn request_mame_ini_export(&mut self) {
+        use crate::utils::ini_utils::options_ini;
+
+        let Some(dir) = self.config.ini_path.clone() else {
+            self.notifications.warning(
+                "INI export",
+                "Set the INI folder in Directories & Paths first",
+            );
+            return;
+        };
+        let overwrites = options_ini::export_overwrites(&dir, &self.config);
+        if overwrites.is_empty() {
+            self.export_mame_ini();
+        } else {
+            self.ini_export_confirm = Some(overwrites);
+        }
+    }
+
+    /// Ask before an export replaces existing ini files
+    fn show_ini_export_confirm(&mut self, ctx: &egui::Context) {
+        let Some(files) = &self.ini_export_confirm else {
+            return;
+        };
+        let mut export = false;
+        let mut cancel = false;
+        egui::Window::new("Export MAME INI Files")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} ini files in the INI folder will be replaced:",
+                    files.len()
+                ));
+                egui::ScrollArea::vertical()
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for file in files {
+                            ui.label(egui::RichText::new(file.display().to_string()).small());
+                        }
+                    });
+                ui.label(
+                    egui::RichText::new("The current files are kept next to them as .ini.bak.")
+                        .weak(),
+                );
+                ui.horizontal(|ui| {
+                    export = ui.button("Export").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+        if export {
+            self.ini_export_confirm = None;
+            self.export_mame_ini();
+        } else if cancel {
+            self.ini_export_confirm = None;
+        }
+    }
+
+    /// Write the properties into MAME's ini folder
+    fn export_mame_ini(&mut self) {
+        use crate::utils::ini_utils::options_ini;
+
+        let Some(dir) = self.config.ini_path.clone() else {
+            return;
+        };
+        match options_ini::export_ini_folder(&dir, &self.config) {
+            Ok(summary) => {
+                if self.config.preferences.enable_toast_notifications {
+                    self.notifications
+                        .success("INI files exported", summary.describe());
+                }
+            }
+            Err(e) => self
+                .notifications
+                .error("INI export failed", format!("{:#}", e)),
+        }
+    }
+
+    /// Export a fixdat once the verification results asked for one
+    fn process_fixdat_request(&mut self) {
+        use crate::utils::rom_utils::fixdat::FixdatScope;
+
+        let Some(scope) = self
+            .dialog_manager
+            .rom_verify_dialog()
+            .take_fixdat_request()
+        else {
+            return;
+        };
+        let include: Option<HashSet<String>> = match scope {
+            FixdatScope::All => None,
+            FixdatScope::CurrentFilter => {
+                if self.game_index_manager.is_cache_dirty() {
+                    self.update_filtered_games_cache();
+                }
+                Some(
+                    self.game_index_manager
+                        .get_filtered_games()
+                        .iter()
+                        .filter_map(|&idx| self.games.get(idx))
+                        .map(|game| game.name.clone())
+                        .collect(),
+                )
+            }
+            FixdatScope::Favorites => Some(self.config.favorite_games.clone()),
+        };
+        self.dialog_manager
+            .rom_verify_dialog()
+            .export_fixdat(&self.games, include.as_ref());
+    }
+
     /// Jump to the first game that starts with the given character
     pub fn jump_to_game_starting_with(&mut self, character: char) {
         let search_char = character.to_lowercase().to_string();
@@ -878,12 +1567,24 @@ impl eframe::App for MameApp {
         if self.performance_manager.frame_count.is_multiple_of(30) {
             self.check_running_games();
         }
+        self.process_launch_hooks();
+        if self.launch_log.is_busy()
+            || !self.pending_launches.is_empty()
+            || !self.exit_hooks.is_empty()
+        {
+            // Keep polling so launches and their hooks finish without user input
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
 
         if self.config.preferences.performance.enable_lazy_icons {
             self.process_icon_queue(ctx);
         }
 
+        self.process_fs_changes(ctx);
         self.update_game_verification_statuses();
+        self.save_statuses_after_verification();
+        self.process_fixdat_request();
+        self.show_ini_export_confirm(ctx);
 
         if self.config.preferences.ui_shell == UiShellMode::RedesignPreview {
             let mut shell = mem::take(&mut self.redesign_shell);
@@ -1106,6 +1807,12 @@ impl MameApp {
                 != old_others.show_parents_only
             || self.config.filter_settings.other_filters.show_chd_games
                 != old_others.show_chd_games
+            || self
+                .config
+                .filter_settings
+                .other_filters
+                .show_samples_missing
+                != old_others.show_samples_missing
             || self.config.filter_settings.cpu_filter != old_cpu
             || self.config.filter_settings.device_filter != old_device
             || self.config.filter_settings.sound_filter != old_sound
@@ -1182,8 +1889,54 @@ impl MameApp {
         self.history_panel.show(ui, &self.config);
     }
 
+    pub fn render_launch_log_panel(&mut self, ui: &mut egui::Ui) {
+        self.launch_log_panel.show(ui, &self.launch_log);
+    }
+
+    pub fn render_recordings_panel(&mut self, ui: &mut egui::Ui) {
+        let selected_game = self.selected_game.and_then(|idx| self.games.get(idx));
+        self.recordings_panel.show(ui, &self.config, selected_game);
+        let request = match self.recordings_panel.take_request() {
+            Some(RecordingRequest::Record { game, file_name }) => LaunchRequest::GameWithArgs {
+                game,
+                note: format!("recording {}", file_name),
+                args: vec!["-record".to_string(), file_name],
+            },
+            Some(RecordingRequest::Playback {
+                game,
+                file_name,
+                exit_after,
+            }) => {
+                let mut args = vec!["-playback".to_string(), file_name.clone()];
+                if exit_after {
+                    args.push("-exit_after_playback".to_string());
+                }
+                LaunchRequest::GameWithArgs {
+                    game,
+                    args,
+                    note: format!("playing back {}", file_name),
+                }
+            }
+            None => return,
+        };
+        self.request_launch(request);
+    }
+
+    pub fn render_session_panel(&mut self, ui: &mut egui::Ui) {
+        self.session_panel
+            .show(ui, &self.sessions, self.config.preferences.instance_policy);
+        if let Some(id) = self.session_panel.take_stop_request() {
+            self.stop_session(id);
+        }
+    }
+
     pub fn render_software_list_panel(&mut self, ui: &mut egui::Ui) {
-        self.software_list_panel.show(ui, &self.config);
+        let selected_machine = self.selected_game.and_then(|idx| self.games.get(idx));
+        self.software_list_panel
+            .show(ui, &self.config, &self.games, selected_machine);
+        if let Some(launch) = self.software_list_panel.take_launch_request() {
+            self.launch_software(launch);
+        }
     }
 
     pub fn render_game_list_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
@@ -1595,6 +2348,11 @@ impl MameApp {
                         ui.close();
                     }
 
+                    if ui.button("🔗 Launch Hooks").clicked() {
+                        self.dialog_manager.open_dialog(DialogType::LaunchHooks);
+                        ui.close();
+                    }
+
                     ui.menu_button("Advanced MAME Settings", |ui| {
                         if ui.button("⚙️ Advanced MAME Settings").clicked() {
                             self.dialog_manager.set_advanced_mame_settings_dialog(Some(
@@ -1606,6 +2364,29 @@ impl MameApp {
                                 .open_dialog(DialogType::AdvancedMameSettings);
                             ui.close();
                         }
+
+                        ui.separator();
+
+                        if ui
+                            .button("📥 Import MAME INI Files")
+                            .on_hover_text(
+                                "Read mame.ini, vertical/horizontal.ini, source/*.ini and \
+                                 game ini files from the INI folder",
+                            )
+                            .clicked()
+                        {
+                            self.import_mame_ini();
+                            ui.close();
+                        }
+
+                        if ui
+                            .button("📤 Export MAME INI Files")
+                            .on_hover_text("Write the properties as ini files MAME reads")
+                            .clicked()
+                        {
+                            self.request_mame_ini_export();
+                            ui.close();
+                        }
                     });
 
                     ui.separator();
@@ -1638,8 +2419,23 @@ impl MameApp {
                         ui.close();
                     }
 
+                    if ui.button("🧱 Rebuild ROM Sets...").clicked() {
+                        self.dialog_manager.open_dialog(DialogType::RomRebuild);
+                        ui.close();
+                    }
+
+                    if ui.button("🧹 Clean Up ROM Folders...").clicked() {
+                        self.dialog_manager.open_dialog(DialogType::RomCleanup);
+                        ui.close();
+                    }
+
                     ui.separator();
 
+                    if ui.button("🔄 Rebuild Game Database").clicked() {
+                        self.rebuild_mame_database();
+                        ui.close();
+                    }
+
                     if ui.button("📊 Plugin Support Info").clicked() {
                         self.show_plugin_info();
                         ui.close();
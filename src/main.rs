@@ -2,6 +2,9 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::if_same_then_else)]
 #![allow(clippy::upper_case_acronyms)]
+// Setup style used by the older tests
+#![cfg_attr(test, allow(clippy::writeln_empty_string))]
+#![cfg_attr(test, allow(clippy::field_reassign_with_default))]
 
 mod app;
 mod config;
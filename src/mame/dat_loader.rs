@@ -0,0 +1,564 @@
+// src/mame/dat_loader.rs
+//! Loads ROM-manager DATs (Logiqx XML and ClrMamePro text) so collections can
+//! be audited against something other than the installed MAME binary.
+use crate::models::{DiskEntry, DumpStatus, Game, MachineRomSet, RomEntry, RomStatus};
+use anyhow::{Context, Result, anyhow};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A DAT file turned into machines the ROM auditor understands
+#[derive(Debug, Clone, Default)]
+pub struct DatFile {
+    pub path: PathBuf,
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    pub games: Vec<Game>,
+}
+
+impl DatFile {
+    /// Short label for the verification page, e.g. "MAME 0.229 (41234 sets)"
+    pub fn label(&self) -> String {
+        let title = if self.description.is_empty() {
+            &self.name
+        } else {
+            &self.description
+        };
+        format!("{} ({} sets)", title, self.games.len())
+    }
+}
+
+#[derive(Default)]
+struct DatMachine {
+    name: Option<String>,
+    description: Option<String>,
+    year: Option<String>,
+    manufacturer: Option<String>,
+    cloneof: Option<String>,
+    romof: Option<String>,
+    is_bios: bool,
+    is_device: bool,
+    roms: Vec<RomEntry>,
+    disks: Vec<DiskEntry>,
+    device_refs: Vec<String>,
+    sampleof: Option<String>,
+    samples: Vec<String>,
+}
+
+impl DatMachine {
+    fn into_game(self) -> Option<Game> {
+        let name = self.name?;
+        let requires_chd = !self.disks.is_empty();
+        let chd_name = self.disks.first().map(|disk| disk.name.clone());
+
+        Some(Game {
+            description: self.description.unwrap_or_else(|| name.clone()),
+            manufacturer: self.manufacturer.unwrap_or_else(|| "Unknown".to_string()),
+            year: self.year.unwrap_or_else(|| "????".to_string()),
+            driver: String::new(),
+            driver_status: "unknown".to_string(),
+            status: RomStatus::Unknown,
+            parent: self.cloneof.clone(),
+            category: String::new(),
+            play_count: 0,
+            is_clone: self.cloneof.is_some(),
+            is_device: self.is_device,
+            is_bios: self.is_bios,
+            controls: String::new(),
+            requires_chd,
+            chd_name,
+            verification_status: None,
+            sample_status: Default::default(),
+            rom_set: MachineRomSet {
+                cloneof: self.cloneof,
+                romof: self.romof,
+                roms: self.roms,
+                disks: self.disks,
+                device_refs: self.device_refs,
+                sampleof: self.sampleof,
+                samples: self.samples,
+                ..Default::default()
+            },
+            name,
+        })
+    }
+}
+
+pub struct DatLoader;
+
+impl DatLoader {
+    /// Load a Logiqx XML or ClrMamePro DAT, detected from its contents
+    pub fn load(path: &Path) -> Result<DatFile> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+
+        let mut dat = if text.starts_with('<') {
+            Self::parse_logiqx(text)
+        } else {
+            Self::parse_clrmamepro(text)
+        }
+        .with_context(|| format!("Failed to parse DAT {}", path.display()))?;
+
+        if dat.games.is_empty() {
+            return Err(anyhow!("{} does not contain any sets", path.display()));
+        }
+        if dat.name.is_empty() {
+            dat.name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("DAT")
+                .to_string();
+        }
+        dat.path = path.to_path_buf();
+        Ok(dat)
+    }
+
+    fn parse_logiqx(xml: &str) -> Result<DatFile> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut dat = DatFile::default();
+        let mut buf = Vec::new();
+        let mut in_header = false;
+        let mut current: Option<DatMachine> = None;
+
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(event) => match event.name().as_ref() {
+                    b"header" => in_header = true,
+                    b"game" | b"machine" | b"resource" => {
+                        current = Some(Self::machine_from_start(&event, &reader)?);
+                    }
+                    b"description" | b"year" | b"manufacturer" | b"name" | b"version" => {
+                        let text = reader.read_text(event.name())?;
+                        let text = quick_xml::escape::unescape(&text)?.into_owned();
+                        if let Some(machine) = current.as_mut() {
+                            match event.name().as_ref() {
+                                b"description" => machine.description = Some(text),
+                                b"year" => machine.year = Some(text),
+                                b"manufacturer" => machine.manufacturer = Some(text),
+                                _ => {}
+                            }
+                        } else if in_header {
+                            match event.name().as_ref() {
+                                b"name" => dat.name = text,
+                                b"description" => dat.description = text,
+                                b"version" => dat.version = text,
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {
+                        if let Some(machine) = current.as_mut() {
+                            Self::read_entry(machine, &event, &reader)?;
+                        }
+                    }
+                },
+                Event::Empty(event) => {
+                    if let Some(machine) = current.as_mut() {
+                        Self::read_entry(machine, &event, &reader)?;
+                    }
+                }
+                Event::End(event) => match event.name().as_ref() {
+                    b"header" => in_header = false,
+                    b"game" | b"machine" | b"resource" => {
+                        if let Some(game) = current.take().and_then(DatMachine::into_game) {
+                            dat.games.push(game);
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+
+            buf.clear();
+        }
+
+        Ok(dat)
+    }
+
+    fn machine_from_start(event: &BytesStart<'_>, reader: &Reader<&[u8]>) -> Result<DatMachine> {
+        Ok(DatMachine {
+            name: Self::xml_attr(event, b"name", reader)?,
+            cloneof: Self::xml_attr(event, b"cloneof", reader)?,
+            romof: Self::xml_attr(event, b"romof", reader)?,
+            sampleof: Self::xml_attr(event, b"sampleof", reader)?,
+            is_bios: event.name().as_ref() == b"resource"
+                || Self::xml_attr(event, b"isbios", reader)?.as_deref() == Some("yes"),
+            is_device: Self::xml_attr(event, b"isdevice", reader)?.as_deref() == Some("yes"),
+            ..Default::default()
+        })
+    }
+
+    fn read_entry(
+        machine: &mut DatMachine,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        let attr = |key: &[u8]| Self::xml_attr(event, key, reader);
+        match event.name().as_ref() {
+            b"rom" => {
+                if let Some(name) = attr(b"name")? {
+                    machine.roms.push(RomEntry {
+                        name,
+                        size: attr(b"size")?
+                            .and_then(|size| size.parse().ok())
+                            .unwrap_or(0),
+                        crc: attr(b"crc")?.map(|crc| crc.to_lowercase()),
+                        sha1: attr(b"sha1")?.map(|sha1| sha1.to_lowercase()),
+                        merge: attr(b"merge")?,
+                        bios: attr(b"bios")?,
+                        region: attr(b"region")?,
+                        optional: attr(b"optional")?.as_deref() == Some("yes"),
+                        status: DumpStatus::from_xml(attr(b"status")?.as_deref()),
+                    });
+                }
+            }
+            b"disk" => {
+                if let Some(name) = attr(b"name")? {
+                    machine.disks.push(DiskEntry {
+                        name,
+                        sha1: attr(b"sha1")?.map(|sha1| sha1.to_lowercase()),
+                        merge: attr(b"merge")?,
+                        region: attr(b"region")?,
+                        index: attr(b"index")?.and_then(|index| index.parse().ok()),
+                        writable: attr(b"writable")?.as_deref() == Some("yes"),
+                        optional: attr(b"optional")?.as_deref() == Some("yes"),
+                        status: DumpStatus::from_xml(attr(b"status")?.as_deref()),
+                    });
+                }
+            }
+            b"device_ref" => {
+                if let Some(name) = attr(b"name")?
+                    && !machine.device_refs.contains(&name)
+                {
+                    machine.device_refs.push(name);
+                }
+            }
+            b"sample" => machine.samples.extend(attr(b"name")?),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn xml_attr(
+        event: &BytesStart<'_>,
+        key: &[u8],
+        reader: &Reader<&[u8]>,
+    ) -> Result<Option<String>> {
+        for attr in event.attributes().with_checks(false) {
+            let attr = attr?;
+            if attr.key.as_ref() == key {
+                return Ok(Some(
+                    attr.decode_and_unescape_value(reader.decoder())?
+                        .into_owned(),
+                ));
+            }
+        }
+        Ok(None)
+    }
+
+    /// ClrMamePro text DATs are nested `key ( ... )` blocks of `key value` pairs
+    fn parse_clrmamepro(text: &str) -> Result<DatFile> {
+        let tokens = tokenize(text)?;
+        let mut pos = 0;
+        let mut dat = DatFile::default();
+
+        while pos < tokens.len() {
+            let Token::Word(key) = &tokens[pos] else {
+                return Err(anyhow!("Unexpected '(' or ')' at top level"));
+            };
+            pos += 1;
+            let block = parse_block(&tokens, &mut pos)
+                .with_context(|| format!("Block '{}' is not closed", key))?;
+
+            match key.as_str() {
+                "clrmamepro" => {
+                    dat.name = block.text("name").unwrap_or_default();
+                    dat.description = block.text("description").unwrap_or_default();
+                    dat.version = block.text("version").unwrap_or_default();
+                }
+                "game" | "machine" | "resource" => {
+                    let machine = Self::machine_from_block(&block, key == "resource");
+                    if let Some(game) = machine.into_game() {
+                        dat.games.push(game);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(dat)
+    }
+
+    fn machine_from_block(block: &Block, is_resource: bool) -> DatMachine {
+        let mut machine = DatMachine {
+            name: block.text("name"),
+            description: block.text("description"),
+            year: block.text("year"),
+            manufacturer: block.text("manufacturer"),
+            cloneof: block.text("cloneof"),
+            romof: block.text("romof"),
+            sampleof: block.text("sampleof"),
+            is_bios: is_resource || block.text("isbios").as_deref() == Some("yes"),
+            is_device: block.text("isdevice").as_deref() == Some("yes"),
+            ..Default::default()
+        };
+
+        for (key, value) in &block.0 {
+            let Value::Block(entry) = value else {
+                if let Value::Text(name) = value {
+                    match key.as_str() {
+                        "device_ref" => machine.device_refs.push(name.clone()),
+                        "sample" => machine.samples.push(name.clone()),
+                        _ => {}
+                    }
+                }
+                continue;
+            };
+            // Older DATs mark dump status with "flags" instead of "status"
+            let status = entry.text("status").or_else(|| entry.text("flags"));
+            match key.as_str() {
+                "rom" => {
+                    if let Some(name) = entry.text("name") {
+                        machine.roms.push(RomEntry {
+                            name,
+                            size: entry
+                                .text("size")
+                                .and_then(|size| size.parse().ok())
+                                .unwrap_or(0),
+                            crc: entry.text("crc").map(|crc| crc.to_lowercase()),
+                            sha1: entry.text("sha1").map(|sha1| sha1.to_lowercase()),
+                            merge: entry.text("merge"),
+                            bios: entry.text("bios"),
+                            region: entry.text("region"),
+                            optional: entry.text("optional").as_deref() == Some("yes"),
+                            status: DumpStatus::from_xml(status.as_deref()),
+                        });
+                    }
+                }
+                "disk" => {
+                    if let Some(name) = entry.text("name") {
+                        machine.disks.push(DiskEntry {
+                            name,
+                            sha1: entry.text("sha1").map(|sha1| sha1.to_lowercase()),
+                            merge: entry.text("merge"),
+                            region: entry.text("region"),
+                            index: entry.text("index").and_then(|index| index.parse().ok()),
+                            writable: entry.text("writable").as_deref() == Some("yes"),
+                            optional: entry.text("optional").as_deref() == Some("yes"),
+                            status: DumpStatus::from_xml(status.as_deref()),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        machine
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Word(String),
+}
+
+enum Value {
+    Text(String),
+    Block(Block),
+}
+
+struct Block(Vec<(String, Value)>);
+
+impl Block {
+    fn text(&self, key: &str) -> Option<String> {
+        self.0.iter().find_map(|(k, value)| match value {
+            Value::Text(text) if k == key => Some(text.clone()),
+            _ => None,
+        })
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' => {
+                chars.next();
+                let mut word = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(anyhow!("Unterminated quoted string")),
+                    }
+                }
+                tokens.push(Token::Word(word));
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Parse `( key value key ( ... ) ... )` starting at the opening parenthesis
+fn parse_block(tokens: &[Token], pos: &mut usize) -> Result<Block> {
+    if tokens.get(*pos) != Some(&Token::Open) {
+        return Err(anyhow!("Expected '('"));
+    }
+    *pos += 1;
+
+    let mut entries = Vec::new();
+    loop {
+        match tokens.get(*pos) {
+            Some(Token::Close) => {
+                *pos += 1;
+                return Ok(Block(entries));
+            }
+            Some(Token::Word(key)) => {
+                *pos += 1;
+                match tokens.get(*pos) {
+                    Some(Token::Open) => {
+                        entries.push((key.clone(), Value::Block(parse_block(tokens, pos)?)));
+                    }
+                    Some(Token::Word(value)) => {
+                        *pos += 1;
+                        entries.push((key.clone(), Value::Text(value.clone())));
+                    }
+                    // A key with no value right before the closing parenthesis
+                    Some(Token::Close) => {}
+                    None => return Err(anyhow!("Unexpected end of file")),
+                }
+            }
+            Some(Token::Open) => return Err(anyhow!("Unexpected '('")),
+            None => return Err(anyhow!("Unexpected end of file")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_pacman_family(dat: &DatFile) {
+        assert_eq!(dat.games.len(), 2);
+        let pacman = &dat.games[0];
+        assert_eq!(pacman.name, "pacman");
+        assert_eq!(pacman.description, "Pac-Man (Midway)");
+        assert_eq!(pacman.year, "1980");
+        assert_eq!(pacman.rom_set.roms.len(), 2);
+        assert_eq!(pacman.rom_set.roms[0].crc.as_deref(), Some("c1e6ab10"));
+        assert_eq!(pacman.rom_set.roms[0].size, 4096);
+        assert_eq!(pacman.rom_set.roms[1].status, DumpStatus::NoDump);
+
+        let clone = &dat.games[1];
+        assert_eq!(clone.parent.as_deref(), Some("pacman"));
+        assert_eq!(clone.rom_set.romof.as_deref(), Some("pacman"));
+        assert_eq!(clone.rom_set.roms[0].merge.as_deref(), Some("pacman.6e"));
+        assert_eq!(clone.rom_set.disks[0].name, "puckdisk");
+        assert_eq!(
+            clone.rom_set.disks[0].sha1.as_deref(),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        assert!(clone.requires_chd);
+    }
+
+    #[test]
+    fn parses_logiqx_xml() {
+        let xml = r#"<?xml version="1.0"?>
+            <!DOCTYPE datafile PUBLIC "-//Logiqx//DTD ROM Management Datafile//EN" "http://www.logiqx.com/Dats/datafile.dtd">
+            <datafile>
+                <header>
+                    <name>MAME</name>
+                    <description>MAME 0.229</description>
+                    <version>0.229</version>
+                </header>
+                <game name="pacman">
+                    <description>Pac-Man (Midway)</description>
+                    <year>1980</year>
+                    <manufacturer>Namco (Midway license)</manufacturer>
+                    <rom name="pacman.6e" size="4096" crc="C1E6AB10" sha1="E87E059C5BE45753F7E9F33DFF851F16D6751181"/>
+                    <rom name="pacman.prom" size="32" status="nodump"/>
+                </game>
+                <game name="puckman" cloneof="pacman" romof="pacman">
+                    <description>Puck Man &amp; Friends</description>
+                    <rom name="pm1.6e" merge="pacman.6e" size="4096" crc="c1e6ab10"/>
+                    <disk name="puckdisk" sha1="0123456789ABCDEF0123456789ABCDEF01234567"/>
+                </game>
+            </datafile>"#;
+
+        let dat = DatLoader::parse_logiqx(xml).unwrap();
+        assert_eq!(dat.name, "MAME");
+        assert_eq!(dat.description, "MAME 0.229");
+        assert_eq!(dat.version, "0.229");
+        assert_eq!(dat.games[1].description, "Puck Man & Friends");
+        assert_pacman_family(&dat);
+    }
+
+    #[test]
+    fn parses_clrmamepro_text() {
+        let text = r#"
+            clrmamepro (
+                name "MAME"
+                description "MAME 0.229"
+                version 0.229
+            )
+
+            game (
+                name pacman
+                description "Pac-Man (Midway)"
+                year 1980
+                manufacturer "Namco (Midway license)"
+                rom ( name pacman.6e size 4096 crc C1E6AB10 sha1 E87E059C5BE45753F7E9F33DFF851F16D6751181 )
+                rom ( name pacman.prom size 32 flags nodump )
+            )
+
+            game (
+                name puckman
+                description "Puck Man"
+                cloneof pacman
+                romof pacman
+                rom ( name pm1.6e merge pacman.6e size 4096 crc c1e6ab10 )
+                disk ( name puckdisk sha1 0123456789ABCDEF0123456789ABCDEF01234567 )
+            )
+        "#;
+
+        let dat = DatLoader::parse_clrmamepro(text).unwrap();
+        assert_eq!(dat.name, "MAME");
+        assert_eq!(dat.version, "0.229");
+        assert_pacman_family(&dat);
+
+        assert!(DatLoader::parse_clrmamepro("game ( name pacman").is_err());
+    }
+}
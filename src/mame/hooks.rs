@@ -0,0 +1,252 @@
+// src/mame/hooks.rs
+//! Runs launch hook commands through the system shell on a background
+//! thread. Template variables are filled in (shell-quoted) first, and a
+//! command still running after the timeout is killed.
+use crate::models::{AppConfig, Game, HookStage};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Values for `{rom}`, `{description}`, `{mame}`, `{orientation}` and `{category}`
+#[derive(Debug, Clone, Default)]
+pub struct HookVars {
+    pub rom: String,
+    pub description: String,
+    pub mame: String,
+    pub orientation: String,
+    pub category: String,
+}
+
+impl HookVars {
+    pub fn new(game: &Game, description: &str, config: &AppConfig) -> Self {
+        Self {
+            rom: game.name.clone(),
+            description: description.to_string(),
+            mame: config
+                .mame_executables
+                .get(config.selected_mame_index)
+                .map(|mame| mame.path.clone())
+                .unwrap_or_default(),
+            orientation: if game.rom_set.vertical {
+                "vertical"
+            } else {
+                "horizontal"
+            }
+            .to_string(),
+            category: game.category.clone(),
+        }
+    }
+
+    /// Fill in the variables, quoted so descriptions like
+    /// `Ms. Pac-Man (bootleg)` reach the command as one argument
+    pub fn expand(&self, template: &str) -> String {
+        [
+            ("{rom}", &self.rom),
+            ("{description}", &self.description),
+            ("{mame}", &self.mame),
+            ("{orientation}", &self.orientation),
+            ("{category}", &self.category),
+        ]
+        .iter()
+        .fold(template.to_string(), |command, (name, value)| {
+            command.replace(name, &shell_quote(value))
+        })
+    }
+}
+
+#[cfg(unix)]
+fn shell_quote(value: &str) -> String {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:".contains(c))
+    {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(not(unix))]
+fn shell_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// A hook command that did not succeed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookFailure {
+    pub command: String,
+    pub reason: String,
+}
+
+/// Hooks of one launch or exit, running in the background
+pub struct HookRun {
+    pub game: String,
+    pub stage: HookStage,
+    receiver: Receiver<Vec<HookFailure>>,
+}
+
+impl HookRun {
+    /// Run already expanded commands one after another
+    pub fn start(game: &str, stage: HookStage, commands: Vec<String>, timeout: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let failures = commands
+                .iter()
+                .filter_map(|command| run_command(command, timeout).err())
+                .collect();
+            let _ = sender.send(failures);
+        });
+        Self {
+            game: game.to_string(),
+            stage,
+            receiver,
+        }
+    }
+
+    /// The failures once every command has finished
+    pub fn try_finish(&self) -> Option<Vec<HookFailure>> {
+        match self.receiver.try_recv() {
+            Ok(failures) => Some(failures),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Vec::new()),
+        }
+    }
+}
+
+fn run_command(command: &str, timeout: Duration) -> Result<(), HookFailure> {
+    let fail = |reason: String| HookFailure {
+        command: command.to_string(),
+        reason,
+    };
+
+    #[cfg(unix)]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    };
+    #[cfg(not(unix))]
+    let mut shell = {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    };
+    let mut child = shell
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| fail(format!("could not start: {}", e)))?;
+
+    // A command that leaves a daemon behind keeps stderr open, so the
+    // output is only waited for briefly once the command itself is done
+    let (stderr_tx, stderr_rx) = mpsc::channel();
+    if let Some(mut stderr) = child.stderr.take() {
+        thread::spawn(move || {
+            let mut output = String::new();
+            let _ = stderr.read_to_string(&mut output);
+            let _ = stderr_tx.send(output);
+        });
+    }
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(fail(format!("timed out after {}s", timeout.as_secs())));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(fail(e.to_string())),
+        }
+    };
+    if status.success() {
+        return Ok(());
+    }
+
+    let stderr = stderr_rx
+        .recv_timeout(Duration::from_millis(200))
+        .unwrap_or_default();
+    let exit = match status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => "killed by a signal".to_string(),
+    };
+    Err(fail(
+        match stderr.lines().rfind(|line| !line.trim().is_empty()) {
+            Some(line) => format!("{}: {}", exit, line.trim()),
+            None => exit,
+        },
+    ))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::models::{HookCommands, LaunchHooks, RomStatus};
+
+    #[test]
+    fn expands_orders_and_runs_hooks() {
+        let game = Game {
+            name: "mspacman".to_string(),
+            description: "Ms. Pac-Man".to_string(),
+            status: RomStatus::Available,
+            category: "Maze".to_string(),
+            ..Game::default()
+        };
+        let mut hooks = LaunchHooks::default();
+        let scope = |before: &str, after: &str| HookCommands {
+            before: before.to_string(),
+            after: after.to_string(),
+        };
+        hooks.global = scope("global-on\n# comment\n", "global-off");
+        hooks
+            .categories
+            .insert("Maze".to_string(), scope("", "maze-off"));
+        hooks
+            .games
+            .insert("mspacman".to_string(), scope("game-on", "game-off"));
+        assert_eq!(
+            hooks.commands(&game, HookStage::BeforeLaunch),
+            vec!["global-on", "game-on"]
+        );
+        assert_eq!(
+            hooks.commands(&game, HookStage::AfterExit),
+            vec!["game-off", "maze-off", "global-off"]
+        );
+
+        let vars = HookVars {
+            rom: game.name.clone(),
+            description: "Ms. Pac-Man's".to_string(),
+            orientation: "vertical".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            vars.expand("leds {rom} {orientation} {description}"),
+            r"leds mspacman vertical 'Ms. Pac-Man'\''s'"
+        );
+
+        let run = HookRun::start(
+            "mspacman",
+            HookStage::BeforeLaunch,
+            vec![
+                "true".to_string(),
+                "echo broken >&2; exit 3".to_string(),
+                "sleep 5".to_string(),
+            ],
+            Duration::from_millis(300),
+        );
+        let failures = loop {
+            if let Some(failures) = run.try_finish() {
+                break failures;
+            }
+            thread::sleep(Duration::from_millis(20));
+        };
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].reason, "exit code 3: broken");
+        assert!(failures[1].reason.starts_with("timed out"));
+    }
+}
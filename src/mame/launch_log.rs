@@ -0,0 +1,631 @@
+// src/mame/launch_log.rs
+//! Captures what MAME prints while a game runs. Each launch's stdout and
+//! stderr are read on background threads, appended to one log file per
+//! MAMEUIx session and kept in memory for the log viewer. When the process
+//! exits its output is checked for the usual reasons a game fails to start.
+use chrono::Local;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ExitStatus};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// Launches kept in memory; older ones are only in the log file
+const MAX_LAUNCHES: usize = 50;
+/// Lines kept in memory per launch
+const MAX_LINES: usize = 5000;
+/// Session log files kept in the log directory
+const MAX_SESSION_FILES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub stream: OutputStream,
+    pub text: String,
+}
+
+/// How a MAME process ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExitInfo {
+    pub code: Option<i32>,
+    /// Signal that killed the process (Unix only)
+    pub signal: Option<i32>,
+}
+
+impl ExitInfo {
+    pub fn from_status(status: ExitStatus) -> Self {
+        #[cfg(unix)]
+        let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+        #[cfg(not(unix))]
+        let signal = None;
+        Self {
+            code: status.code(),
+            signal,
+        }
+    }
+
+    pub fn success(&self) -> bool {
+        self.code == Some(0) && self.signal.is_none()
+    }
+
+    /// Killed by a signal, or on Windows an NTSTATUS exception code such
+    /// as an access violation
+    pub fn crashed(&self) -> bool {
+        self.signal.is_some() || self.code.is_some_and(|code| (code as u32) >= 0xC000_0000)
+    }
+
+    pub fn describe(&self) -> String {
+        match (self.code, self.signal) {
+            (_, Some(signal)) => format!("killed by {}", signal_name(signal)),
+            (Some(code), None) => format!("exit code {}", code),
+            (None, None) => "unknown exit status".to_string(),
+        }
+    }
+}
+
+/// Common names for the signals a crashing emulator dies from
+pub fn signal_name(signal: i32) -> String {
+    let name = match signal {
+        4 => "SIGILL",
+        6 => "SIGABRT",
+        7 => "SIGBUS",
+        8 => "SIGFPE",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        15 => "SIGTERM",
+        _ => return format!("signal {}", signal),
+    };
+    format!("{} (signal {})", name, signal)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    MissingFiles,
+    UnknownOption,
+    UnknownSystem,
+    BgfxBackend,
+    Crash,
+    Other,
+}
+
+impl FailureKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            FailureKind::MissingFiles => "Required files are missing",
+            FailureKind::UnknownOption => "Unknown option",
+            FailureKind::UnknownSystem => "Unknown system",
+            FailureKind::BgfxBackend => "BGFX backend failed",
+            FailureKind::Crash => "MAME crashed",
+            FailureKind::Other => "MAME exited with an error",
+        }
+    }
+}
+
+/// Why a launch failed, worked out from its output and exit status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    pub kind: FailureKind,
+    /// Command-line option to blame, e.g. `-bgfx_backend`
+    pub option: Option<String>,
+    pub detail: String,
+    /// Index of the output line the failure was read from
+    pub line: Option<usize>,
+}
+
+/// A classified failure, ready to be reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchFailure {
+    pub launch_id: u64,
+    pub game: String,
+    pub classification: Classification,
+}
+
+impl LaunchFailure {
+    pub fn message(&self) -> String {
+        let classification = &self.classification;
+        let mut message = classification.kind.label().to_string();
+        if let Some(option) = &classification.option {
+            message.push_str(&format!(" — option ‘{}’", option));
+        }
+        if !classification.detail.is_empty() {
+            message.push_str(&format!(": {}", classification.detail));
+        }
+        message
+    }
+}
+
+/// Work out why MAME failed. Output only counts when the process did not
+/// exit cleanly, since MAME also warns about things it recovers from.
+pub fn classify(lines: &[LogLine], exit: ExitInfo) -> Option<Classification> {
+    if exit.success() {
+        return None;
+    }
+
+    let mut found: Vec<Classification> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let text = line.text.trim();
+        let lower = text.to_ascii_lowercase();
+        let at = |kind, option: Option<String>, detail: String| Classification {
+            kind,
+            option,
+            detail,
+            line: Some(index),
+        };
+
+        if let Some(pos) = lower.find("unknown option") {
+            let option = text[pos + "unknown option".len()..]
+                .trim_start_matches([':', ' '])
+                .split_whitespace()
+                .next()
+                .map(|option| option.trim_matches(['\'', '"', '`']).to_string())
+                .filter(|option| !option.is_empty());
+            let detail = match option {
+                Some(_) => String::new(),
+                None => text.to_string(),
+            };
+            found.push(at(FailureKind::UnknownOption, option, detail));
+        } else if lower.contains("required files are missing") {
+            let missing: Vec<&str> = lines
+                .iter()
+                .filter(|line| line.text.contains("NOT FOUND"))
+                .filter_map(|line| line.text.split_whitespace().next())
+                .collect();
+            let detail = if missing.is_empty() {
+                text.to_string()
+            } else {
+                missing.join(", ")
+            };
+            found.push(at(FailureKind::MissingFiles, None, detail));
+        } else if lower.contains("unknown system") {
+            found.push(at(FailureKind::UnknownSystem, None, text.to_string()));
+        } else if lower.contains("bgfx")
+            && [
+                "error",
+                "unable",
+                "unknown",
+                "failed",
+                "invalid",
+                "not supported",
+            ]
+            .iter()
+            .any(|word| lower.contains(word))
+        {
+            found.push(at(
+                FailureKind::BgfxBackend,
+                Some("-bgfx_backend".to_string()),
+                text.to_string(),
+            ));
+        } else if lower.contains("caught unhandled signal")
+            || lower.contains("segmentation fault")
+            || lower.contains("unhandled exception")
+        {
+            found.push(at(FailureKind::Crash, None, text.to_string()));
+        }
+    }
+
+    let priority = [
+        FailureKind::UnknownOption,
+        FailureKind::MissingFiles,
+        FailureKind::UnknownSystem,
+        FailureKind::BgfxBackend,
+        FailureKind::Crash,
+    ];
+    for kind in priority {
+        if let Some(classification) = found.iter().find(|c| c.kind == kind) {
+            return Some(classification.clone());
+        }
+    }
+
+    if let Some(signal) = exit.signal {
+        return Some(Classification {
+            kind: FailureKind::Crash,
+            option: None,
+            detail: format!("killed by {}", signal_name(signal)),
+            line: None,
+        });
+    }
+
+    // MAME's own exit codes, for when it printed nothing recognisable
+    let code = exit.code.unwrap_or(-1);
+    let kind = match code {
+        _ if exit.crashed() => FailureKind::Crash,
+        2 => FailureKind::MissingFiles,
+        5 => FailureKind::UnknownSystem,
+        _ => FailureKind::Other,
+    };
+    let last_error = lines
+        .iter()
+        .rposition(|line| line.stream == OutputStream::Stderr && !line.text.trim().is_empty());
+    let detail = match last_error {
+        Some(index) => format!("{} ({})", lines[index].text.trim(), exit.describe()),
+        None => exit.describe(),
+    };
+    Some(Classification {
+        kind,
+        option: None,
+        detail,
+        line: last_error,
+    })
+}
+
+/// Output and outcome of one launch
+#[derive(Debug, Clone)]
+pub struct LaunchRecord {
+    pub id: u64,
+    pub game: String,
+    pub started: String,
+    pub command_line: String,
+    pub lines: Vec<LogLine>,
+    pub exit: Option<ExitInfo>,
+    pub failure: Option<Classification>,
+    /// Ended from the session manager rather than by MAME itself
+    pub stopped: bool,
+    open_streams: u8,
+    classified: bool,
+}
+
+impl LaunchRecord {
+    pub fn is_running(&self) -> bool {
+        self.exit.is_none()
+    }
+
+    pub fn status(&self) -> String {
+        match (&self.exit, &self.failure) {
+            (None, _) => "running".to_string(),
+            (Some(_), _) if self.stopped => "stopped".to_string(),
+            (Some(_), Some(failure)) => failure.kind.label().to_string(),
+            (Some(exit), None) if exit.success() => "finished".to_string(),
+            (Some(exit), None) => exit.describe(),
+        }
+    }
+
+    /// Exited and all of its output has been read
+    fn is_complete(&self) -> bool {
+        self.exit.is_some() && self.open_streams == 0
+    }
+}
+
+enum LogEvent {
+    Line(u64, LogLine),
+    Closed(u64),
+}
+
+/// Collects the output of every launch in this session
+pub struct LaunchLogger {
+    dir: Option<PathBuf>,
+    path: Option<PathBuf>,
+    file: Option<File>,
+    sender: Sender<LogEvent>,
+    receiver: Receiver<LogEvent>,
+    launches: Vec<LaunchRecord>,
+    next_id: u64,
+}
+
+impl LaunchLogger {
+    /// Session logs go to the user's data directory (e.g. `~/.local/share/mameuix/logs`)
+    pub fn new() -> Self {
+        Self::in_dir(dirs::data_dir().map(|dir| dir.join("mameuix").join("logs")))
+    }
+
+    pub fn in_dir(dir: Option<PathBuf>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            dir,
+            path: None,
+            file: None,
+            sender,
+            receiver,
+            launches: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    /// This session's log file, once something was launched
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn launches(&self) -> &[LaunchRecord] {
+        &self.launches
+    }
+
+    /// Whether a launch is still running or its output is still being read
+    pub fn is_busy(&self) -> bool {
+        self.launches.iter().any(|record| !record.classified)
+    }
+
+    /// Start reading a freshly spawned MAME's output. The child must have
+    /// been spawned with piped stdout and stderr.
+    pub fn attach(&mut self, game: &str, command_line: &str, child: &mut Child) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut open_streams = 0;
+        if let Some(stdout) = child.stdout.take() {
+            self.read_stream(id, OutputStream::Stdout, stdout);
+            open_streams += 1;
+        }
+        if let Some(stderr) = child.stderr.take() {
+            self.read_stream(id, OutputStream::Stderr, stderr);
+            open_streams += 1;
+        }
+
+        let started = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        self.write(&format!(
+            "=== {} #{} {}: {}",
+            started, id, game, command_line
+        ));
+        self.launches.push(LaunchRecord {
+            id,
+            game: game.to_string(),
+            started,
+            command_line: command_line.to_string(),
+            lines: Vec::new(),
+            exit: None,
+            failure: None,
+            open_streams,
+            stopped: false,
+            classified: false,
+        });
+        if self.launches.len() > MAX_LAUNCHES {
+            let excess = self.launches.len() - MAX_LAUNCHES;
+            self.launches.drain(..excess);
+        }
+        id
+    }
+
+    /// Record how a launch ended. A launch the user stopped is not checked
+    /// for failures.
+    pub fn finish(&mut self, launch_id: u64, exit: ExitInfo, stopped: bool) {
+        if let Some(record) = self
+            .launches
+            .iter_mut()
+            .find(|record| record.id == launch_id)
+        {
+            record.exit = Some(exit);
+            record.stopped = stopped;
+        }
+    }
+
+    /// Take in the output read since the last call. Returns the launches
+    /// that ended with a recognised failure.
+    pub fn poll(&mut self) -> Vec<LaunchFailure> {
+        while let Ok(event) = self.receiver.try_recv() {
+            match event {
+                LogEvent::Line(id, line) => {
+                    if let Some(record) = self.launches.iter().find(|record| record.id == id) {
+                        let marker = match line.stream {
+                            OutputStream::Stdout => "out",
+                            OutputStream::Stderr => "err",
+                        };
+                        let entry = format!("{} #{} {}: {}", record.game, id, marker, line.text);
+                        self.write(&entry);
+                    }
+                    if let Some(record) = self.launches.iter_mut().find(|record| record.id == id)
+                        && record.lines.len() < MAX_LINES
+                    {
+                        record.lines.push(line);
+                    }
+                }
+                LogEvent::Closed(id) => {
+                    if let Some(record) = self.launches.iter_mut().find(|record| record.id == id) {
+                        record.open_streams = record.open_streams.saturating_sub(1);
+                    }
+                }
+            }
+        }
+
+        let mut failures = Vec::new();
+        let mut summaries = Vec::new();
+        for record in &mut self.launches {
+            if record.classified || !record.is_complete() {
+                continue;
+            }
+            let exit = record.exit.unwrap_or_default();
+            record.classified = true;
+            if !record.stopped {
+                record.failure = classify(&record.lines, exit);
+            }
+            summaries.push(format!(
+                "=== #{} {} ended: {}",
+                record.id,
+                record.game,
+                record.status()
+            ));
+            if let Some(classification) = &record.failure {
+                failures.push(LaunchFailure {
+                    launch_id: record.id,
+                    game: record.game.clone(),
+                    classification: classification.clone(),
+                });
+            }
+        }
+        for summary in summaries {
+            self.write(&summary);
+        }
+        failures
+    }
+
+    fn read_stream(&self, id: u64, stream: OutputStream, source: impl Read + Send + 'static) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let reader = BufReader::new(source);
+            for line in reader.split(b'\n') {
+                let Ok(bytes) = line else { break };
+                let text = String::from_utf8_lossy(&bytes).trim_end().to_string();
+                if sender
+                    .send(LogEvent::Line(id, LogLine { stream, text }))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            let _ = sender.send(LogEvent::Closed(id));
+        });
+    }
+
+    fn write(&mut self, entry: &str) {
+        if self.file.is_none() {
+            self.open_session_file();
+        }
+        if let Some(file) = &mut self.file
+            && let Err(e) = writeln!(file, "{}", entry)
+        {
+            eprintln!("Failed to write launch log: {}", e);
+            self.file = None;
+        }
+    }
+
+    fn open_session_file(&mut self) {
+        let Some(dir) = &self.dir else { return };
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Failed to create log directory {}: {}", dir.display(), e);
+            self.dir = None;
+            return;
+        }
+        prune_session_files(dir);
+
+        let path = dir.join(format!(
+            "session-{}.log",
+            Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        match OpenOptions::new().create(true).append(true).open(&path) {
+            Ok(file) => {
+                self.file = Some(file);
+                self.path = Some(path);
+            }
+            Err(e) => {
+                eprintln!("Failed to open launch log {}: {}", path.display(), e);
+                self.dir = None;
+            }
+        }
+    }
+}
+
+impl Default for LaunchLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keep the newest session logs, leaving room for the one about to be opened
+fn prune_session_files(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut sessions: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("session-") && name.ends_with(".log"))
+        })
+        .collect();
+    // Timestamped names sort oldest first
+    sessions.sort();
+    let keep = MAX_SESSION_FILES.saturating_sub(1);
+    if sessions.len() > keep {
+        let excess = sessions.len() - keep;
+        for path in sessions.drain(..excess) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(stderr: &[&str]) -> Vec<LogLine> {
+        stderr
+            .iter()
+            .map(|text| LogLine {
+                stream: OutputStream::Stderr,
+                text: text.to_string(),
+            })
+            .collect()
+    }
+
+    fn exit(code: i32) -> ExitInfo {
+        ExitInfo {
+            code: Some(code),
+            signal: None,
+        }
+    }
+
+    #[test]
+    fn classifies_common_failures() {
+        let missing = lines(&[
+            "pacman.6e NOT FOUND (tried in pacman puckman)",
+            "pacman.6f NOT FOUND (tried in pacman puckman)",
+            "Required files are missing, the machine cannot be run.",
+        ]);
+        let failure = classify(&missing, exit(2)).unwrap();
+        assert_eq!(failure.kind, FailureKind::MissingFiles);
+        assert_eq!(failure.detail, "pacman.6e, pacman.6f");
+        assert_eq!(failure.line, Some(2));
+
+        let option = classify(&lines(&["Error: unknown option: -bogus"]), exit(6)).unwrap();
+        assert_eq!(option.kind, FailureKind::UnknownOption);
+        assert_eq!(option.option.as_deref(), Some("-bogus"));
+
+        let bgfx = lines(&["Unable to find BGFX backend 'metal'", "Fatal error"]);
+        let failure = classify(&bgfx, exit(3)).unwrap();
+        assert_eq!(failure.kind, FailureKind::BgfxBackend);
+        assert_eq!(failure.option.as_deref(), Some("-bgfx_backend"));
+
+        let crash = ExitInfo {
+            code: None,
+            signal: Some(11),
+        };
+        let failure = classify(&[], crash).unwrap();
+        assert_eq!(failure.kind, FailureKind::Crash);
+        assert!(failure.detail.contains("SIGSEGV"));
+
+        // Warnings from a clean exit are not failures
+        assert_eq!(classify(&bgfx, exit(0)), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn captures_output_into_the_session_log() {
+        use std::process::{Command, Stdio};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut logger = LaunchLogger::in_dir(Some(dir.path().to_path_buf()));
+        let mut child = Command::new("sh")
+            .args([
+                "-c",
+                "echo hello; echo 'Error: unknown option: -nope' >&2; exit 6",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let id = logger.attach("pacman", "mame pacman -nope", &mut child);
+        let status = child.wait().unwrap();
+        logger.finish(id, ExitInfo::from_status(status), false);
+
+        let mut failures = Vec::new();
+        for _ in 0..200 {
+            failures.extend(logger.poll());
+            if !failures.is_empty() {
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].classification.option.as_deref(), Some("-nope"));
+
+        let log = fs::read_to_string(logger.path().unwrap()).unwrap();
+        assert!(log.contains("pacman #1 out: hello"));
+        assert!(log.contains("ended: Unknown option"));
+    }
+}
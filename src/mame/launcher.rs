@@ -1,10 +1,63 @@
-use crate::models::AppConfig;
-use std::process::{Child, Command};
+use crate::mame::SoftwareLaunch;
+use crate::models::{AppConfig, Game, GameProperties, PropertyLevel};
+use std::process::{Child, Command, Stdio};
+
+/// A running MAME whose stdout and stderr are piped for the launch log
+pub struct MameProcess {
+    pub child: Child,
+    pub command_line: String,
+}
 
 pub fn launch_game(
+    game: &Game,
+    config: &AppConfig,
+) -> Result<MameProcess, Box<dyn std::error::Error>> {
+    launch_game_with_args(game, &[], config)
+}
+
+/// Run a game with extra switches after its name, e.g. `-record <file>`
+pub fn launch_game_with_args(
+    game: &Game,
+    extra_args: &[String],
+    config: &AppConfig,
+) -> Result<MameProcess, Box<dyn std::error::Error>> {
+    launch_with_media(
+        &game.name,
+        extra_args,
+        &effective_properties(game, config),
+        config,
+    )
+}
+
+/// Run a system with software-list media, e.g. `a2600 combat` or
+/// `amiga -flop1 lemmings:flop1 -flop2 lemmings:flop2`
+pub fn launch_software(
+    launch: &SoftwareLaunch,
+    system: &Game,
+    config: &AppConfig,
+) -> Result<MameProcess, Box<dyn std::error::Error>> {
+    launch_with_media(
+        &launch.system,
+        &launch.media_args,
+        &effective_properties(system, config),
+        config,
+    )
+}
+
+/// The defaults with the orientation, source, parent and game overrides on top
+pub fn effective_properties(game: &Game, config: &AppConfig) -> GameProperties {
+    config
+        .property_layers
+        .resolve(&config.default_game_properties, game, PropertyLevel::Game)
+        .properties
+}
+
+fn launch_with_media(
     rom_name: &str,
+    media_args: &[String],
+    game_properties: &GameProperties,
     config: &AppConfig,
-) -> Result<Child, Box<dyn std::error::Error>> {
+) -> Result<MameProcess, Box<dyn std::error::Error>> {
     if let Some(mame) = config.mame_executables.get(config.selected_mame_index) {
         let mut cmd = Command::new(&mame.path);
 
@@ -52,15 +105,8 @@ pub fn launch_game(
                 .arg(comment_path.to_string_lossy().to_string());
         }
 
-        // Get game-specific properties or use defaults
-        let game_properties = config
-            .game_properties
-            .get(rom_name)
-            .cloned()
-            .unwrap_or_else(|| config.default_game_properties.clone());
-
-        // Apply game properties directly
-        apply_game_properties(&mut cmd, &game_properties);
+        // Apply the game's effective properties
+        apply_game_properties(&mut cmd, game_properties);
 
         // IMPORTANT: High score support
         // Enable the hiscore plugin if hiscore.dat is configured
@@ -179,25 +225,14 @@ pub fn launch_game(
         // Note: gameinit.dat support is handled automatically by MAME
         // No additional command line arguments needed
 
-        // Custom arguments from default game properties
-        if !config
-            .default_game_properties
-            .miscellaneous
-            .custom_args
-            .is_empty()
-        {
-            for arg in config
-                .default_game_properties
-                .miscellaneous
-                .custom_args
-                .split_whitespace()
-            {
-                cmd.arg(arg);
-            }
+        // Custom arguments from whichever level set them last
+        for arg in game_properties.miscellaneous.custom_args.split_whitespace() {
+            cmd.arg(arg);
         }
 
-        // Finally, add the ROM name
+        // Finally, add the ROM name and any software to mount
         cmd.arg(rom_name);
+        cmd.args(media_args);
 
         // Debug: Print the full command
         #[cfg(debug_assertions)]
@@ -206,7 +241,23 @@ pub fn launch_game(
             println!("{:?}", cmd);
         }
 
-        Ok(cmd.spawn()?)
+        let command_line = std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|arg| {
+                let arg = arg.to_string_lossy();
+                if arg.contains(' ') {
+                    format!("\"{}\"", arg)
+                } else {
+                    arg.into_owned()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+        Ok(MameProcess {
+            child,
+            command_line,
+        })
     } else {
         Err("No MAME executable configured".into())
     }
@@ -496,6 +547,10 @@ fn apply_game_properties(cmd: &mut Command, props: &crate::models::GamePropertie
         crate::models::game_properties::Resolution::Custom(width, height) => {
             cmd.arg("-resolution").arg(format!("{}x{}", width, height));
         }
+        crate::models::game_properties::Resolution::CustomRefresh(width, height, refresh) => {
+            cmd.arg("-resolution")
+                .arg(format!("{}x{}@{}", width, height, refresh));
+        }
         crate::models::game_properties::Resolution::Auto => {
             // Use auto resolution
         }
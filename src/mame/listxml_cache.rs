@@ -0,0 +1,199 @@
+// src/mame/listxml_cache.rs
+use crate::models::{Game, MameExecutable};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Bump whenever the cached `Game` layout changes so stale files are ignored.
+const CACHE_FORMAT_VERSION: u32 = 7;
+
+/// Identity of the MAME binary (and catver.ini) a cached -listxml parse came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListXmlCacheKey {
+    pub executable_path: PathBuf,
+    pub executable_size: u64,
+    pub executable_mtime: u64,
+    pub mame_version: String,
+    /// Categories are baked into the parsed games, so catver.ini is part of the key.
+    pub catver_path: Option<PathBuf>,
+    pub catver_mtime: Option<u64>,
+}
+
+impl ListXmlCacheKey {
+    pub fn from_executable(mame: &MameExecutable, catver_path: Option<&Path>) -> Result<Self> {
+        let metadata = fs::metadata(&mame.path)
+            .with_context(|| format!("Failed to stat MAME executable {}", mame.path))?;
+
+        Ok(Self {
+            executable_path: PathBuf::from(&mame.path),
+            executable_size: metadata.len(),
+            executable_mtime: mtime_secs(&metadata).unwrap_or(0),
+            mame_version: mame.version.clone(),
+            catver_path: catver_path.map(Path::to_path_buf),
+            catver_mtime: catver_path
+                .and_then(|path| fs::metadata(path).ok())
+                .and_then(|metadata| mtime_secs(&metadata)),
+        })
+    }
+
+    /// Named by a CRC32 of the executable path, which unlike `DefaultHasher`
+    /// stays the same across Rust releases
+    fn file_name(&self) -> String {
+        let path = self.executable_path.to_string_lossy();
+        format!("listxml-{:08x}.json", crc32fast::hash(path.as_bytes()))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ListXmlCacheFile {
+    format_version: u32,
+    key: ListXmlCacheKey,
+    games: Vec<Game>,
+    manufacturers: Vec<String>,
+}
+
+/// Parsed -listxml data restored from disk.
+pub struct CachedMachineData {
+    pub games: Vec<Game>,
+    pub manufacturers: Vec<String>,
+}
+
+/// On-disk cache of parsed `mame -listxml` output, one file per MAME executable.
+pub struct ListXmlCache {
+    cache_dir: PathBuf,
+}
+
+impl ListXmlCache {
+    /// Cache stored under the user's cache directory (e.g. `~/.cache/mameuix`).
+    pub fn new() -> Option<Self> {
+        dirs::cache_dir().map(|dir| Self::with_dir(dir.join("mameuix")))
+    }
+
+    pub fn with_dir(cache_dir: PathBuf) -> Self {
+        Self { cache_dir }
+    }
+
+    /// Load cached machine data if it was produced by the same binary.
+    pub fn load(&self, key: &ListXmlCacheKey) -> Option<CachedMachineData> {
+        let path = self.cache_dir.join(key.file_name());
+        let contents = fs::read(&path).ok()?;
+
+        let cached: ListXmlCacheFile = match serde_json::from_slice(&contents) {
+            Ok(cached) => cached,
+            Err(e) => {
+                eprintln!(
+                    "Ignoring unreadable listxml cache {}: {}",
+                    path.display(),
+                    e
+                );
+                return None;
+            }
+        };
+
+        if cached.format_version != CACHE_FORMAT_VERSION || &cached.key != key {
+            return None;
+        }
+
+        Some(CachedMachineData {
+            games: cached.games,
+            manufacturers: cached.manufacturers,
+        })
+    }
+
+    pub fn store(
+        &self,
+        key: &ListXmlCacheKey,
+        games: &[Game],
+        manufacturers: &[String],
+    ) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir).with_context(|| {
+            format!(
+                "Failed to create cache directory {}",
+                self.cache_dir.display()
+            )
+        })?;
+
+        let cached = ListXmlCacheFile {
+            format_version: CACHE_FORMAT_VERSION,
+            key: key.clone(),
+            games: games.to_vec(),
+            manufacturers: manufacturers.to_vec(),
+        };
+
+        // Write to a temp file first so a crash mid-write never leaves a torn cache.
+        let path = self.cache_dir.join(key.file_name());
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec(&cached)?)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Drop the cache file for this executable, forcing the next load to rescan.
+    pub fn invalidate(&self, key: &ListXmlCacheKey) {
+        let _ = fs::remove_file(self.cache_dir.join(key.file_name()));
+    }
+}
+
+fn mtime_secs(metadata: &fs::Metadata) -> Option<u64> {
+    metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_key(version: &str) -> ListXmlCacheKey {
+        ListXmlCacheKey {
+            executable_path: PathBuf::from("/usr/bin/mame"),
+            executable_size: 1234,
+            executable_mtime: 42,
+            mame_version: version.to_string(),
+            catver_path: None,
+            catver_mtime: None,
+        }
+    }
+
+    fn sample_game() -> Game {
+        Game {
+            name: "pacman".to_string(),
+            description: "Pac-Man".to_string(),
+            manufacturer: "Namco".to_string(),
+            year: "1980".to_string(),
+            driver: "pacman".to_string(),
+            driver_status: "good".to_string(),
+            category: "Maze".to_string(),
+            ..Game::default()
+        }
+    }
+
+    #[test]
+    fn round_trips_and_rejects_changed_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ListXmlCache::with_dir(dir.path().to_path_buf());
+        let key = sample_key("0.270");
+
+        cache
+            .store(&key, &[sample_game()], &["Namco".to_string()])
+            .unwrap();
+
+        let loaded = cache.load(&key).unwrap();
+        assert_eq!(loaded.games.len(), 1);
+        assert_eq!(loaded.games[0].name, "pacman");
+        assert_eq!(loaded.manufacturers, vec!["Namco".to_string()]);
+
+        assert!(cache.load(&sample_key("0.271")).is_none());
+
+        cache.invalidate(&key);
+        assert!(cache.load(&key).is_none());
+    }
+}
@@ -1,9 +1,27 @@
 mod category_loader;
+mod dat_loader;
+mod hooks;
+mod launch_log;
 mod launcher;
+mod listxml_cache;
+mod recordings;
 mod scanner;
+mod sessions;
+mod software_launch;
 mod software_list;
+mod verifyroms;
 
 pub use category_loader::CategoryLoader;
-pub use launcher::{launch_game, verify_plugin_support};
+pub use dat_loader::{DatFile, DatLoader};
+pub use hooks::{HookFailure, HookRun, HookVars};
+pub use launch_log::{LaunchLogger, LaunchRecord, OutputStream};
+pub use launcher::{launch_game, launch_game_with_args, launch_software, verify_plugin_support};
+pub use listxml_cache::{ListXmlCache, ListXmlCacheKey};
+pub use recordings::{
+    Recording, delete_recording, list_recordings, new_recording_name, rename_recording,
+};
 pub use scanner::GameScanner;
+pub use sessions::{FinishedSession, GameSession, SessionManager};
+pub use software_launch::{SoftwareLaunch, compatible_systems};
 pub use software_list::{SoftwareEntry, SoftwareListLoader, SoftwareListSummary};
+pub use verifyroms::{VerifyCommand, VerifyPool, default_verify_workers};
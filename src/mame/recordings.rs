@@ -0,0 +1,176 @@
+// src/mame/recordings.rs
+//! Input recordings (`.inp` files) in MAME's input directory. A file is
+//! matched to its game by the system name in its header, so renamed
+//! recordings still show up under the right game.
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const INP_MAGIC: &[u8; 8] = b"MAMEINP\0";
+/// Header layout: magic, start time, version, then a 12-byte system name
+const SYSNAME_OFFSET: usize = 20;
+const SYSNAME_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    pub path: PathBuf,
+    /// File name, which is what `-playback` takes
+    pub file_name: String,
+    pub size: u64,
+    pub modified: Option<DateTime<Local>>,
+}
+
+/// System a recording was made on, from its header
+pub fn recorded_system(path: &Path) -> Option<String> {
+    let mut header = [0u8; SYSNAME_OFFSET + SYSNAME_LEN];
+    File::open(path).ok()?.read_exact(&mut header).ok()?;
+    if &header[..INP_MAGIC.len()] != INP_MAGIC {
+        return None;
+    }
+    let name = &header[SYSNAME_OFFSET..];
+    let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+    Some(String::from_utf8_lossy(&name[..end]).into_owned())
+}
+
+/// Recordings of a game, newest first. Files without a readable header
+/// count when their name starts with the game's.
+pub fn list_recordings(dir: &Path, game: &str) -> Vec<Recording> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut recordings: Vec<Recording> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("inp"))
+        })
+        .filter_map(|entry| {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let belongs = match recorded_system(&path) {
+                Some(system) => system == game,
+                None => file_name
+                    .strip_prefix(game)
+                    .is_some_and(|rest| rest.starts_with(['-', '.', '_'])),
+            };
+            if !belongs {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            Some(Recording {
+                path,
+                file_name,
+                size: metadata.len(),
+                modified: metadata.modified().ok().map(DateTime::<Local>::from),
+            })
+        })
+        .collect();
+    recordings.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then(a.file_name.cmp(&b.file_name))
+    });
+    recordings
+}
+
+/// A new file name that is not taken yet, e.g. `pacman-20261017-141502.inp`
+pub fn new_recording_name(dir: &Path, game: &str) -> String {
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let mut name = format!("{}-{}.inp", game, stamp);
+    let mut n = 2;
+    while dir.join(&name).exists() {
+        name = format!("{}-{}-{}.inp", game, stamp, n);
+        n += 1;
+    }
+    name
+}
+
+/// Rename within the input directory, keeping the `.inp` extension
+pub fn rename_recording(recording: &Recording, new_name: &str) -> Result<PathBuf> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() || new_name.contains(['/', '\\']) || new_name.starts_with('.') {
+        bail!("'{}' is not a valid file name", new_name);
+    }
+    let file_name = if new_name.to_ascii_lowercase().ends_with(".inp") {
+        new_name.to_string()
+    } else {
+        format!("{}.inp", new_name)
+    };
+    let target = recording.path.with_file_name(&file_name);
+    if target == recording.path {
+        return Ok(target);
+    }
+    if target.exists() {
+        bail!("{} already exists", file_name);
+    }
+    fs::rename(&recording.path, &target)
+        .with_context(|| format!("Failed to rename {}", recording.file_name))?;
+    Ok(target)
+}
+
+pub fn delete_recording(recording: &Recording) -> Result<()> {
+    fs::remove_file(&recording.path)
+        .with_context(|| format!("Failed to delete {}", recording.file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_inp(dir: &Path, file_name: &str, system: &str) {
+        let mut data = Vec::from(&INP_MAGIC[..]);
+        data.resize(SYSNAME_OFFSET, 0);
+        let mut name = system.as_bytes().to_vec();
+        name.resize(SYSNAME_LEN, 0);
+        data.extend(name);
+        data.extend([0u8; 32]);
+        fs::write(dir.join(file_name), data).unwrap();
+    }
+
+    #[test]
+    fn lists_renames_and_deletes_recordings() {
+        let dir = tempfile::tempdir().unwrap();
+        write_inp(dir.path(), "pacman-1.inp", "pacman");
+        write_inp(dir.path(), "highscore.inp", "pacman");
+        write_inp(dir.path(), "pacman-galaga.inp", "galaga");
+        fs::write(dir.path().join("pacman-old.inp"), b"headerless").unwrap();
+        fs::write(dir.path().join("pacman.cfg"), b"").unwrap();
+
+        let names = |game| -> Vec<String> {
+            let mut names: Vec<String> = list_recordings(dir.path(), game)
+                .into_iter()
+                .map(|recording| recording.file_name)
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(
+            names("pacman"),
+            vec!["highscore.inp", "pacman-1.inp", "pacman-old.inp"]
+        );
+        assert_eq!(names("galaga"), vec!["pacman-galaga.inp"]);
+
+        let name = new_recording_name(dir.path(), "pacman");
+        assert!(name.starts_with("pacman-") && name.ends_with(".inp"));
+
+        let recording = list_recordings(dir.path(), "pacman")
+            .into_iter()
+            .find(|recording| recording.file_name == "highscore.inp")
+            .unwrap();
+        assert!(rename_recording(&recording, "pacman-1").is_err());
+        assert!(rename_recording(&recording, "../escape").is_err());
+        let renamed = rename_recording(&recording, "world record").unwrap();
+        assert_eq!(recorded_system(&renamed).as_deref(), Some("pacman"));
+
+        let renamed = list_recordings(dir.path(), "pacman")
+            .into_iter()
+            .find(|recording| recording.file_name == "world record.inp")
+            .unwrap();
+        delete_recording(&renamed).unwrap();
+        assert_eq!(names("pacman"), vec!["pacman-1.inp", "pacman-old.inp"]);
+    }
+}
@@ -1,6 +1,9 @@
 // src/mame/scanner.rs
 use crate::mame::CategoryLoader;
-use crate::models::{Game, RomSetType};
+use crate::models::{
+    DiskEntry, DumpStatus, Game, MachineRomSet, MediaSlot, RomEntry, SoftwareListRef,
+    SoftwareListStatus,
+};
 use anyhow::{Context, Result};
 use quick_xml::Reader;
 use quick_xml::events::{BytesStart, Event};
@@ -14,6 +17,7 @@ struct ParsedMachine {
     year: Option<String>,
     manufacturer: Option<String>,
     parent: Option<String>,
+    romof: Option<String>,
     source_file: Option<String>,
     driver_name: Option<String>,
     driver_status: Option<String>,
@@ -22,6 +26,17 @@ struct ParsedMachine {
     is_bios: bool,
     disk_name: Option<String>,
     harddisk_device_chd: bool,
+    roms: Vec<RomEntry>,
+    disks: Vec<DiskEntry>,
+    device_refs: Vec<String>,
+    sampleof: Option<String>,
+    samples: Vec<String>,
+    software_lists: Vec<SoftwareListRef>,
+    media_slots: Vec<MediaSlot>,
+    /// Orientation of the first `<display>`; later screens don't count
+    vertical: Option<bool>,
+    /// Interfaces of the `<device>` being read, for its `<instance>`
+    device_interfaces: Vec<String>,
 }
 
 pub struct GameScanner {
@@ -108,9 +123,19 @@ impl GameScanner {
                                     Some(Self::read_text_unescaped(&mut reader, event.name())?);
                             }
                             b"driver" => Self::read_driver_attrs(machine, &event, &reader)?,
+                            b"rom" => Self::read_rom_attrs(machine, &event, &reader)?,
                             b"disk" => Self::read_disk_attrs(machine, &event, &reader)?,
                             b"device" => Self::read_device_attrs(machine, &event, &reader)?,
+                            b"device_ref" => Self::read_device_ref_attrs(machine, &event, &reader)?,
+                            b"sample" => machine
+                                .samples
+                                .extend(Self::xml_attr(&event, b"name", &reader)?),
                             b"extension" => Self::read_extension_attrs(machine, &event, &reader)?,
+                            b"instance" => Self::read_instance_attrs(machine, &event, &reader)?,
+                            b"display" => Self::read_display_attrs(machine, &event, &reader)?,
+                            b"softwarelist" => {
+                                Self::read_softwarelist_attrs(machine, &event, &reader)?
+                            }
                             _ => {}
                         }
                     }
@@ -119,9 +144,19 @@ impl GameScanner {
                     if let Some(machine) = current_machine.as_mut() {
                         match event.name().as_ref() {
                             b"driver" => Self::read_driver_attrs(machine, &event, &reader)?,
+                            b"rom" => Self::read_rom_attrs(machine, &event, &reader)?,
                             b"disk" => Self::read_disk_attrs(machine, &event, &reader)?,
                             b"device" => Self::read_device_attrs(machine, &event, &reader)?,
+                            b"device_ref" => Self::read_device_ref_attrs(machine, &event, &reader)?,
+                            b"sample" => machine
+                                .samples
+                                .extend(Self::xml_attr(&event, b"name", &reader)?),
                             b"extension" => Self::read_extension_attrs(machine, &event, &reader)?,
+                            b"instance" => Self::read_instance_attrs(machine, &event, &reader)?,
+                            b"display" => Self::read_display_attrs(machine, &event, &reader)?,
+                            b"softwarelist" => {
+                                Self::read_softwarelist_attrs(machine, &event, &reader)?
+                            }
                             _ => {}
                         }
                     }
@@ -147,6 +182,8 @@ impl GameScanner {
         Ok(ParsedMachine {
             name: Self::xml_attr(event, b"name", reader)?,
             parent: Self::xml_attr(event, b"cloneof", reader)?,
+            romof: Self::xml_attr(event, b"romof", reader)?,
+            sampleof: Self::xml_attr(event, b"sampleof", reader)?,
             source_file: Self::xml_attr(event, b"sourcefile", reader)?,
             is_device: Self::xml_attr(event, b"isdevice", reader)?.as_deref() == Some("yes"),
             is_bios: Self::xml_attr(event, b"isbios", reader)?.as_deref() == Some("yes"),
@@ -168,14 +205,54 @@ impl GameScanner {
         Ok(())
     }
 
+    fn read_rom_attrs(
+        machine: &mut ParsedMachine,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        let Some(name) = Self::xml_attr(event, b"name", reader)? else {
+            return Ok(());
+        };
+
+        machine.roms.push(RomEntry {
+            name,
+            size: Self::xml_attr(event, b"size", reader)?
+                .and_then(|size| size.parse().ok())
+                .unwrap_or(0),
+            crc: Self::xml_attr(event, b"crc", reader)?.map(|crc| crc.to_lowercase()),
+            sha1: Self::xml_attr(event, b"sha1", reader)?.map(|sha1| sha1.to_lowercase()),
+            merge: Self::xml_attr(event, b"merge", reader)?,
+            bios: Self::xml_attr(event, b"bios", reader)?,
+            region: Self::xml_attr(event, b"region", reader)?,
+            optional: Self::xml_attr(event, b"optional", reader)?.as_deref() == Some("yes"),
+            status: DumpStatus::from_xml(Self::xml_attr(event, b"status", reader)?.as_deref()),
+        });
+        Ok(())
+    }
+
     fn read_disk_attrs(
         machine: &mut ParsedMachine,
         event: &BytesStart<'_>,
         reader: &Reader<&[u8]>,
     ) -> Result<()> {
+        let Some(name) = Self::xml_attr(event, b"name", reader)? else {
+            return Ok(());
+        };
+
         if machine.disk_name.is_none() {
-            machine.disk_name = Self::xml_attr(event, b"name", reader)?;
+            machine.disk_name = Some(name.clone());
         }
+
+        machine.disks.push(DiskEntry {
+            name,
+            sha1: Self::xml_attr(event, b"sha1", reader)?.map(|sha1| sha1.to_lowercase()),
+            merge: Self::xml_attr(event, b"merge", reader)?,
+            region: Self::xml_attr(event, b"region", reader)?,
+            index: Self::xml_attr(event, b"index", reader)?.and_then(|index| index.parse().ok()),
+            writable: Self::xml_attr(event, b"writable", reader)?.as_deref() == Some("yes"),
+            optional: Self::xml_attr(event, b"optional", reader)?.as_deref() == Some("yes"),
+            status: DumpStatus::from_xml(Self::xml_attr(event, b"status", reader)?.as_deref()),
+        });
         Ok(())
     }
 
@@ -187,6 +264,67 @@ impl GameScanner {
         if Self::xml_attr(event, b"type", reader)?.as_deref() == Some("harddisk") {
             machine.harddisk_device_chd = true;
         }
+        machine.device_interfaces = Self::xml_attr(event, b"interface", reader)?
+            .map(|interfaces| interfaces.split(',').map(str::to_string).collect())
+            .unwrap_or_default();
+        Ok(())
+    }
+
+    fn read_softwarelist_attrs(
+        machine: &mut ParsedMachine,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        if let Some(name) = Self::xml_attr(event, b"name", reader)? {
+            machine.software_lists.push(SoftwareListRef {
+                name,
+                status: SoftwareListStatus::from_xml(
+                    Self::xml_attr(event, b"status", reader)?.as_deref(),
+                ),
+                filter: Self::xml_attr(event, b"filter", reader)?,
+            });
+        }
+        Ok(())
+    }
+
+    fn read_display_attrs(
+        machine: &mut ParsedMachine,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        if machine.vertical.is_none() {
+            let rotate = Self::xml_attr(event, b"rotate", reader)?;
+            machine.vertical = Some(matches!(rotate.as_deref(), Some("90" | "270")));
+        }
+        Ok(())
+    }
+
+    fn read_instance_attrs(
+        machine: &mut ParsedMachine,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        if let Some(briefname) = Self::xml_attr(event, b"briefname", reader)?
+            && !machine.device_interfaces.is_empty()
+        {
+            machine.media_slots.push(MediaSlot {
+                briefname,
+                interfaces: machine.device_interfaces.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn read_device_ref_attrs(
+        machine: &mut ParsedMachine,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        if let Some(name) = Self::xml_attr(event, b"name", reader)?
+            && !machine.device_refs.contains(&name)
+        {
+            machine.device_refs.push(name);
+        }
         Ok(())
     }
 
@@ -234,7 +372,21 @@ impl GameScanner {
             .manufacturer
             .unwrap_or_else(|| "Unknown".to_string());
         let source_file = machine.source_file.unwrap_or_else(|| "unknown".to_string());
+        let vertical = machine.vertical.unwrap_or(false);
         let parent = machine.parent;
+        let rom_set = MachineRomSet {
+            cloneof: parent.clone(),
+            romof: machine.romof,
+            roms: machine.roms,
+            disks: machine.disks,
+            device_refs: machine.device_refs,
+            sampleof: machine.sampleof,
+            samples: machine.samples,
+            software_lists: machine.software_lists,
+            media_slots: machine.media_slots,
+            source_file: Some(source_file.clone()),
+            vertical,
+        };
 
         let (requires_chd, chd_name) = if let Some(disk_name) = machine.disk_name {
             (true, Some(disk_name))
@@ -274,6 +426,8 @@ impl GameScanner {
             requires_chd,
             chd_name,
             verification_status: None,
+            sample_status: Default::default(),
+            rom_set,
         })
     }
 
@@ -372,6 +526,8 @@ impl GameScanner {
             requires_chd,
             chd_name,
             verification_status: None,
+            sample_status: Default::default(),
+            rom_set: MachineRomSet::default(),
         })
     }
 
@@ -691,65 +847,6 @@ impl GameScanner {
 
         false
     }
-
-    /// Detect ROM set type based on collection analysis
-    pub fn detect_rom_set_type(&self, games: &[Game]) -> RomSetType {
-        if games.is_empty() {
-            return RomSetType::Unknown;
-        }
-
-        // Count total games vs parent games
-        let total_games = games.len();
-        let parent_games = games.iter().filter(|g| !g.is_clone).count();
-        let clone_games = total_games - parent_games;
-
-        // Calculate clone ratio
-        let clone_ratio = if total_games > 0 {
-            clone_games as f64 / total_games as f64
-        } else {
-            0.0
-        };
-
-        // Analyze clone patterns to determine ROM set type
-        let mut clone_groups = std::collections::HashMap::new();
-
-        for game in games {
-            if game.is_clone
-                && let Some(parent) = &game.parent
-            {
-                clone_groups
-                    .entry(parent.clone())
-                    .or_insert_with(Vec::new)
-                    .push(game.name.clone());
-            }
-        }
-
-        // Count average clones per parent
-        let avg_clones_per_parent = if !clone_groups.is_empty() {
-            clone_groups
-                .values()
-                .map(|clones| clones.len())
-                .sum::<usize>() as f64
-                / clone_groups.len() as f64
-        } else {
-            0.0
-        };
-
-        // Detection logic based on RomVault documentation and clone patterns
-        if clone_ratio > 0.4 && avg_clones_per_parent > 2.0 {
-            // High clone ratio with many clones per parent = Non-Merged
-            RomSetType::NonMerged
-        } else if clone_ratio > 0.2 && avg_clones_per_parent > 1.0 {
-            // Moderate clone ratio = Split
-            RomSetType::Split
-        } else if clone_ratio < 0.1 {
-            // Low clone ratio = likely Merged
-            RomSetType::Merged
-        } else {
-            // Default to Split if uncertain
-            RomSetType::Split
-        }
-    }
 }
 
 /// Scanner alternatif yang menggunakan streaming untuk instalasi MAME sangat besar
@@ -831,6 +928,98 @@ impl StreamingGameScanner {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parses_rom_and_disk_manifests() {
+        let xml = r#"
+            <mame>
+                <machine name="kinst2" cloneof="kinst" romof="kinst" sourcefile="kinst.cpp">
+                    <description>Killer Instinct 2</description>
+                    <rom name="ki2_l14.u98" size="524288" crc="6A0B1F2E" sha1="ABCDEF0123"/>
+                    <rom name="ki-u10.bin" merge="ki-u10.bin" size="2097152" crc="3b3a3c2b" region="snd"/>
+                    <rom name="pal.u1" size="260" status="nodump" optional="yes"/>
+                    <rom name="bios.bin" bios="v1" size="1024" crc="00000001" status="baddump"/>
+                    <disk name="kinst2" sha1="E4C34A14" region="ide:0:hdd" index="0" writable="yes"/>
+                    <device_ref name="ide_harddisk"/>
+                    <device_ref name="ide_harddisk"/>
+                    <display tag="screen" type="raster" rotate="0"/>
+                    <display tag="aux" type="raster" rotate="90"/>
+                </machine>
+                <machine name="spacfury" sampleof="spacfury" sourcefile="segag80v.cpp">
+                    <description>Space Fury</description>
+                    <sample name="craft"/>
+                    <sample name="xplode"/>
+                    <display tag="screen" type="vector" rotate="270"/>
+                </machine>
+                <machine name="amiga" sourcefile="amiga.cpp">
+                    <description>Amiga 500</description>
+                    <device type="floppydisk" tag="fdc:0" interface="floppy_3_5">
+                        <instance name="floppydisk1" briefname="flop1"/>
+                        <extension name="adf"/>
+                    </device>
+                    <device type="printer" tag="prn"/>
+                    <softwarelist tag="flop_list" name="amiga_flop" status="original"/>
+                    <softwarelist tag="cd_list" name="cdtv" status="compatible" filter="NTSC"/>
+                </machine>
+            </mame>
+        "#;
+
+        let scanner = GameScanner::new("mame");
+        let games = scanner.parse_xml(xml).unwrap();
+        let rom_set = &games[0].rom_set;
+
+        assert_eq!(rom_set.cloneof.as_deref(), Some("kinst"));
+        assert_eq!(rom_set.romof.as_deref(), Some("kinst"));
+        assert_eq!(rom_set.roms.len(), 4);
+        assert_eq!(rom_set.roms[0].size, 524288);
+        assert_eq!(rom_set.roms[0].crc.as_deref(), Some("6a0b1f2e"));
+        assert_eq!(rom_set.roms[0].sha1.as_deref(), Some("abcdef0123"));
+        assert_eq!(rom_set.roms[1].merge.as_deref(), Some("ki-u10.bin"));
+        assert_eq!(rom_set.roms[1].region.as_deref(), Some("snd"));
+        assert_eq!(rom_set.roms[2].status, DumpStatus::NoDump);
+        assert!(rom_set.roms[2].optional);
+        assert_eq!(rom_set.roms[3].bios.as_deref(), Some("v1"));
+        assert_eq!(rom_set.roms[3].status, DumpStatus::BadDump);
+        assert_eq!(rom_set.dumped_roms().count(), 3);
+        assert_eq!(rom_set.disks.len(), 1);
+        assert_eq!(rom_set.disks[0].sha1.as_deref(), Some("e4c34a14"));
+        assert_eq!(rom_set.disks[0].index, Some(0));
+        assert!(rom_set.disks[0].writable);
+        assert_eq!(rom_set.device_refs, vec!["ide_harddisk".to_string()]);
+        assert_eq!(games[0].chd_name.as_deref(), Some("kinst2"));
+        assert!(rom_set.samples.is_empty());
+        assert_eq!(games[1].rom_set.sampleof.as_deref(), Some("spacfury"));
+        assert_eq!(games[1].rom_set.samples, vec!["craft", "xplode"]);
+        assert!(!rom_set.vertical);
+        assert!(games[1].rom_set.vertical);
+        assert_eq!(
+            games[1].rom_set.source_file.as_deref(),
+            Some("segag80v.cpp")
+        );
+        let amiga = &games[2].rom_set;
+        assert_eq!(amiga.software_lists.len(), 2);
+        assert_eq!(amiga.software_lists[0].name, "amiga_flop");
+        assert_eq!(amiga.software_lists[0].status, SoftwareListStatus::Original);
+        assert_eq!(
+            amiga.software_lists[1].status,
+            SoftwareListStatus::Compatible
+        );
+        assert_eq!(amiga.software_lists[1].filter.as_deref(), Some("NTSC"));
+        assert!(amiga.software_lists[1].accepts(Some("NTSC,PAL"), None));
+        assert!(!amiga.software_lists[1].accepts(Some("PAL"), None));
+        assert!(!amiga.software_lists[1].accepts(None, Some("NTSC")));
+        // Incompatibility is checked before compatibility, as MAME does
+        assert!(!amiga.software_lists[1].accepts(Some("NTSC,PAL"), Some("NTSC")));
+        assert!(amiga.software_lists[1].accepts(Some("NTSC"), Some("PAL")));
+        assert!(amiga.software_lists[0].accepts(Some("PAL"), None));
+        assert_eq!(
+            amiga.media_slots,
+            vec![MediaSlot {
+                briefname: "flop1".to_string(),
+                interfaces: vec!["floppy_3_5".to_string()],
+            }]
+        );
+    }
+
     #[test]
     fn parses_machine_xml_with_entities_clone_and_chd() {
         let xml = r#"
@@ -0,0 +1,230 @@
+// src/mame/sessions.rs
+//! Tracks the MAME processes started from MAMEUIx: how long each has been
+//! running, stopping them on request, and how they ended.
+use crate::mame::launch_log::ExitInfo;
+use crate::models::InstancePolicy;
+use chrono::Local;
+use std::io;
+use std::process::Child;
+use std::time::{Duration, Instant};
+
+/// Finished sessions kept for the Sessions panel
+const MAX_RECENT: usize = 20;
+
+/// A game that is running now
+pub struct GameSession {
+    /// Same id as the launch in the launch log
+    pub id: u64,
+    pub game: String,
+    pub description: String,
+    pub started: Instant,
+    /// Set once the user asked the game to stop
+    pub stop_requested: bool,
+    child: Child,
+}
+
+impl GameSession {
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+}
+
+/// How a session ended
+#[derive(Debug, Clone)]
+pub struct FinishedSession {
+    pub id: u64,
+    pub game: String,
+    pub description: String,
+    pub play_time: u32,
+    pub ended: String,
+    pub exit: ExitInfo,
+    pub stopped_by_user: bool,
+}
+
+impl FinishedSession {
+    /// A crash is MAME dying on its own; being stopped from here is not one
+    pub fn crashed(&self) -> bool {
+        !self.stopped_by_user && self.exit.crashed()
+    }
+
+    pub fn outcome(&self) -> String {
+        if self.stopped_by_user {
+            "stopped".to_string()
+        } else if self.exit.success() {
+            "exited normally".to_string()
+        } else if self.crashed() {
+            format!("crashed ({})", self.exit.describe())
+        } else {
+            self.exit.describe()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SessionManager {
+    active: Vec<GameSession>,
+    recent: Vec<FinishedSession>,
+}
+
+impl SessionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn active(&self) -> &[GameSession] {
+        &self.active
+    }
+
+    /// Newest first
+    pub fn recent(&self) -> impl Iterator<Item = &FinishedSession> {
+        self.recent.iter().rev()
+    }
+
+    pub fn is_running(&self, game: &str) -> bool {
+        self.active.iter().any(|session| session.game == game)
+    }
+
+    /// Why a game may not be started under the policy, if it may not.
+    /// A game is never run twice at once.
+    pub fn check_policy(&self, game: &str, policy: InstancePolicy) -> Result<(), String> {
+        if let Some(session) = self.active.iter().find(|session| session.game == game) {
+            return Err(format!("{} is already running", session.description));
+        }
+        match (policy, self.active.first()) {
+            (InstancePolicy::Single, Some(session)) => Err(format!(
+                "{} is still running; stop it from the Sessions tab or allow several games in Preferences",
+                session.description
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn start(&mut self, id: u64, game: &str, description: &str, child: Child) {
+        self.active.push(GameSession {
+            id,
+            game: game.to_string(),
+            description: description.to_string(),
+            started: Instant::now(),
+            stop_requested: false,
+            child,
+        });
+    }
+
+    /// Ask a session to quit. The first request lets MAME shut down cleanly
+    /// (saving NVRAM and settings) where the platform allows; asking again
+    /// kills it outright.
+    pub fn stop(&mut self, id: u64) -> io::Result<()> {
+        let Some(session) = self.active.iter_mut().find(|session| session.id == id) else {
+            return Ok(());
+        };
+        let force = session.stop_requested;
+        session.stop_requested = true;
+        if !force && terminate(&session.child) {
+            return Ok(());
+        }
+        session.child.kill()
+    }
+
+    /// Collect the sessions that ended since the last call
+    pub fn poll(&mut self) -> Vec<FinishedSession> {
+        let mut finished = Vec::new();
+        let mut still_running = Vec::new();
+        for mut session in self.active.drain(..) {
+            match session.child.try_wait() {
+                Ok(Some(status)) => finished.push(FinishedSession {
+                    id: session.id,
+                    game: session.game,
+                    description: session.description,
+                    play_time: session.started.elapsed().as_secs() as u32,
+                    ended: Local::now().format("%H:%M:%S").to_string(),
+                    exit: ExitInfo::from_status(status),
+                    stopped_by_user: session.stop_requested,
+                }),
+                Ok(None) => still_running.push(session),
+                Err(e) => eprintln!("Lost track of {}: {}", session.game, e),
+            }
+        }
+        self.active = still_running;
+
+        self.recent.extend(finished.iter().cloned());
+        if self.recent.len() > MAX_RECENT {
+            let excess = self.recent.len() - MAX_RECENT;
+            self.recent.drain(..excess);
+        }
+        finished
+    }
+}
+
+/// Send SIGTERM so MAME exits the way it does from its own menu
+#[cfg(unix)]
+fn terminate(child: &Child) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(child.id()) else {
+        return false;
+    };
+    // SAFETY: kill only signals the process; the pid is our own unreaped
+    // child, so it cannot have been recycled for another process
+    unsafe { libc::kill(pid, libc::SIGTERM) == 0 }
+}
+
+#[cfg(not(unix))]
+fn terminate(_child: &Child) -> bool {
+    false
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn enforces_the_policy_and_stops_sessions() {
+        let mut sessions = SessionManager::new();
+        let child = Command::new("sleep").arg("30").spawn().unwrap();
+        sessions.start(1, "pacman", "Pac-Man", child);
+
+        assert!(
+            sessions
+                .check_policy("pacman", InstancePolicy::Multiple)
+                .is_err()
+        );
+        assert!(
+            sessions
+                .check_policy("galaga", InstancePolicy::Single)
+                .is_err()
+        );
+        assert!(
+            sessions
+                .check_policy("galaga", InstancePolicy::Multiple)
+                .is_ok()
+        );
+
+        sessions.stop(1).unwrap();
+        let mut finished = Vec::new();
+        for _ in 0..200 {
+            finished.extend(sessions.poll());
+            if !finished.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(finished.len(), 1);
+        assert!(finished[0].stopped_by_user);
+        assert!(!finished[0].crashed());
+        assert_eq!(finished[0].exit.signal, Some(15));
+        assert!(!sessions.is_running("pacman"));
+
+        let crash = FinishedSession {
+            stopped_by_user: false,
+            exit: ExitInfo {
+                code: None,
+                signal: Some(11),
+            },
+            ..finished[0].clone()
+        };
+        assert!(crash.crashed());
+    }
+}
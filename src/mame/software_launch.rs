@@ -0,0 +1,171 @@
+// src/mame/software_launch.rs
+//! Works out which machines can run a software-list item and the arguments
+//! that mount it: `mame <system> <software>` for single-part items, and one
+//! media switch per part (`-flop1 <software>:flop1 ...`) for multi-part ones.
+use crate::mame::SoftwareEntry;
+use crate::models::{Game, SoftwareListStatus};
+
+/// A software item ready to be run on a system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoftwareLaunch {
+    pub system: String,
+    pub software: String,
+    pub description: String,
+    /// Arguments after the system name
+    pub media_args: Vec<String>,
+}
+
+impl SoftwareLaunch {
+    pub fn new(system: &Game, entry: &SoftwareEntry) -> Self {
+        Self {
+            system: system.name.clone(),
+            software: entry.name.clone(),
+            description: entry.description.clone(),
+            media_args: media_args(system, entry),
+        }
+    }
+}
+
+/// Machines whose `-listxml` entry names the item's software list and whose
+/// list filter accepts it. The default system comes first: the parent named
+/// after the list (`amiga` for `amiga_flop`), then machines the list was
+/// made for before those it is only compatible with, parents before clones.
+pub fn compatible_systems<'a>(entry: &SoftwareEntry, games: &'a [Game]) -> Vec<&'a Game> {
+    let mut systems: Vec<(&Game, SoftwareListStatus)> = games
+        .iter()
+        .filter(|game| !game.is_device)
+        .filter_map(|game| {
+            let list = game
+                .rom_set
+                .software_lists
+                .iter()
+                .find(|list| entry.is_accepted_by(list))?;
+            Some((game, list.status))
+        })
+        .collect();
+
+    let family = entry
+        .list_name
+        .split('_')
+        .next()
+        .unwrap_or(&entry.list_name);
+    systems.sort_by_key(|(game, status)| {
+        (
+            game.name != family,
+            *status != SoftwareListStatus::Original,
+            game.is_clone,
+            game.name.clone(),
+        )
+    });
+    systems.into_iter().map(|(game, _)| game).collect()
+}
+
+/// Arguments that mount every part of the item in a matching media slot
+pub fn media_args(system: &Game, entry: &SoftwareEntry) -> Vec<String> {
+    let mut args = Vec::new();
+    if entry.parts.len() > 1 {
+        let mut used = Vec::new();
+        for part in &entry.parts {
+            let Some(slot) = system.rom_set.media_slots.iter().find(|slot| {
+                !used.contains(&slot.briefname) && slot.interfaces.contains(&part.interface)
+            }) else {
+                continue;
+            };
+            used.push(slot.briefname.clone());
+            args.push(format!("-{}", slot.briefname));
+            args.push(format!("{}:{}", entry.name, part.name));
+        }
+    }
+
+    // MAME picks the slot itself for single parts, or when no slot matched
+    if args.is_empty() {
+        args.push(entry.name.clone());
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mame::software_list::SoftwarePart;
+    use crate::models::{MachineRomSet, MediaSlot, RomStatus, SoftwareListRef};
+
+    fn system(
+        name: &str,
+        is_clone: bool,
+        lists: &[SoftwareListRef],
+        slots: &[(&str, &str)],
+    ) -> Game {
+        Game {
+            name: name.to_string(),
+            description: name.to_string(),
+            status: RomStatus::Available,
+            is_clone,
+            rom_set: MachineRomSet {
+                software_lists: lists.to_vec(),
+                media_slots: slots
+                    .iter()
+                    .map(|(briefname, interface)| MediaSlot {
+                        briefname: briefname.to_string(),
+                        interfaces: vec![interface.to_string()],
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            ..Game::default()
+        }
+    }
+
+    #[test]
+    fn picks_the_default_system_and_one_slot_per_part() {
+        let slots = [("flop1", "floppy_3_5"), ("flop2", "floppy_3_5")];
+        let list = |status, filter: Option<&str>| SoftwareListRef {
+            name: "amiga_flop".to_string(),
+            status,
+            filter: filter.map(str::to_string),
+        };
+        let original = [list(SoftwareListStatus::Original, None)];
+        let games = vec![
+            system("a1000", false, &original, &slots),
+            system("amiga", false, &original, &slots),
+            system("a500p", true, &original, &slots),
+            system(
+                "cdtv",
+                false,
+                &[list(SoftwareListStatus::Compatible, None)],
+                &slots,
+            ),
+            system(
+                "a500n",
+                false,
+                &[list(SoftwareListStatus::Original, Some("NTSC"))],
+                &slots,
+            ),
+            system("pacman", false, &[], &[]),
+        ];
+        let mut entry = SoftwareEntry {
+            list_name: "amiga_flop".to_string(),
+            name: "lemmings".to_string(),
+            compatibility: Some("PAL".to_string()),
+            ..Default::default()
+        };
+        let names: Vec<&str> = compatible_systems(&entry, &games)
+            .iter()
+            .map(|game| game.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["amiga", "a1000", "a500p", "cdtv"]);
+
+        let part = |name: &str| SoftwarePart {
+            name: name.to_string(),
+            interface: "floppy_3_5".to_string(),
+        };
+        entry.parts = vec![part("flop1"), part("flop2")];
+        assert_eq!(
+            media_args(&games[1], &entry),
+            vec!["-flop1", "lemmings:flop1", "-flop2", "lemmings:flop2"]
+        );
+
+        entry.parts.truncate(1);
+        assert_eq!(media_args(&games[1], &entry), vec!["lemmings"]);
+    }
+}
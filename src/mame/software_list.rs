@@ -1,3 +1,4 @@
+use crate::models::{DiskEntry, DumpStatus, RomEntry, SoftwareListRef};
 use anyhow::{Context, Result};
 use quick_xml::Reader;
 use quick_xml::events::{BytesStart, Event};
@@ -25,7 +26,35 @@ pub struct SoftwareEntry {
     pub parent: Option<String>,
     pub part_count: usize,
     pub interfaces: Vec<String>,
+    /// `<part>` entries in order; multi-part items are loaded part by part
+    pub parts: Vec<SoftwarePart>,
+    /// `compatibility` / `incompatibility` features, matched against a
+    /// machine's software-list filter (e.g. "NTSC,PAL")
+    pub compatibility: Option<String>,
+    pub incompatibility: Option<String>,
     pub source_file: PathBuf,
+    /// `<rom>` entries from every `<part>/<dataarea>`
+    pub roms: Vec<RomEntry>,
+    /// `<disk>` entries from every `<part>/<diskarea>`
+    pub disks: Vec<DiskEntry>,
+}
+
+impl SoftwareEntry {
+    /// Whether a machine's `<softwarelist>` entry covers this item
+    pub fn is_accepted_by(&self, list: &SoftwareListRef) -> bool {
+        list.name == self.list_name
+            && list.accepts(
+                self.compatibility.as_deref(),
+                self.incompatibility.as_deref(),
+            )
+    }
+}
+
+/// One `<part name interface>` of a software item, e.g. `flop2` / `floppy_3_5`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SoftwarePart {
+    pub name: String,
+    pub interface: String,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -135,7 +164,7 @@ impl SoftwareListLoader {
     fn parse_xml(path: &Path, xml: &str) -> Result<SoftwareListLoadResult> {
         if Self::looks_like_rom_manager_dat(xml) {
             return Err(anyhow::anyhow!(
-                "{} is a ROM-manager DAT XML file, not a MAME software-list hash XML file",
+                "{} is a ROM-manager DAT XML file, not a MAME software-list hash XML file; load it from ROM Verification instead",
                 path.display()
             ));
         }
@@ -173,15 +202,26 @@ impl SoftwareListLoader {
                                     Self::read_text_unescaped(&mut reader, event.name())?;
                             }
                             b"part" => Self::read_part_attrs(entry, &event, &reader)?,
+                            b"rom" => Self::read_rom_attrs(entry, &event, &reader)?,
+                            b"disk" => Self::read_disk_attrs(entry, &event, &reader)?,
+                            b"sharedfeat" | b"feature" => {
+                                Self::read_feature_attrs(entry, &event, &reader)?
+                            }
                             _ => {}
                         }
                     }
                 }
                 Event::Empty(event) => {
-                    if let Some(entry) = current_entry.as_mut()
-                        && event.name().as_ref() == b"part"
-                    {
-                        Self::read_part_attrs(entry, &event, &reader)?;
+                    if let Some(entry) = current_entry.as_mut() {
+                        match event.name().as_ref() {
+                            b"part" => Self::read_part_attrs(entry, &event, &reader)?,
+                            b"rom" => Self::read_rom_attrs(entry, &event, &reader)?,
+                            b"disk" => Self::read_disk_attrs(entry, &event, &reader)?,
+                            b"sharedfeat" | b"feature" => {
+                                Self::read_feature_attrs(entry, &event, &reader)?
+                            }
+                            _ => {}
+                        }
                     }
                 }
                 Event::End(event) if event.name().as_ref() == b"software" => {
@@ -273,11 +313,71 @@ impl SoftwareListLoader {
         reader: &Reader<&[u8]>,
     ) -> Result<()> {
         entry.part_count += 1;
-        if let Some(interface) = Self::xml_attr(event, b"interface", reader)?
-            && !entry.interfaces.contains(&interface)
-        {
-            entry.interfaces.push(interface);
+        let interface = Self::xml_attr(event, b"interface", reader)?.unwrap_or_default();
+        if !interface.is_empty() && !entry.interfaces.contains(&interface) {
+            entry.interfaces.push(interface.clone());
         }
+        entry.parts.push(SoftwarePart {
+            name: Self::xml_attr(event, b"name", reader)?.unwrap_or_default(),
+            interface,
+        });
+        Ok(())
+    }
+
+    fn read_feature_attrs(
+        entry: &mut SoftwareEntry,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        let value = Self::xml_attr(event, b"value", reader)?;
+        match Self::xml_attr(event, b"name", reader)?.as_deref() {
+            Some("compatibility") => entry.compatibility = value,
+            Some("incompatibility") => entry.incompatibility = value,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn read_rom_attrs(
+        entry: &mut SoftwareEntry,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        // Continue, fill and ignore load flags have no name and no hashes
+        let Some(name) = Self::xml_attr(event, b"name", reader)? else {
+            return Ok(());
+        };
+
+        entry.roms.push(RomEntry {
+            name,
+            size: Self::xml_attr(event, b"size", reader)?
+                .and_then(|size| size.parse().ok())
+                .unwrap_or(0),
+            crc: Self::xml_attr(event, b"crc", reader)?.map(|crc| crc.to_lowercase()),
+            sha1: Self::xml_attr(event, b"sha1", reader)?.map(|sha1| sha1.to_lowercase()),
+            status: DumpStatus::from_xml(Self::xml_attr(event, b"status", reader)?.as_deref()),
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    fn read_disk_attrs(
+        entry: &mut SoftwareEntry,
+        event: &BytesStart<'_>,
+        reader: &Reader<&[u8]>,
+    ) -> Result<()> {
+        let Some(name) = Self::xml_attr(event, b"name", reader)? else {
+            return Ok(());
+        };
+
+        entry.disks.push(DiskEntry {
+            name,
+            sha1: Self::xml_attr(event, b"sha1", reader)?.map(|sha1| sha1.to_lowercase()),
+            // Hash XML spells it "writeable"
+            writable: Self::xml_attr(event, b"writeable", reader)?.as_deref() == Some("yes"),
+            status: DumpStatus::from_xml(Self::xml_attr(event, b"status", reader)?.as_deref()),
+            ..Default::default()
+        });
         Ok(())
     }
 
@@ -310,6 +410,7 @@ impl SoftwareListLoader {
 #[cfg(test)]
 mod tests {
     use super::SoftwareListLoader;
+    use crate::models::DumpStatus;
     use std::path::Path;
 
     #[test]
@@ -319,6 +420,7 @@ mod tests {
                 <softwarelist name="a2600" description="Atari 2600 cartridges">
                     <software name="combat">
                         <description>Combat</description>
+                        <sharedfeat name="compatibility" value="NTSC"/>
                         <year>1977</year>
                         <publisher>Atari</publisher>
                         <part name="cart" interface="a2600_cart">
@@ -333,6 +435,20 @@ mod tests {
                         <publisher>Atari</publisher>
                         <part name="cart" interface="a2600_cart"/>
                     </software>
+                    <software name="harddriv">
+                        <description>Hard Drivin'</description>
+                        <part name="cart" interface="a2600_cart">
+                            <dataarea name="rom" size="4096">
+                                <rom name="hd.bin" size="2048" crc="DEADBEEF" sha1="AB12" status="baddump"/>
+                                <rom size="2048" offset="0x800" loadflag="continue"/>
+                            </dataarea>
+                        </part>
+                        <part name="hdd" interface="a2600_hdd">
+                            <diskarea name="harddriv">
+                                <disk name="harddriv" sha1="CD34" writeable="yes"/>
+                            </diskarea>
+                        </part>
+                    </software>
                 </softwarelist>
             </softwarelists>
         "#;
@@ -341,12 +457,27 @@ mod tests {
 
         assert_eq!(result.lists.len(), 1);
         assert_eq!(result.lists[0].name, "a2600");
-        assert_eq!(result.lists[0].software_count, 2);
-        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.lists[0].software_count, 3);
+        assert_eq!(result.entries.len(), 3);
         assert_eq!(result.entries[0].description, "Combat");
+        assert_eq!(result.entries[0].compatibility.as_deref(), Some("NTSC"));
         assert_eq!(result.entries[1].description, "Combat & Bonus");
         assert_eq!(result.entries[1].clone_of.as_deref(), Some("combat"));
         assert_eq!(result.entries[1].supported, "partial");
+        assert_eq!(result.entries[0].roms.len(), 1);
+        assert_eq!(result.entries[0].roms[0].crc.as_deref(), Some("9f7fdd53"));
+        assert!(result.entries[1].roms.is_empty());
+
+        let harddriv = &result.entries[2];
+        assert_eq!(harddriv.part_count, 2);
+        assert_eq!(harddriv.parts[1].name, "hdd");
+        assert_eq!(harddriv.parts[1].interface, "a2600_hdd");
+        assert_eq!(harddriv.roms.len(), 1);
+        assert_eq!(harddriv.roms[0].crc.as_deref(), Some("deadbeef"));
+        assert_eq!(harddriv.roms[0].status, DumpStatus::BadDump);
+        assert_eq!(harddriv.disks.len(), 1);
+        assert_eq!(harddriv.disks[0].sha1.as_deref(), Some("cd34"));
+        assert!(harddriv.disks[0].writable);
     }
 
     #[test]
@@ -0,0 +1,546 @@
+// src/mame/verifyroms.rs
+//! Runs `mame -verifyroms` over batches of sets on a pool of workers and
+//! attributes every line of MAME's audit report to the set it belongs to.
+use crate::models::{AppConfig, DumpStatus, Game};
+use crate::ui::components::rom_verify::{
+    FileCheck, FileCheckStatus, VerificationResult, VerifyStatus,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::Duration;
+
+/// Sets passed to one `-verifyroms` call
+pub const BATCH_SIZE: usize = 64;
+
+/// Verdict on a set's summary line (`romset pacman [puckman] is good`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetVerdict {
+    Good,
+    BestAvailable,
+    Bad,
+    NotFound,
+    NoRoms,
+}
+
+/// Problem MAME reports for one ROM or disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileIssue {
+    NotFound,
+    NotFoundOptional,
+    NotFoundNoGoodDump,
+    IncorrectChecksum,
+    IncorrectLength,
+    NoGoodDump,
+    NeedsRedump,
+}
+
+impl FileIssue {
+    fn from_status(status: &str) -> Option<Self> {
+        // Longer phrases first, they start with the shorter ones
+        let issue = if status.starts_with("NOT FOUND - NO GOOD DUMP KNOWN") {
+            FileIssue::NotFoundNoGoodDump
+        } else if status.starts_with("NOT FOUND BUT OPTIONAL") {
+            FileIssue::NotFoundOptional
+        } else if status.starts_with("NOT FOUND") {
+            FileIssue::NotFound
+        } else if status.starts_with("INCORRECT CHECKSUM") {
+            FileIssue::IncorrectChecksum
+        } else if status.starts_with("INCORRECT LENGTH") {
+            FileIssue::IncorrectLength
+        } else if status.starts_with("NO GOOD DUMP KNOWN") {
+            FileIssue::NoGoodDump
+        } else if status.starts_with("NEEDS REDUMP") {
+            FileIssue::NeedsRedump
+        } else {
+            return None;
+        };
+        Some(issue)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    pub name: String,
+    pub issue: FileIssue,
+    /// Hashes from the `EXPECTED:` and `FOUND:` lines of a checksum mismatch
+    pub expected: Option<String>,
+    pub found: Option<String>,
+}
+
+/// Everything MAME printed about one set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetReport {
+    pub verdict: Option<SetVerdict>,
+    pub files: Vec<FileReport>,
+}
+
+/// Split `-verifyroms` output into per-set reports, keyed by set name.
+/// Lines that belong to no set (warnings, totals) are skipped.
+pub fn parse_verifyroms_output(output: &str) -> HashMap<String, SetReport> {
+    let mut reports: HashMap<String, SetReport> = HashMap::new();
+    let mut last_file: Option<String> = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        if let Some(rest) = line.strip_prefix("romset ") {
+            last_file = None;
+            let Some((name, verdict)) = parse_summary(rest) else {
+                continue;
+            };
+            reports.entry(name).or_default().verdict = Some(verdict);
+        } else if let Some(hashes) = line.strip_prefix("EXPECTED:") {
+            if let Some(file) = last_file_mut(&mut reports, &last_file) {
+                file.expected = Some(hashes.trim().to_string());
+            }
+        } else if let Some(hashes) = line.strip_prefix("FOUND:") {
+            if let Some(file) = last_file_mut(&mut reports, &last_file) {
+                file.found = Some(hashes.trim().to_string());
+            }
+        } else if let Some((set, file)) = parse_file_line(line) {
+            reports.entry(set.clone()).or_default().files.push(file);
+            last_file = Some(set);
+        } else {
+            last_file = None;
+        }
+    }
+
+    reports
+}
+
+/// `pacman [puckman] is best available` → ("pacman", BestAvailable)
+fn parse_summary(rest: &str) -> Option<(String, SetVerdict)> {
+    let (name, rest) = rest.split_once(' ')?;
+    let name = name.trim_matches('"').to_string();
+    let mut rest = rest.trim_start();
+    if rest.starts_with('[')
+        && let Some(end) = rest.find(']')
+    {
+        rest = rest[end + 1..].trim_start();
+    }
+
+    let verdict = if rest.starts_with("is good") {
+        SetVerdict::Good
+    } else if rest.starts_with("is best available") {
+        SetVerdict::BestAvailable
+    } else if rest.starts_with("is bad") {
+        SetVerdict::Bad
+    } else if rest.starts_with("not found") {
+        SetVerdict::NotFound
+    } else if rest.starts_with("has no roms") {
+        SetVerdict::NoRoms
+    } else {
+        return None;
+    };
+    Some((name, verdict))
+}
+
+/// `pacman      : pacman.6e (4096 bytes) - NOT FOUND`
+fn parse_file_line(line: &str) -> Option<(String, FileReport)> {
+    let (set, rest) = line.split_once(':')?;
+    let set = set.trim();
+    if set.is_empty() || set.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let rest = rest.trim_start();
+    // The status itself may contain " - ", so split after the size if there is one
+    let (file, status) = match rest.find(" bytes) - ") {
+        Some(index) => (
+            &rest[..index + " bytes)".len()],
+            &rest[index + " bytes) - ".len()..],
+        ),
+        None => rest.split_once(" - ")?,
+    };
+    let name = match file.rfind(" (") {
+        Some(index) if file.ends_with(" bytes)") => &file[..index],
+        _ => file,
+    };
+
+    Some((
+        set.to_string(),
+        FileReport {
+            name: name.trim().to_string(),
+            issue: FileIssue::from_status(status.trim())?,
+            expected: None,
+            found: None,
+        },
+    ))
+}
+
+fn last_file_mut<'a>(
+    reports: &'a mut HashMap<String, SetReport>,
+    set: &Option<String>,
+) -> Option<&'a mut FileReport> {
+    reports.get_mut(set.as_ref()?)?.files.last_mut()
+}
+
+/// Turn MAME's report for a game into a verification result. A finished
+/// batch run prints nothing at all for a set none of whose files were
+/// found, so no report means the set is missing.
+pub fn result_from_report(game: &Game, report: Option<&SetReport>) -> VerificationResult {
+    let mut result = VerificationResult {
+        game_name: game.name.clone(),
+        description: game.description.clone(),
+        status: VerifyStatus::Failed,
+        missing_files: Vec::new(),
+        incorrect_files: Vec::new(),
+        extra_files: Vec::new(),
+        chd_status: None,
+        files: Vec::new(),
+    };
+    let Some(report) = report else {
+        let missing = SetReport {
+            verdict: Some(SetVerdict::NotFound),
+            files: Vec::new(),
+        };
+        result.status = VerifyStatus::NotFound;
+        result.files = file_checks(game, &missing);
+        return result;
+    };
+
+    let disks: HashSet<String> = game
+        .rom_set
+        .disks
+        .iter()
+        .map(|disk| disk.name.to_lowercase())
+        .collect();
+    let mut missing_disks = Vec::new();
+    let mut incorrect_disks = Vec::new();
+    for file in &report.files {
+        let is_disk = disks.contains(&file.name.to_lowercase());
+        match (file.issue, is_disk) {
+            (FileIssue::NotFound, true) => missing_disks.push(file.name.as_str()),
+            (FileIssue::NotFound, false) => result.missing_files.push(file.name.clone()),
+            (FileIssue::IncorrectChecksum | FileIssue::IncorrectLength, true) => {
+                incorrect_disks.push(file.name.as_str())
+            }
+            (FileIssue::IncorrectChecksum | FileIssue::IncorrectLength, false) => {
+                result.incorrect_files.push(file.name.clone())
+            }
+            // Covered by the set's "best available" verdict
+            _ => {}
+        }
+    }
+
+    let mut chd_problems = Vec::new();
+    if !missing_disks.is_empty() {
+        chd_problems.push(format!("CHD not found: {}", missing_disks.join(", ")));
+    }
+    if !incorrect_disks.is_empty() {
+        chd_problems.push(format!("CHD incorrect: {}", incorrect_disks.join(", ")));
+    }
+    result.chd_status = (!chd_problems.is_empty()).then(|| chd_problems.join("; "));
+    result.files = file_checks(game, report);
+
+    result.status = match report.verdict {
+        Some(SetVerdict::Good | SetVerdict::NoRoms) => VerifyStatus::Passed,
+        Some(SetVerdict::BestAvailable) => VerifyStatus::Warning,
+        Some(SetVerdict::NotFound) => VerifyStatus::NotFound,
+        Some(SetVerdict::Bad) => VerifyStatus::Failed,
+        // Output cut short; judge by the file lines that did arrive
+        None if result.missing_files.is_empty()
+            && result.incorrect_files.is_empty()
+            && result.chd_status.is_none() =>
+        {
+            result
+                .missing_files
+                .push("MAME reported no verdict for this set".to_string());
+            VerifyStatus::Failed
+        }
+        None => VerifyStatus::Failed,
+    };
+    result
+}
+
+/// Per-file outcome for every ROM and disk of the game's manifest. Files
+/// MAME printed nothing about matched, unless the whole set was not found.
+fn file_checks(game: &Game, report: &SetReport) -> Vec<FileCheck> {
+    let set_missing = report.verdict == Some(SetVerdict::NotFound);
+    let check = |name: &str, crc: Option<&String>, dump: DumpStatus| {
+        let line = report
+            .files
+            .iter()
+            .find(|file| file.name.eq_ignore_ascii_case(name));
+        let status = match line.map(|file| file.issue) {
+            _ if dump == DumpStatus::NoDump => FileCheckStatus::NoDump,
+            Some(FileIssue::NotFoundNoGoodDump | FileIssue::NoGoodDump) => FileCheckStatus::NoDump,
+            Some(FileIssue::NotFound | FileIssue::NotFoundOptional) => FileCheckStatus::Missing,
+            Some(FileIssue::IncorrectChecksum | FileIssue::IncorrectLength) => {
+                FileCheckStatus::Incorrect
+            }
+            Some(FileIssue::NeedsRedump) => FileCheckStatus::Good,
+            None if set_missing => FileCheckStatus::Missing,
+            None => FileCheckStatus::Good,
+        };
+        FileCheck {
+            name: name.to_string(),
+            crc: crc.cloned(),
+            status,
+            expected: line.and_then(|file| file.expected.clone()),
+            actual: line.and_then(|file| file.found.clone()),
+        }
+    };
+
+    let roms = game
+        .rom_set
+        .roms
+        .iter()
+        .map(|rom| check(&rom.name, rom.crc.as_ref(), rom.status));
+    let disks = game
+        .rom_set
+        .disks
+        .iter()
+        .map(|disk| check(&disk.name, None, disk.status));
+    roms.chain(disks).collect()
+}
+
+/// How to invoke MAME for an audit
+#[derive(Debug, Clone)]
+pub struct VerifyCommand {
+    pub mame_path: String,
+    /// `-rompath` value, so MAME audits the same folders the app scans
+    pub rom_path: Option<String>,
+}
+
+impl VerifyCommand {
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let mame = config.mame_executables.get(config.selected_mame_index)?;
+        let rom_path = config
+            .rom_paths
+            .iter()
+            .chain(config.extra_rom_dirs.iter())
+            .map(|path| path.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(";");
+
+        Some(Self {
+            mame_path: mame.path.clone(),
+            rom_path: (!rom_path.is_empty()).then_some(rom_path),
+        })
+    }
+
+    /// Verify several sets with a single MAME process
+    pub fn verify_batch(&self, games: &[Game]) -> Vec<VerificationResult> {
+        let mut command = Command::new(&self.mame_path);
+        if let Some(rom_path) = &self.rom_path {
+            command.arg("-rompath").arg(rom_path);
+        }
+        command
+            .arg("-verifyroms")
+            .args(games.iter().map(|game| &game.name));
+
+        let output = match command.output() {
+            Ok(output) => output,
+            Err(e) => {
+                return games
+                    .iter()
+                    .map(|game| VerificationResult {
+                        game_name: game.name.clone(),
+                        description: game.description.clone(),
+                        status: VerifyStatus::Failed,
+                        missing_files: vec![format!("Error running verification: {}", e)],
+                        incorrect_files: Vec::new(),
+                        extra_files: Vec::new(),
+                        chd_status: None,
+                        files: Vec::new(),
+                    })
+                    .collect();
+            }
+        };
+
+        let combined = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let reports = parse_verifyroms_output(&combined);
+        // Killed part way, silence about a set says nothing about its files
+        let finished = output.status.code().is_some();
+        games
+            .iter()
+            .map(|game| match reports.get(&game.name) {
+                None if !finished => {
+                    let mut result = result_from_report(game, None);
+                    result.status = VerifyStatus::Failed;
+                    result.files.clear();
+                    result
+                        .missing_files
+                        .push("MAME stopped before reporting this set".to_string());
+                    result
+                }
+                report => result_from_report(game, report),
+            })
+            .collect()
+    }
+}
+
+/// Workers that each run one batch at a time until the queue is empty
+pub struct VerifyPool {
+    results: mpsc::Receiver<VerificationResult>,
+    stop: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+}
+
+impl VerifyPool {
+    pub fn start(command: VerifyCommand, games: Vec<Game>, workers: usize) -> Self {
+        let batches: VecDeque<Vec<Game>> = games
+            .chunks(BATCH_SIZE)
+            .map(|batch| batch.to_vec())
+            .collect();
+        let workers = workers.clamp(1, batches.len().max(1));
+        let queue = Arc::new(Mutex::new(batches));
+        let stop = Arc::new(AtomicBool::new(false));
+        let paused = Arc::new(AtomicBool::new(false));
+        let (sender, results) = mpsc::channel();
+
+        for _ in 0..workers {
+            let command = command.clone();
+            let queue = queue.clone();
+            let stop = stop.clone();
+            let paused = paused.clone();
+            let sender = sender.clone();
+            thread::spawn(move || {
+                loop {
+                    while paused.load(Ordering::Relaxed) && !stop.load(Ordering::Relaxed) {
+                        thread::sleep(Duration::from_millis(100));
+                    }
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let Some(batch) = queue.lock().ok().and_then(|mut queue| queue.pop_front())
+                    else {
+                        break;
+                    };
+                    for result in command.verify_batch(&batch) {
+                        if sender.send(result).is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+
+        Self {
+            results,
+            stop,
+            paused,
+        }
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Workers finish the batch they are on, then take no more
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Next result; disconnected once every worker has finished
+    pub fn recv_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<VerificationResult, mpsc::RecvTimeoutError> {
+        self.results.recv_timeout(timeout)
+    }
+}
+
+/// Default pool size: a few MAME processes at once, bounded by the CPU count
+pub fn default_verify_workers() -> usize {
+    thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DiskEntry, MachineRomSet, RomStatus};
+
+    /// `mame -verifyroms pacman galaga robby dkong zzyzx kinst`, where none
+    /// of zzyzx's files are present: MAME says nothing about it, and the
+    /// total only counts the sets it found
+    const OUTPUT: &str = "\
+pacman      : pacman.6e (4096 bytes) - NOT FOUND (tried in pacman puckman)
+romset pacman [puckman] is bad
+galaga      : gg1_1b.3p (4096 bytes) - INCORRECT CHECKSUM:
+EXPECTED: CRC(ab7af8ea) SHA1(10d6d2b5b9d39cf1e6f1b0b1c1b8e81e3e6b2e45)
+   FOUND: CRC(00000000) SHA1(0000000000000000000000000000000000000000)
+romset galaga is bad
+robby       : robby.bin (8192 bytes) - NO GOOD DUMP KNOWN
+romset robby is best available
+romset dkong is good
+kinst       : kinst - NOT FOUND
+romset kinst is bad
+5 romsets found, 2 were OK.
+";
+
+    fn game(name: &str, disks: &[&str]) -> Game {
+        Game {
+            name: name.to_string(),
+            description: name.to_string(),
+            status: RomStatus::Available,
+            requires_chd: !disks.is_empty(),
+            rom_set: MachineRomSet {
+                disks: disks
+                    .iter()
+                    .map(|name| DiskEntry {
+                        name: name.to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            ..Game::default()
+        }
+    }
+
+    #[test]
+    fn attributes_every_line_to_its_set() {
+        let reports = parse_verifyroms_output(OUTPUT);
+
+        assert_eq!(reports["pacman"].verdict, Some(SetVerdict::Bad));
+        assert_eq!(reports["pacman"].files[0].name, "pacman.6e");
+        assert_eq!(reports["pacman"].files[0].issue, FileIssue::NotFound);
+        let galaga = &reports["galaga"].files[0];
+        assert_eq!(galaga.issue, FileIssue::IncorrectChecksum);
+        assert!(
+            galaga
+                .expected
+                .as_deref()
+                .unwrap()
+                .starts_with("CRC(ab7af8ea)")
+        );
+        assert!(
+            galaga
+                .found
+                .as_deref()
+                .unwrap()
+                .starts_with("CRC(00000000)")
+        );
+        assert_eq!(reports["robby"].verdict, Some(SetVerdict::BestAvailable));
+        assert_eq!(reports["dkong"].verdict, Some(SetVerdict::Good));
+        assert!(!reports.contains_key("zzyzx"));
+
+        let pacman = result_from_report(&game("pacman", &[]), reports.get("pacman"));
+        assert_eq!(pacman.status, VerifyStatus::Failed);
+        assert_eq!(pacman.missing_files, vec!["pacman.6e"]);
+        let galaga = result_from_report(&game("galaga", &[]), reports.get("galaga"));
+        assert_eq!(galaga.incorrect_files, vec!["gg1_1b.3p"]);
+        let robby = result_from_report(&game("robby", &[]), reports.get("robby"));
+        assert_eq!(robby.status, VerifyStatus::Warning);
+        assert!(robby.missing_files.is_empty());
+        let kinst = result_from_report(&game("kinst", &["kinst"]), reports.get("kinst"));
+        assert_eq!(kinst.chd_status.as_deref(), Some("CHD not found: kinst"));
+        assert_eq!(kinst.files[0].status, FileCheckStatus::Missing);
+        assert!(kinst.missing_files.is_empty());
+        let zzyzx = result_from_report(&game("zzyzx", &["zzyzx"]), reports.get("zzyzx"));
+        assert_eq!(zzyzx.status, VerifyStatus::NotFound);
+        assert_eq!(zzyzx.files[0].status, FileCheckStatus::Missing);
+    }
+}
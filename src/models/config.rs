@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 // GraphicsConfig is used in the struct definition below
-use super::{FilterSettings, GameStats, Preferences, SortColumn, SortDirection};
+use super::{
+    FilterSettings, GameStats, LaunchHooks, Preferences, PropertyLayers, SortColumn, SortDirection,
+};
 use crate::utils::graphics::GraphicsConfig;
 
 // View mode for game list display
@@ -44,6 +46,25 @@ impl UiShellMode {
     }
 }
 
+/// How many games may run at once. The same game is never started twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum InstancePolicy {
+    /// One game at a time; launching another is refused with a warning
+    /// until the running one is stopped
+    Single,
+    #[default]
+    Multiple,
+}
+
+impl InstancePolicy {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Single => "One game at a time",
+            Self::Multiple => "Several games at once",
+        }
+    }
+}
+
 // Window size and position settings for dialogs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowSettings {
@@ -688,6 +709,8 @@ struct AppConfigToml {
     pub command_dat_path: Option<PathBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub catver_ini_path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quarantine_path: Option<PathBuf>,
 
     // MAME Internal Folders Configuration
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -715,9 +738,15 @@ struct AppConfigToml {
     pub favorite_games: HashSet<String>,
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
     pub game_stats: HashMap<String, GameStats>,
+    /// Full per-game properties written before overrides were layered;
+    /// read once and turned into `property_layers`
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
     pub game_properties: HashMap<String, super::game_properties::GameProperties>,
     pub default_game_properties: super::game_properties::GameProperties,
+    #[serde(skip_serializing_if = "PropertyLayers::is_empty", default)]
+    pub property_layers: PropertyLayers,
+    #[serde(skip_serializing_if = "LaunchHooks::is_empty", default)]
+    pub launch_hooks: LaunchHooks,
 
     // Hidden categories
     #[serde(skip_serializing_if = "HashSet::is_empty", default)]
@@ -739,6 +768,8 @@ struct AppConfigToml {
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
     pub mame_audit_times: HashMap<String, String>,
     pub assume_merged_sets: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify_workers: Option<usize>,
 
     // Graphics and video
     pub graphics_config: GraphicsConfig,
@@ -801,6 +832,7 @@ pub struct AppConfig {
     pub gameinit_dat_path: Option<PathBuf>, // gameinit.dat file path
     pub command_dat_path: Option<PathBuf>,  // command.dat file path
     pub catver_ini_path: Option<PathBuf>,   // catver.ini file path for category support
+    pub quarantine_path: Option<PathBuf>,   // Where ROM cleanup moves unneeded files
 
     // MAME Internal Folders Configuration
     pub cfg_path: Option<PathBuf>,     // Configuration files directory
@@ -819,8 +851,9 @@ pub struct AppConfig {
     pub game_preferred_mame: HashMap<String, usize>, // Preferred MAME for each game
     pub favorite_games: HashSet<String>,             // User's favorite games
     pub game_stats: HashMap<String, GameStats>,      // Play statistics per game
-    pub game_properties: HashMap<String, super::game_properties::GameProperties>, // Per-game properties
     pub default_game_properties: super::game_properties::GameProperties, // Default properties for all games
+    pub property_layers: PropertyLayers, // Orientation, source and game overrides of the defaults
+    pub launch_hooks: LaunchHooks,       // Commands run before launch and after exit
 
     // Hidden categories - categories that should not be shown in the game list
     pub hidden_categories: HashSet<String>,
@@ -839,6 +872,7 @@ pub struct AppConfig {
     pub use_mame_audit: bool, // Use MAME's built-in audit
     pub mame_audit_times: HashMap<String, String>, // Last audit time per directory
     pub assume_merged_sets: bool, // Assume ROMs are merged sets
+    pub verify_workers: usize, // MAME -verifyroms processes run at once
 
     // Graphics and video
     pub graphics_config: GraphicsConfig, // Graphics backend configuration
@@ -889,6 +923,7 @@ impl AppConfig {
             gameinit_dat_path: self.gameinit_dat_path.clone(),
             command_dat_path: self.command_dat_path.clone(),
             catver_ini_path: self.catver_ini_path.clone(),
+            quarantine_path: self.quarantine_path.clone(),
             cfg_path: self.cfg_path.clone(),
             nvram_path: self.nvram_path.clone(),
             input_path: self.input_path.clone(),
@@ -901,8 +936,10 @@ impl AppConfig {
             game_preferred_mame: self.game_preferred_mame.clone(),
             favorite_games: self.favorite_games.clone(),
             game_stats: self.game_stats.clone(),
-            game_properties: self.game_properties.clone(),
+            game_properties: HashMap::new(),
             default_game_properties: self.default_game_properties.clone(),
+            property_layers: self.property_layers.clone(),
+            launch_hooks: self.launch_hooks.clone(),
             hidden_categories: self.hidden_categories.clone(),
             show_filters: self.show_filters,
             selected_rom: self.selected_rom.clone(),
@@ -915,6 +952,7 @@ impl AppConfig {
             use_mame_audit: self.use_mame_audit,
             mame_audit_times: self.mame_audit_times.clone(),
             assume_merged_sets: self.assume_merged_sets,
+            verify_workers: Some(self.verify_workers),
             graphics_config: self.graphics_config.clone(),
             bgfx_path: self.bgfx_path.clone(),
             last_directories: self.last_directories.clone(),
@@ -958,6 +996,7 @@ impl AppConfig {
             gameinit_dat_path: toml.gameinit_dat_path,
             command_dat_path: toml.command_dat_path,
             catver_ini_path: toml.catver_ini_path,
+            quarantine_path: toml.quarantine_path,
             cfg_path: toml.cfg_path,
             nvram_path: toml.nvram_path,
             input_path: toml.input_path,
@@ -970,8 +1009,13 @@ impl AppConfig {
             game_preferred_mame: toml.game_preferred_mame,
             favorite_games: toml.favorite_games,
             game_stats: toml.game_stats,
-            game_properties: toml.game_properties,
+            property_layers: {
+                let mut layers = toml.property_layers;
+                layers.migrate_games(toml.game_properties, &toml.default_game_properties);
+                layers
+            },
             default_game_properties: toml.default_game_properties,
+            launch_hooks: toml.launch_hooks,
             hidden_categories: toml.hidden_categories,
             show_filters: toml.show_filters,
             selected_rom: toml.selected_rom,
@@ -984,6 +1028,9 @@ impl AppConfig {
             use_mame_audit: toml.use_mame_audit,
             mame_audit_times: toml.mame_audit_times,
             assume_merged_sets: toml.assume_merged_sets,
+            verify_workers: toml
+                .verify_workers
+                .unwrap_or_else(crate::mame::default_verify_workers),
             graphics_config: toml.graphics_config,
             bgfx_path: toml.bgfx_path,
             last_directories: toml.last_directories,
@@ -1055,6 +1102,7 @@ impl Default for AppConfig {
             gameinit_dat_path: None,
             command_dat_path: None,
             catver_ini_path: None,
+            quarantine_path: None,
 
             // MAME Internal Folders Configuration
             cfg_path: None,
@@ -1073,8 +1121,9 @@ impl Default for AppConfig {
             game_preferred_mame: HashMap::new(),
             favorite_games: HashSet::new(),
             game_stats: HashMap::new(),
-            game_properties: HashMap::new(),
             default_game_properties: super::game_properties::GameProperties::default(),
+            property_layers: PropertyLayers::default(),
+            launch_hooks: LaunchHooks::default(),
 
             // Initialize empty hidden categories
             hidden_categories: HashSet::new(),
@@ -1093,6 +1142,7 @@ impl Default for AppConfig {
             use_mame_audit: false,
             mame_audit_times: HashMap::new(),
             assume_merged_sets: false,
+            verify_workers: crate::mame::default_verify_workers(),
 
             // Default graphics settings
             graphics_config: GraphicsConfig::default(),
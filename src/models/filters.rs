@@ -51,6 +51,9 @@ pub struct OtherFilters {
     pub show_favorites: bool,
     pub show_parents_only: bool,
     pub show_chd_games: bool,
+    /// Games whose sample set is missing or incomplete
+    #[serde(default)]
+    pub show_samples_missing: bool,
 }
 
 impl Default for AvailabilityFilters {
@@ -285,7 +288,8 @@ impl FilterSettings {
             !(self.status_filters.show_working && self.status_filters.show_not_working);
         let other_active = self.other_filters.show_favorites
             || self.other_filters.show_parents_only
-            || self.other_filters.show_chd_games;
+            || self.other_filters.show_chd_games
+            || self.other_filters.show_samples_missing;
 
         availability_active
             || status_active
@@ -333,6 +337,9 @@ impl FilterSettings {
         if self.other_filters.show_chd_games {
             count += 1;
         }
+        if self.other_filters.show_samples_missing {
+            count += 1;
+        }
 
         if !self.cpu_filter.is_empty() {
             count += 1;
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 // Remove unused import: use std::collections::HashMap;
 
 /// Represents a single game/ROM in the MAME system
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Game {
     pub name: String,             // ROM filename without extension
     pub description: String,      // Human-readable game name
@@ -23,6 +23,12 @@ pub struct Game {
     pub chd_name: Option<String>, // Name of the required CHD file (if any)
     // Verification status tracking
     pub verification_status: Option<VerificationStatus>,
+    // Whether the samples listed in the manifest were found
+    #[serde(default)]
+    pub sample_status: SampleStatus,
+    // Full ROM/disk manifest from -listxml
+    #[serde(default)]
+    pub rom_set: super::MachineRomSet,
 }
 
 /// Represents the status of a ROM file
@@ -30,13 +36,14 @@ pub struct Game {
 pub enum RomStatus {
     #[default]
     Unknown, // Status hasn't been determined yet
-    Available,   // ROM file is present and correct
-    Missing,     // ROM file is not found
-    Incorrect,   // ROM file exists but has wrong checksum
-    NotWorking,  // ROM is present but game doesn't work
-    Preliminary, // Early driver, not fully working
-    ChdRequired, // ROM is available but CHD is required
-    ChdMissing,  // ROM is available but CHD is missing
+    Available,    // ROM file is present and correct
+    Missing,      // ROM file is not found
+    Incorrect,    // ROM file exists but has wrong checksum
+    NotWorking,   // ROM is present but game doesn't work
+    Preliminary,  // Early driver, not fully working
+    ChdRequired,  // ROM is available but CHD is required
+    ChdMissing,   // ROM is available but CHD is missing
+    ChdIncorrect, // CHD is present but its SHA1 does not match listxml
 }
 
 /// Represents the verification status from ROM verification
@@ -50,6 +57,31 @@ pub enum VerificationStatus {
     NotVerified, // ROM hasn't been verified yet
 }
 
+/// Whether the samples a game plays are in the configured sample paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SampleStatus {
+    #[default]
+    NotRequired, // Game uses no samples
+    Available,  // Every sample was found
+    Incomplete, // Sample set found, but some samples are missing from it
+    Missing,    // No sample set found
+}
+
+impl SampleStatus {
+    pub fn is_missing(self) -> bool {
+        matches!(self, SampleStatus::Incomplete | SampleStatus::Missing)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SampleStatus::NotRequired => "None required",
+            SampleStatus::Available => "Available",
+            SampleStatus::Incomplete => "Incomplete",
+            SampleStatus::Missing => "Missing",
+        }
+    }
+}
+
 impl RomStatus {
     pub fn to_icon(self) -> &'static str {
         match self {
@@ -60,6 +92,7 @@ impl RomStatus {
             RomStatus::Preliminary => "🟡",
             RomStatus::ChdRequired => "💿",
             RomStatus::ChdMissing => "💿❌",
+            RomStatus::ChdIncorrect => "💿⚠️",
             RomStatus::Unknown => "❓",
         }
     }
@@ -73,6 +106,7 @@ impl RomStatus {
             RomStatus::Preliminary => "Preliminary",
             RomStatus::ChdRequired => "CHD Required",
             RomStatus::ChdMissing => "CHD Missing",
+            RomStatus::ChdIncorrect => "CHD Incorrect",
             RomStatus::Unknown => "Unknown",
         }
     }
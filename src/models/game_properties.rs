@@ -294,6 +294,8 @@ pub enum ScreenSelection {
 pub enum Resolution {
     Auto,
     Custom(u32, u32),
+    /// Width, height and refresh rate, MAME's `WxH@R`
+    CustomRefresh(u32, u32, u32),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
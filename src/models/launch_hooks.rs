@@ -0,0 +1,120 @@
+// src/models/launch_hooks.rs
+//! Commands run around a game: before MAME starts and after it exits, set
+//! for every game, a category or a single game. Each scope holds one
+//! command per line.
+use super::Game;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookStage {
+    BeforeLaunch,
+    AfterExit,
+}
+
+impl HookStage {
+    pub fn label(self) -> &'static str {
+        match self {
+            HookStage::BeforeLaunch => "Pre-launch",
+            HookStage::AfterExit => "Post-exit",
+        }
+    }
+}
+
+/// Commands of one scope, one per line
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookCommands {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub before: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub after: String,
+}
+
+impl HookCommands {
+    pub fn is_empty(&self) -> bool {
+        self.lines(HookStage::BeforeLaunch).next().is_none()
+            && self.lines(HookStage::AfterExit).next().is_none()
+    }
+
+    pub fn text_mut(&mut self, stage: HookStage) -> &mut String {
+        match stage {
+            HookStage::BeforeLaunch => &mut self.before,
+            HookStage::AfterExit => &mut self.after,
+        }
+    }
+
+    /// Non-blank lines; `#` starts a comment line
+    pub fn lines(&self, stage: HookStage) -> impl Iterator<Item = &str> {
+        let text = match stage {
+            HookStage::BeforeLaunch => &self.before,
+            HookStage::AfterExit => &self.after,
+        };
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchHooks {
+    #[serde(default)]
+    pub global: HookCommands,
+    #[serde(default)]
+    pub categories: BTreeMap<String, HookCommands>,
+    #[serde(default)]
+    pub games: BTreeMap<String, HookCommands>,
+    /// Seconds a command may run before it is killed
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Skip the launch when a pre-launch command fails
+    #[serde(default)]
+    pub abort_on_failure: bool,
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for LaunchHooks {
+    fn default() -> Self {
+        Self {
+            global: HookCommands::default(),
+            categories: BTreeMap::new(),
+            games: BTreeMap::new(),
+            timeout_secs: default_timeout_secs(),
+            abort_on_failure: false,
+        }
+    }
+}
+
+impl LaunchHooks {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Commands to run for a game, unexpanded. Before launch they go from
+    /// the widest scope to the narrowest; after exit the other way round,
+    /// so a game's hook can undo its own setup before the global one does.
+    pub fn commands(&self, game: &Game, stage: HookStage) -> Vec<String> {
+        let mut scopes = vec![
+            Some(&self.global),
+            self.categories.get(&game.category),
+            self.games.get(&game.name),
+        ];
+        if stage == HookStage::AfterExit {
+            scopes.reverse();
+        }
+        scopes
+            .into_iter()
+            .flatten()
+            .flat_map(|commands| commands.lines(stage))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Drop categories and games left without commands
+    pub fn prune(&mut self) {
+        self.categories.retain(|_, commands| !commands.is_empty());
+        self.games.retain(|_, commands| !commands.is_empty());
+    }
+}
@@ -6,12 +6,18 @@ pub mod config;
 pub mod filters;
 pub mod game;
 pub mod game_properties;
+pub mod launch_hooks;
+pub mod property_layers;
+pub mod rom_set;
 
 // Re-export everything from submodules
 pub use config::*;
 pub use filters::*;
 pub use game::*;
 pub use game_properties::*;
+pub use launch_hooks::*;
+pub use property_layers::*;
+pub use rom_set::*;
 
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -34,6 +40,8 @@ pub enum LoadingMessage {
     MameLoadFailed(String),
     RomScanStarted,
     RomScanProgress(usize, usize),
+    RomSetDetected(crate::utils::rom_utils::RomSetDetection),
+    RomScanCached(Box<crate::utils::rom_utils::RomScanCache>),
     RomScanComplete(Vec<crate::models::Game>),
     RomScanFailed(String),
 }
@@ -109,6 +117,30 @@ impl GameIndex {
         }
     }
 
+    /// Replace one game after its status changed, moving it between the
+    /// available/missing buckets while keeping them sorted
+    pub fn update_game(&mut self, idx: usize, game: crate::models::Game) {
+        let Some(slot) = self.games.get_mut(idx) else {
+            return;
+        };
+        *slot = game;
+
+        let status = self.games[idx].status;
+        for (bucket, wanted) in [
+            (&mut self.available_games, RomStatus::Available),
+            (&mut self.missing_games, RomStatus::Missing),
+        ] {
+            match (bucket.binary_search(&idx), status == wanted) {
+                (Err(pos), true) => bucket.insert(pos, idx),
+                (Ok(pos), false) => {
+                    bucket.remove(pos);
+                }
+                _ => {}
+            }
+        }
+        self.search_cache.clear();
+    }
+
     pub fn has_clones(&self, game_name: &str) -> bool {
         self.games
             .iter()
@@ -255,6 +287,14 @@ pub struct GameStats {
     pub play_count: u32,
     pub last_played: Option<String>,
     pub total_play_time: u32, // dalam detik
+    /// Sessions that ended in a crash rather than a normal exit
+    #[serde(default)]
+    pub crash_count: u32,
+    /// How the last session ended, e.g. "exit code 2" or "crashed (killed by SIGSEGV ...)"
+    #[serde(default)]
+    pub last_exit: Option<String>,
+    #[serde(default)]
+    pub last_crashed: bool,
 }
 
 // VisibleColumns mengontrol kolom mana yang ditampilkan di game list
@@ -324,6 +364,9 @@ pub struct Preferences {
     /// Experimental Steam-inspired shell. When set to RedesignPreview, legacy layout is skipped.
     #[serde(default)]
     pub ui_shell: UiShellMode,
+
+    #[serde(default)]
+    pub instance_policy: InstancePolicy,
 }
 
 impl Preferences {
@@ -358,6 +401,7 @@ impl Default for Preferences {
             use_dock_layout: true,
             enable_toast_notifications: true,
             ui_shell: UiShellMode::default(),
+            instance_policy: InstancePolicy::default(),
         }
     }
 }
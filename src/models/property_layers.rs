@@ -0,0 +1,423 @@
+// src/models/property_layers.rs
+//! Launch options layered the way MAME layers its ini files: the defaults
+//! (mame.ini), then vertical.ini or horizontal.ini, source/<driver>.ini, the
+//! parent's ini and finally the game's own. Every level above the defaults
+//! only stores the options it changes, keyed by their path in
+//! `GameProperties` (e.g. `display.run_in_window`) with JSON-encoded values.
+use super::{Game, GameProperties};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Option path → JSON-encoded value
+pub type PropertyDelta = BTreeMap<String, String>;
+
+/// Where an option's value comes from, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PropertyLevel {
+    Default,
+    Orientation,
+    Source,
+    Parent,
+    Game,
+}
+
+impl PropertyLevel {
+    pub const ALL: [PropertyLevel; 5] = [
+        PropertyLevel::Default,
+        PropertyLevel::Orientation,
+        PropertyLevel::Source,
+        PropertyLevel::Parent,
+        PropertyLevel::Game,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PropertyLevel::Default => "Default",
+            PropertyLevel::Orientation => "Orientation",
+            PropertyLevel::Source => "Source",
+            PropertyLevel::Parent => "Parent",
+            PropertyLevel::Game => "Game",
+        }
+    }
+}
+
+/// Sparse overrides stored above the default properties
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PropertyLayers {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vertical: PropertyDelta,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub horizontal: PropertyDelta,
+    /// Keyed by source file stem, e.g. `pacman` for `namco/pacman.cpp`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub source: BTreeMap<String, PropertyDelta>,
+    /// Keyed by machine name; a parent's overrides also apply to its clones
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub game: BTreeMap<String, PropertyDelta>,
+}
+
+/// Properties of a game together with the level each option was set at
+#[derive(Debug, Clone)]
+pub struct EffectiveProperties {
+    pub properties: GameProperties,
+    origins: BTreeMap<String, PropertyLevel>,
+}
+
+impl EffectiveProperties {
+    pub fn origin(&self, path: &str) -> PropertyLevel {
+        self.origins
+            .get(path)
+            .copied()
+            .unwrap_or(PropertyLevel::Default)
+    }
+
+    /// Options set above the defaults, by path
+    pub fn overrides(&self) -> impl Iterator<Item = (&str, PropertyLevel)> {
+        self.origins
+            .iter()
+            .map(|(path, level)| (path.as_str(), *level))
+    }
+
+    /// Current value of an option as shown in MAME-style listings
+    pub fn value(&self, path: &str) -> String {
+        match option_value(&self.properties, path) {
+            Some(Value::String(text)) => text,
+            Some(other) => other.to_string(),
+            None => String::new(),
+        }
+    }
+}
+
+impl PropertyLayers {
+    pub fn is_empty(&self) -> bool {
+        self.vertical.is_empty()
+            && self.horizontal.is_empty()
+            && self.source.is_empty()
+            && self.game.is_empty()
+    }
+
+    /// What a level is called for this game, or None when it doesn't apply
+    /// (no parent for an original set, no known source file)
+    pub fn layer_name(game: &Game, level: PropertyLevel) -> Option<String> {
+        match level {
+            PropertyLevel::Default => Some("All games".to_string()),
+            PropertyLevel::Orientation if game.rom_set.vertical => {
+                Some("Vertical games".to_string())
+            }
+            PropertyLevel::Orientation => Some("Horizontal games".to_string()),
+            PropertyLevel::Source => source_key(game).map(|source| format!("Source {}", source)),
+            PropertyLevel::Parent => parent_key(game).map(|parent| format!("Parent {}", parent)),
+            PropertyLevel::Game => Some(format!("Game {}", game.name)),
+        }
+    }
+
+    /// Properties of `game` with every level up to and including `through`
+    pub fn resolve(
+        &self,
+        defaults: &GameProperties,
+        game: &Game,
+        through: PropertyLevel,
+    ) -> EffectiveProperties {
+        let mut root = to_tree(defaults);
+        let mut origins = BTreeMap::new();
+        for level in PropertyLevel::ALL
+            .into_iter()
+            .filter(|level| *level != PropertyLevel::Default && *level <= through)
+        {
+            let Some(delta) = self.delta(game, level) else {
+                continue;
+            };
+            for (path, encoded) in delta {
+                if apply_option(&mut root, path, encoded) {
+                    origins.insert(path.clone(), level);
+                }
+            }
+        }
+
+        let mut properties: GameProperties =
+            serde_json::from_value(root).unwrap_or_else(|_| defaults.clone());
+        properties.game_name = game.name.clone();
+        EffectiveProperties {
+            properties,
+            origins,
+        }
+    }
+
+    /// Keep at `level` only the options of `properties` that differ from
+    /// what the levels below already give
+    pub fn store(
+        &mut self,
+        defaults: &GameProperties,
+        game: &Game,
+        level: PropertyLevel,
+        properties: &GameProperties,
+    ) {
+        let Some(below) = PropertyLevel::ALL.into_iter().rfind(|below| *below < level) else {
+            return;
+        };
+        let delta = diff(&self.resolve(defaults, game, below).properties, properties);
+        let empty = delta.is_empty();
+        match level {
+            PropertyLevel::Default => {}
+            PropertyLevel::Orientation if game.rom_set.vertical => self.vertical = delta,
+            PropertyLevel::Orientation => self.horizontal = delta,
+            PropertyLevel::Source => {
+                if let Some(source) = source_key(game) {
+                    store_keyed(&mut self.source, source, delta, empty);
+                }
+            }
+            PropertyLevel::Parent => {
+                if let Some(parent) = parent_key(game) {
+                    store_keyed(&mut self.game, parent, delta, empty);
+                }
+            }
+            PropertyLevel::Game => store_keyed(&mut self.game, game.name.clone(), delta, empty),
+        }
+    }
+
+    /// Turn full per-game properties from older configs into game deltas
+    pub fn migrate_games(
+        &mut self,
+        legacy: HashMap<String, GameProperties>,
+        defaults: &GameProperties,
+    ) {
+        for (name, properties) in legacy {
+            let delta = diff(defaults, &properties);
+            if !delta.is_empty() {
+                self.game.entry(name).or_insert(delta);
+            }
+        }
+    }
+
+    fn delta(&self, game: &Game, level: PropertyLevel) -> Option<&PropertyDelta> {
+        match level {
+            PropertyLevel::Default => None,
+            PropertyLevel::Orientation if game.rom_set.vertical => Some(&self.vertical),
+            PropertyLevel::Orientation => Some(&self.horizontal),
+            PropertyLevel::Source => self.source.get(&source_key(game)?),
+            PropertyLevel::Parent => self.game.get(&parent_key(game)?),
+            PropertyLevel::Game => self.game.get(&game.name),
+        }
+    }
+}
+
+/// Properties with the options of a delta set on top
+pub fn apply_delta(properties: &GameProperties, delta: &PropertyDelta) -> GameProperties {
+    let mut root = to_tree(properties);
+    for (path, encoded) in delta {
+        apply_option(&mut root, path, encoded);
+    }
+    serde_json::from_value(root).unwrap_or_else(|_| properties.clone())
+}
+
+/// One option's value by path, e.g. `sound.sample_rate`
+pub fn option_value(properties: &GameProperties, path: &str) -> Option<Value> {
+    path.split('.')
+        .try_fold(&to_tree(properties), |node, part| node.get(part))
+        .cloned()
+}
+
+/// Options that differ between two sets of properties
+pub fn diff(base: &GameProperties, properties: &GameProperties) -> PropertyDelta {
+    let base = flatten(&to_tree(base));
+    flatten(&to_tree(properties))
+        .into_iter()
+        .filter(|(path, value)| base.get(path) != Some(value))
+        .map(|(path, value)| (path, value.to_string()))
+        .collect()
+}
+
+fn store_keyed(
+    map: &mut BTreeMap<String, PropertyDelta>,
+    key: String,
+    delta: PropertyDelta,
+    empty: bool,
+) {
+    if empty {
+        map.remove(&key);
+    } else {
+        map.insert(key, delta);
+    }
+}
+
+fn source_key(game: &Game) -> Option<String> {
+    let source = game.rom_set.source_file.as_deref()?;
+    Path::new(source)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+}
+
+fn parent_key(game: &Game) -> Option<String> {
+    game.parent.clone().filter(|_| game.is_clone)
+}
+
+fn to_tree(properties: &GameProperties) -> Value {
+    serde_json::to_value(properties).unwrap_or_default()
+}
+
+/// Every option by path, without the name the properties are stored under
+fn flatten(root: &Value) -> BTreeMap<String, Value> {
+    let mut leaves = BTreeMap::new();
+    flatten_into(root, "", &mut leaves);
+    leaves.remove("game_name");
+    leaves
+}
+
+fn flatten_into(value: &Value, prefix: &str, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if is_struct(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_into(child, &path, leaves);
+            }
+        }
+        _ => {
+            leaves.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// Struct fields are snake_case; enum variants (`{"Custom": [640, 480]}`)
+/// are capitalized and are kept as one option
+fn is_struct(map: &serde_json::Map<String, Value>) -> bool {
+    !map.is_empty()
+        && map
+            .keys()
+            .all(|key| key.starts_with(|c: char| c.is_ascii_lowercase()))
+}
+
+/// Set one option, skipping paths and values this version doesn't know
+fn apply_option(root: &mut Value, path: &str, encoded: &str) -> bool {
+    let Ok(value) = serde_json::from_str::<Value>(encoded) else {
+        return false;
+    };
+    let mut candidate = root.clone();
+    let Some(slot) = path
+        .split('.')
+        .try_fold(&mut candidate, |node, part| node.get_mut(part))
+    else {
+        return false;
+    };
+    *slot = value;
+    if serde_json::from_value::<GameProperties>(candidate.clone()).is_err() {
+        return false;
+    }
+    *root = candidate;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MachineRomSet, RomStatus, VideoMode};
+
+    fn game(name: &str, parent: Option<&str>, vertical: bool) -> Game {
+        Game {
+            name: name.to_string(),
+            description: name.to_string(),
+            status: RomStatus::Available,
+            parent: parent.map(str::to_string),
+            is_clone: parent.is_some(),
+            rom_set: MachineRomSet {
+                source_file: Some("namco/pacman.cpp".to_string()),
+                vertical,
+                ..Default::default()
+            },
+            ..Game::default()
+        }
+    }
+
+    #[test]
+    fn levels_stack_as_sparse_deltas_with_origins() {
+        let defaults = GameProperties::default();
+        let mut layers = PropertyLayers::default();
+        let mspacman = game("mspacman", Some("pacman"), true);
+        let pacman = game("pacman", None, true);
+
+        let mut vertical = defaults.clone();
+        vertical.display.run_in_window = false;
+        vertical.display.video_mode = VideoMode::OpenGL;
+        layers.store(&defaults, &pacman, PropertyLevel::Orientation, &vertical);
+        assert_eq!(layers.vertical.len(), 2);
+
+        let mut source = layers
+            .resolve(&defaults, &pacman, PropertyLevel::Source)
+            .properties;
+        source.sound.sample_rate = 44100;
+        layers.store(&defaults, &pacman, PropertyLevel::Source, &source);
+        assert_eq!(
+            layers.source["pacman"],
+            PropertyDelta::from([("sound.sample_rate".to_string(), "44100".to_string())])
+        );
+
+        // The parent's own settings reach its clones through the parent level
+        let mut parent = layers
+            .resolve(&defaults, &pacman, PropertyLevel::Game)
+            .properties;
+        parent.display.video_mode = VideoMode::BGFX;
+        parent.miscellaneous.custom_args = "-nvram_directory /tmp".to_string();
+        layers.store(&defaults, &pacman, PropertyLevel::Game, &parent);
+
+        let mut clone = layers
+            .resolve(&defaults, &mspacman, PropertyLevel::Game)
+            .properties;
+        clone.display.run_in_window = true;
+        layers.store(&defaults, &mspacman, PropertyLevel::Game, &clone);
+
+        let effective = layers.resolve(&defaults, &mspacman, PropertyLevel::Game);
+        assert_eq!(effective.properties.game_name, "mspacman");
+        assert!(effective.properties.display.run_in_window);
+        assert_eq!(effective.properties.display.video_mode, VideoMode::BGFX);
+        assert_eq!(effective.properties.sound.sample_rate, 44100);
+        assert_eq!(
+            effective.properties.miscellaneous.custom_args,
+            "-nvram_directory /tmp"
+        );
+        assert_eq!(
+            effective.origin("display.run_in_window"),
+            PropertyLevel::Game
+        );
+        assert_eq!(
+            effective.origin("display.video_mode"),
+            PropertyLevel::Parent
+        );
+        assert_eq!(effective.origin("sound.sample_rate"), PropertyLevel::Source);
+        assert_eq!(effective.origin("display.throttle"), PropertyLevel::Default);
+        assert_eq!(effective.value("display.video_mode"), "BGFX");
+
+        // Horizontal games don't see the vertical overrides
+        let horizontal = game("galaxian", None, false);
+        assert!(
+            layers
+                .resolve(&defaults, &horizontal, PropertyLevel::Game)
+                .properties
+                .display
+                .run_in_window
+        );
+
+        // Storing what the lower levels already give clears the level
+        let below = layers
+            .resolve(&defaults, &mspacman, PropertyLevel::Parent)
+            .properties;
+        layers.store(&defaults, &mspacman, PropertyLevel::Game, &below);
+        assert!(!layers.game.contains_key("mspacman"));
+
+        // Options this version doesn't know are skipped
+        layers
+            .game
+            .entry("pacman".to_string())
+            .or_default()
+            .insert("display.removed_option".to_string(), "true".to_string());
+        let effective = layers.resolve(&defaults, &pacman, PropertyLevel::Game);
+        assert_eq!(
+            effective.origin("display.removed_option"),
+            PropertyLevel::Default
+        );
+    }
+}
@@ -0,0 +1,162 @@
+// src/models/rom_set.rs
+use serde::{Deserialize, Serialize};
+
+/// Dump status MAME reports for a ROM or disk (`status` attribute in -listxml)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DumpStatus {
+    #[default]
+    Good,
+    BadDump, // Known-bad dump, checksums are best available
+    NoDump,  // No known dump exists, nothing to check
+}
+
+impl DumpStatus {
+    pub fn from_xml(value: Option<&str>) -> Self {
+        match value {
+            Some("baddump") => DumpStatus::BadDump,
+            Some("nodump") => DumpStatus::NoDump,
+            _ => DumpStatus::Good,
+        }
+    }
+}
+
+/// A single `<rom>` entry from -listxml
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct RomEntry {
+    pub name: String,
+    pub size: u64,
+    pub crc: Option<String>,   // Lowercase hex CRC32
+    pub sha1: Option<String>,  // Lowercase hex SHA1
+    pub merge: Option<String>, // Name of the same ROM in the parent/BIOS set
+    pub bios: Option<String>,  // BIOS option this ROM belongs to
+    pub region: Option<String>,
+    pub optional: bool,
+    pub status: DumpStatus,
+}
+
+/// A single `<disk>` entry (CHD) from -listxml
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct DiskEntry {
+    pub name: String,
+    pub sha1: Option<String>,
+    pub merge: Option<String>,
+    pub region: Option<String>,
+    pub index: Option<u32>,
+    pub writable: bool,
+    pub optional: bool,
+    pub status: DumpStatus,
+}
+
+/// Full ROM and disk manifest of a machine, plus its set relationships
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct MachineRomSet {
+    pub cloneof: Option<String>, // Parent machine (clone relationship)
+    pub romof: Option<String>,   // Machine whose ROMs this set shares (parent or BIOS)
+    pub roms: Vec<RomEntry>,
+    pub disks: Vec<DiskEntry>,
+    pub device_refs: Vec<String>, // Devices whose ROMs this machine also needs
+    #[serde(default)]
+    pub sampleof: Option<String>, // Sample set shared with another machine
+    #[serde(default)]
+    pub samples: Vec<String>, // `<sample name>` entries, without the .wav extension
+    #[serde(default)]
+    pub software_lists: Vec<SoftwareListRef>, // Software lists the machine can load from
+    #[serde(default)]
+    pub media_slots: Vec<MediaSlot>, // Media devices software can be mounted in
+    #[serde(default)]
+    pub source_file: Option<String>, // Driver source, e.g. `namco/pacman.cpp`
+    #[serde(default)]
+    pub vertical: bool, // First screen is rotated 90 or 270 degrees
+}
+
+/// Whether a software list was made for the machine or merely works on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SoftwareListStatus {
+    #[default]
+    Original,
+    Compatible,
+}
+
+impl SoftwareListStatus {
+    pub fn from_xml(value: Option<&str>) -> Self {
+        match value {
+            Some("compatible") => SoftwareListStatus::Compatible,
+            _ => SoftwareListStatus::Original,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SoftwareListStatus::Original => "original",
+            SoftwareListStatus::Compatible => "compatible",
+        }
+    }
+}
+
+/// A `<softwarelist name status filter>` entry from -listxml
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SoftwareListRef {
+    pub name: String,
+    pub status: SoftwareListStatus,
+    /// Comma-separated values matched against a software item's
+    /// `compatibility` feature (e.g. "NTSC")
+    pub filter: Option<String>,
+}
+
+impl SoftwareListRef {
+    /// Whether the machine runs an item with these `compatibility` and
+    /// `incompatibility` features, following MAME's own softlist check:
+    /// without a filter everything runs, an item incompatible with any
+    /// filter value does not, and an item that lists its compatibility
+    /// needs one of the filter values in it.
+    pub fn accepts(&self, compatibility: Option<&str>, incompatibility: Option<&str>) -> bool {
+        let Some(filter) = &self.filter else {
+            return true;
+        };
+        let contains = |features: &str, value: &str| {
+            features.split(',').any(|feature| feature.trim() == value)
+        };
+        let values: Vec<&str> = filter
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .collect();
+
+        if let Some(incompatibility) = incompatibility
+            && values.iter().any(|value| contains(incompatibility, value))
+        {
+            return false;
+        }
+        match compatibility {
+            Some(compatibility) => values.iter().any(|value| contains(compatibility, value)),
+            None => true,
+        }
+    }
+}
+
+/// A media device of a machine (`<device interface>` with its `<instance>`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct MediaSlot {
+    pub briefname: String, // Command-line switch without the dash, e.g. "flop1"
+    pub interfaces: Vec<String>, // Software part interfaces the slot accepts
+}
+
+impl MachineRomSet {
+    pub fn is_empty(&self) -> bool {
+        self.roms.is_empty() && self.disks.is_empty()
+    }
+
+    /// ROMs that actually have a dump to look for
+    pub fn dumped_roms(&self) -> impl Iterator<Item = &RomEntry> {
+        self.roms
+            .iter()
+            .filter(|rom| rom.status != DumpStatus::NoDump)
+    }
+
+    /// Disks that actually have a dump to look for
+    pub fn dumped_disks(&self) -> impl Iterator<Item = &DiskEntry> {
+        self.disks
+            .iter()
+            .filter(|disk| disk.status != DumpStatus::NoDump)
+    }
+}
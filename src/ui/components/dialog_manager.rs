@@ -6,9 +6,12 @@ use crate::ui::components::advanced_mame_settings::AdvancedMameSettingsDialog;
 use crate::ui::components::directories::DirectoriesDialog;
 use crate::ui::components::game_properties::GamePropertiesDialog;
 use crate::ui::components::hidden_categories::HiddenCategoriesDialog;
+use crate::ui::components::launch_hooks::LaunchHooksDialog;
 use crate::ui::components::mame_finder::{FoundMame, MameFinderDialog};
 use crate::ui::components::preferences::PreferencesDialog;
+use crate::ui::components::rom_cleanup::RomCleanupDialog;
 use crate::ui::components::rom_info::RomInfoDialog;
+use crate::ui::components::rom_rebuild::RomRebuildDialog;
 use crate::ui::components::rom_verify::{RomVerifyDialog, VerificationManager};
 use eframe::egui;
 use std::collections::HashMap;
@@ -36,7 +39,10 @@ pub enum DialogType {
     ManualMame,
     GameProperties,
     RomVerify,
+    RomRebuild,
+    RomCleanup,
     AdvancedMameSettings,
+    LaunchHooks,
 }
 
 /// Dialog state management
@@ -48,6 +54,9 @@ pub struct DialogManager {
     found_mame_executables: Vec<FoundMame>,
     directories_dialog: DirectoriesDialog,
     rom_verify_dialog: RomVerifyDialog,
+    rom_rebuild_dialog: RomRebuildDialog,
+    rom_cleanup_dialog: RomCleanupDialog,
+    launch_hooks_dialog: LaunchHooksDialog,
     game_properties_dialog: Option<GamePropertiesDialog>,
     advanced_mame_settings_dialog: Option<AdvancedMameSettingsDialog>,
 
@@ -70,7 +79,10 @@ impl DialogManager {
             DialogType::MameFinder,
             DialogType::ManualMame,
             DialogType::GameProperties,
+            DialogType::RomRebuild,
+            DialogType::RomCleanup,
             DialogType::AdvancedMameSettings,
+            DialogType::LaunchHooks,
         ] {
             dialog_states.insert(dialog_type, false);
         }
@@ -80,6 +92,9 @@ impl DialogManager {
             found_mame_executables: Vec::new(),
             directories_dialog: DirectoriesDialog::new(),
             rom_verify_dialog: RomVerifyDialog::default(),
+            rom_rebuild_dialog: RomRebuildDialog::default(),
+            rom_cleanup_dialog: RomCleanupDialog::default(),
+            launch_hooks_dialog: LaunchHooksDialog::default(),
             game_properties_dialog: None,
             advanced_mame_settings_dialog: None,
             on_dialog_closed: None,
@@ -319,6 +334,46 @@ impl DialogManager {
             self.rom_verify_dialog.show_window(ctx, config, games);
         }
 
+        // ROM Rebuild Dialog
+        if self.is_dialog_open(DialogType::RomRebuild) {
+            self.rom_rebuild_dialog.show(
+                ctx,
+                self.dialog_states.get_mut(&DialogType::RomRebuild).unwrap(),
+                config,
+                games,
+            );
+        }
+
+        // ROM Cleanup Dialog
+        if self.is_dialog_open(DialogType::RomCleanup)
+            && self.rom_cleanup_dialog.show(
+                ctx,
+                self.dialog_states.get_mut(&DialogType::RomCleanup).unwrap(),
+                config,
+                games,
+            )
+        {
+            actions.push(DialogAction::SaveConfig);
+        }
+
+        // Launch Hooks Dialog
+        if self.is_dialog_open(DialogType::LaunchHooks) {
+            self.launch_hooks_dialog.show(
+                ctx,
+                self.dialog_states
+                    .get_mut(&DialogType::LaunchHooks)
+                    .unwrap(),
+                config,
+                games,
+                selected_game.and_then(|idx| games.get(idx)),
+            );
+
+            if !self.is_dialog_open(DialogType::LaunchHooks) {
+                config.launch_hooks.prune();
+                actions.push(DialogAction::SaveConfig);
+            }
+        }
+
         // Game Properties Dialog
         if self.is_dialog_open(DialogType::GameProperties)
             && let Some(dialog) = &mut self.game_properties_dialog
@@ -400,7 +455,10 @@ impl DialogManager {
             DialogType::MameFinder,
             DialogType::ManualMame,
             DialogType::GameProperties,
+            DialogType::RomRebuild,
+            DialogType::RomCleanup,
             DialogType::AdvancedMameSettings,
+            DialogType::LaunchHooks,
         ] {
             self.close_dialog(dialog_type);
         }
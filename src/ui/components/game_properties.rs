@@ -1,5 +1,5 @@
-use crate::models::Game;
 use crate::models::game_properties::*;
+use crate::models::{EffectiveProperties, Game, PropertyLayers, PropertyLevel};
 use eframe::egui;
 use std::path::Path;
 
@@ -15,6 +15,12 @@ pub struct GamePropertiesDialog {
     selected_tab: PropertiesTab,
     is_default_game: bool,
     original_properties: GameProperties,
+    /// Game whose layers are edited; None edits the defaults
+    game: Option<Game>,
+    /// Level the edited values are saved at
+    level: PropertyLevel,
+    /// What the game launches with, for the origin view
+    effective: Option<EffectiveProperties>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -30,27 +36,33 @@ enum PropertiesTab {
 
 impl GamePropertiesDialog {
     pub fn new_with_config(game: Option<&Game>, config: &crate::models::AppConfig) -> Self {
-        let properties = if let Some(g) = game {
-            // Load saved properties for this specific game
-            config
-                .game_properties
-                .get(&g.name)
-                .cloned()
-                .unwrap_or_else(|| GameProperties {
-                    game_name: g.name.clone(),
-                    ..config.default_game_properties.clone()
-                })
-        } else {
-            // Use default properties
-            config.default_game_properties.clone()
-        };
-
-        Self {
+        let properties = config.default_game_properties.clone();
+        let mut dialog = Self {
             original_properties: properties.clone(),
             properties,
             selected_tab: PropertiesTab::Display,
             is_default_game: game.is_none(),
+            game: game.cloned(),
+            level: PropertyLevel::Default,
+            effective: None,
+        };
+        if game.is_some() {
+            dialog.select_level(PropertyLevel::Game, config);
         }
+        dialog
+    }
+
+    /// Edit another level: shows what the game gets up to that level
+    fn select_level(&mut self, level: PropertyLevel, config: &crate::models::AppConfig) {
+        let Some(game) = &self.game else {
+            return;
+        };
+        let layers = &config.property_layers;
+        let defaults = &config.default_game_properties;
+        self.level = level;
+        self.properties = layers.resolve(defaults, game, level).properties;
+        self.original_properties = self.properties.clone();
+        self.effective = Some(layers.resolve(defaults, game, PropertyLevel::Game));
     }
 
     pub fn show(
@@ -106,15 +118,19 @@ impl GamePropertiesDialog {
 
                 ui.separator();
 
-                // Global game options info
-                ui.horizontal(|ui| {
-                    // Placeholder for icon
-                    ui.label("🎮");
-                    ui.vertical(|ui| {
-                        ui.label("Global game options");
-                        ui.label("Default options used by all games");
+                if self.game.is_some() {
+                    self.show_level_selector(ui, config);
+                } else {
+                    // Global game options info
+                    ui.horizontal(|ui| {
+                        // Placeholder for icon
+                        ui.label("🎮");
+                        ui.vertical(|ui| {
+                            ui.label("Global game options");
+                            ui.label("Default options used by all games");
+                        });
                     });
-                });
+                }
 
                 ui.separator();
 
@@ -992,6 +1008,7 @@ impl GamePropertiesDialog {
                     .selected_text(match &misc.resolution {
                         Resolution::Auto => "Auto".to_string(),
                         Resolution::Custom(w, h) => format!("{}x{}", w, h),
+                        Resolution::CustomRefresh(w, h, r) => format!("{}x{}@{}", w, h, r),
                     })
                     .show_ui(ui, |ui| {
                         ui.selectable_value(&mut misc.resolution, Resolution::Auto, "Auto");
@@ -1560,15 +1577,100 @@ impl GamePropertiesDialog {
         ui.label("• None: Disable specific input type");
     }
 
+    /// Level picker and the list of options set above the defaults
+    fn show_level_selector(&mut self, ui: &mut egui::Ui, config: &crate::models::AppConfig) {
+        let Some(game) = &self.game else {
+            return;
+        };
+        let levels: Vec<(PropertyLevel, String)> = PropertyLevel::ALL
+            .into_iter()
+            .filter(|level| *level != PropertyLevel::Default)
+            .filter_map(|level| Some((level, PropertyLayers::layer_name(game, level)?)))
+            .collect();
+
+        let mut selected = self.level;
+        ui.horizontal(|ui| {
+            ui.label("🎮");
+            ui.label("Save changes for:");
+            let current = levels
+                .iter()
+                .find(|(level, _)| *level == selected)
+                .map(|(_, name)| name.clone())
+                .unwrap_or_default();
+            egui::ComboBox::from_id_salt("properties_level")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for (level, name) in &levels {
+                        ui.selectable_value(&mut selected, *level, name);
+                    }
+                });
+        })
+        .response
+        .on_hover_text(
+            "Like MAME's ini files, each level only keeps what it changes; \
+             the game level wins over parent, source, orientation and defaults",
+        );
+
+        if let Some(effective) = &self.effective {
+            let overrides: Vec<(&str, PropertyLevel)> = effective.overrides().collect();
+            egui::CollapsingHeader::new(format!(
+                "Where values come from ({} overridden)",
+                overrides.len()
+            ))
+            .id_salt("properties_origins")
+            .show(ui, |ui| {
+                if overrides.is_empty() {
+                    ui.weak("Every option comes from the defaults.");
+                    return;
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(140.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("properties_origin_grid")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong("Option");
+                                ui.strong("Value");
+                                ui.strong("Set by");
+                                ui.end_row();
+                                for (path, level) in overrides {
+                                    ui.monospace(path);
+                                    ui.label(effective.value(path));
+                                    ui.label(
+                                        PropertyLayers::layer_name(game, level)
+                                            .unwrap_or_else(|| level.label().to_string()),
+                                    );
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        }
+
+        if selected != self.level {
+            self.select_level(selected, config);
+        }
+    }
+
     fn apply_changes(&mut self, config: &mut crate::models::AppConfig) {
-        if self.is_default_game {
+        match &self.game {
             // Save as default properties
-            config.default_game_properties = self.properties.clone();
-        } else {
-            // Save as per-game properties
-            config
-                .game_properties
-                .insert(self.properties.game_name.clone(), self.properties.clone());
+            None => config.default_game_properties = self.properties.clone(),
+            // Keep only what differs from the levels below
+            Some(game) => {
+                config.property_layers.store(
+                    &config.default_game_properties,
+                    game,
+                    self.level,
+                    &self.properties,
+                );
+                self.effective = Some(config.property_layers.resolve(
+                    &config.default_game_properties,
+                    game,
+                    PropertyLevel::Game,
+                ));
+            }
         }
 
         // Mark properties as saved
@@ -0,0 +1,188 @@
+// src/ui/components/launch_hooks.rs
+// Editor for the commands run before a game starts and after it exits
+
+use crate::models::{AppConfig, Game, HookCommands, HookStage};
+use eframe::egui;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HookScope {
+    Global,
+    Category,
+    Game,
+}
+
+pub struct LaunchHooksDialog {
+    scope: HookScope,
+    category: String,
+}
+
+impl Default for LaunchHooksDialog {
+    fn default() -> Self {
+        Self {
+            scope: HookScope::Global,
+            category: String::new(),
+        }
+    }
+}
+
+impl LaunchHooksDialog {
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        config: &mut AppConfig,
+        games: &[Game],
+        selected_game: Option<&Game>,
+    ) {
+        egui::Window::new("Launch Hooks")
+            .default_size([620.0, 520.0])
+            .open(open)
+            .show(ctx, |ui| {
+                ui.label(
+                    "Commands run through the system shell before MAME starts and after it \
+                     exits, e.g. to switch display modes or set button LEDs. One command per \
+                     line; lines starting with # are ignored.",
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "Variables: {rom} {description} {mame} {orientation} {category} \
+                         (inserted quoted)",
+                    )
+                    .monospace()
+                    .small(),
+                );
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Timeout:");
+                    ui.add(
+                        egui::DragValue::new(&mut config.launch_hooks.timeout_secs)
+                            .range(1..=600)
+                            .suffix(" s"),
+                    )
+                    .on_hover_text("Commands still running after this are killed");
+                    ui.checkbox(
+                        &mut config.launch_hooks.abort_on_failure,
+                        "Don't launch when a pre-launch command fails",
+                    );
+                });
+                ui.add_space(6.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Scope:");
+                    ui.selectable_value(&mut self.scope, HookScope::Global, "All games");
+                    ui.selectable_value(&mut self.scope, HookScope::Category, "Category");
+                    ui.selectable_value(&mut self.scope, HookScope::Game, "Selected game");
+                });
+
+                let hooks = &mut config.launch_hooks;
+                let commands: Option<&mut HookCommands> = match self.scope {
+                    HookScope::Global => Some(&mut hooks.global),
+                    HookScope::Category => {
+                        if self.category.is_empty()
+                            && let Some(game) = selected_game
+                        {
+                            self.category = game.category.clone();
+                        }
+                        let categories: BTreeSet<&str> = games
+                            .iter()
+                            .map(|game| game.category.as_str())
+                            .filter(|category| !category.is_empty())
+                            .collect();
+                        ui.horizontal(|ui| {
+                            ui.label("Category:");
+                            egui::ComboBox::from_id_salt("launch_hooks_category")
+                                .selected_text(&self.category)
+                                .width(260.0)
+                                .show_ui(ui, |ui| {
+                                    for category in categories {
+                                        let label = if hooks.categories.contains_key(category) {
+                                            format!("{} •", category)
+                                        } else {
+                                            category.to_string()
+                                        };
+                                        ui.selectable_value(
+                                            &mut self.category,
+                                            category.to_string(),
+                                            label,
+                                        );
+                                    }
+                                });
+                        });
+                        if self.category.is_empty() {
+                            ui.label("No categories loaded; set catver.ini in Directories.");
+                            None
+                        } else {
+                            Some(hooks.categories.entry(self.category.clone()).or_default())
+                        }
+                    }
+                    HookScope::Game => match selected_game {
+                        Some(game) => {
+                            ui.label(format!("{} ({})", game.description, game.name));
+                            Some(hooks.games.entry(game.name.clone()).or_default())
+                        }
+                        None => {
+                            ui.label("Select a game in the list first.");
+                            None
+                        }
+                    },
+                };
+
+                if let Some(commands) = commands {
+                    for (stage, hint) in [
+                        (
+                            HookStage::BeforeLaunch,
+                            "e.g. xrandr --output HDMI-1 --rotate left",
+                        ),
+                        (
+                            HookStage::AfterExit,
+                            "e.g. xrandr --output HDMI-1 --rotate normal",
+                        ),
+                    ] {
+                        ui.add_space(6.0);
+                        ui.strong(match stage {
+                            HookStage::BeforeLaunch => "Before launch",
+                            HookStage::AfterExit => "After exit",
+                        });
+                        ui.add(
+                            egui::TextEdit::multiline(commands.text_mut(stage))
+                                .code_editor()
+                                .desired_rows(4)
+                                .desired_width(f32::INFINITY)
+                                .hint_text(hint),
+                        );
+                    }
+                }
+
+                ui.add_space(8.0);
+                ui.separator();
+                ui.label(format!(
+                    "Categories with hooks: {}",
+                    configured(&hooks.categories)
+                ));
+                ui.label(format!("Games with hooks: {}", configured(&hooks.games)));
+                ui.label(
+                    egui::RichText::new(
+                        "Pre-launch commands run from all games to the category to the game; \
+                         after exit the order is reversed.",
+                    )
+                    .weak(),
+                );
+            });
+    }
+}
+
+/// Names in a scope that have commands, for the summary
+fn configured(scope: &BTreeMap<String, HookCommands>) -> String {
+    let names: Vec<&str> = scope
+        .iter()
+        .filter(|(_, commands)| !commands.is_empty())
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    }
+}
@@ -7,9 +7,12 @@ pub mod directories;
 pub mod directories_paths; // New modern UI implementation
 pub mod game_properties;
 pub mod hidden_categories;
+pub mod launch_hooks;
 pub mod mame_finder;
 pub mod preferences;
+pub mod rom_cleanup;
 pub mod rom_info;
+pub mod rom_rebuild;
 pub mod rom_verify;
 pub mod steam_ui;
 
@@ -1,5 +1,5 @@
 // src/ui/dialogs/preferences.rs
-use crate::models::{Preferences, Theme, UiShellMode};
+use crate::models::{InstancePolicy, Preferences, Theme, UiShellMode};
 use crate::ui::components::steam_ui::SteamUi;
 use eframe::egui;
 
@@ -151,6 +151,13 @@ impl PreferencesDialog {
             );
             ui.checkbox(&mut prefs.auto_save, "Auto-save configuration changes");
             ui.checkbox(&mut prefs.confirm_exit, "Confirm before exiting");
+
+            ui.horizontal(|ui| {
+                ui.label("Running games:");
+                for policy in [InstancePolicy::Single, InstancePolicy::Multiple] {
+                    ui.radio_value(&mut prefs.instance_policy, policy, policy.display_name());
+                }
+            });
         });
 
         ui.add_space(SteamUi::SECTION_GAP);
@@ -0,0 +1,296 @@
+use crate::models::{AppConfig, Game};
+use crate::utils::rom_utils::cleanup::{
+    CleanupItem, CleanupKind, CleanupReport, QuarantineManifest, list_manifests, quarantine,
+    restore, spawn_cleanup_scan,
+};
+use crate::utils::rom_utils::configured_rom_dirs;
+use eframe::egui;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Tools → Clean Up ROM Folders: lists files no machine needs and moves the
+/// chosen ones into quarantine
+#[derive(Default)]
+pub struct RomCleanupDialog {
+    receiver: Option<mpsc::Receiver<CleanupReport>>,
+    report: Option<CleanupReport>,
+    selected: HashSet<usize>,
+    batches: Option<Vec<(PathBuf, QuarantineManifest)>>,
+    status: Option<String>,
+    error: Option<String>,
+}
+
+impl RomCleanupDialog {
+    pub fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Returns true when the quarantine folder was changed and the config
+    /// needs saving
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        config: &mut AppConfig,
+        games: &[Game],
+    ) -> bool {
+        let mut config_changed = false;
+        self.process_messages();
+        if self.is_running() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+        if self.batches.is_none() {
+            self.batches = Some(
+                config
+                    .quarantine_path
+                    .as_deref()
+                    .map(list_manifests)
+                    .unwrap_or_default(),
+            );
+        }
+
+        egui::Window::new("🧹 Clean Up ROM Folders")
+            .default_size([720.0, 520.0])
+            .open(open)
+            .show(ctx, |ui| {
+                ui.label("Finds files no machine needs, unused files inside sets, and extra copies of a set.");
+                ui.label("Chosen files are moved to the quarantine folder and can be restored; nothing is deleted.");
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label("Quarantine folder:");
+                    let label = config
+                        .quarantine_path
+                        .as_ref()
+                        .map(|dir| dir.display().to_string())
+                        .unwrap_or_else(|| "(not set)".to_string());
+                    ui.monospace(label);
+                    if ui.button("Browse…").clicked()
+                        && let Some(dir) = rfd::FileDialog::new()
+                            .set_title("Select quarantine folder")
+                            .pick_folder()
+                    {
+                        config.quarantine_path = Some(dir);
+                        self.batches = None;
+                        config_changed = true;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    let can_scan = !self.is_running() && !config.rom_paths.is_empty();
+                    if ui
+                        .add_enabled(can_scan, egui::Button::new("🔍 Scan ROM folders"))
+                        .clicked()
+                    {
+                        self.start(config, games);
+                    }
+                    if self.is_running() {
+                        ui.spinner();
+                        ui.label("Scanning…");
+                    }
+                });
+
+                if let Some(status) = &self.status {
+                    ui.label(status);
+                }
+                if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
+                self.show_batches(ui);
+                ui.separator();
+                self.show_report(ui, config);
+            });
+
+        config_changed
+    }
+
+    fn show_batches(&mut self, ui: &mut egui::Ui) {
+        let Some(batches) = &self.batches else {
+            return;
+        };
+        if batches.is_empty() {
+            return;
+        }
+
+        let mut undo = None;
+        ui.separator();
+        ui.label(egui::RichText::new("Quarantined batches").strong());
+        for (path, manifest) in batches {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "{} — {} files",
+                    manifest.created,
+                    manifest.moves.len()
+                ));
+                if ui.button("↩ Undo").clicked() {
+                    undo = Some(path.clone());
+                }
+            });
+        }
+
+        if let Some(path) = undo {
+            match restore(&path) {
+                Ok(summary) => {
+                    self.status = Some(format!(
+                        "Restored {} files, {} left in quarantine",
+                        summary.restored,
+                        summary.skipped.len()
+                    ));
+                    self.error = (!summary.skipped.is_empty()).then(|| summary.skipped.join("\n"));
+                }
+                Err(e) => self.error = Some(format!("Undo failed: {:#}", e)),
+            }
+            self.batches = None;
+        }
+    }
+
+    fn show_report(&mut self, ui: &mut egui::Ui, config: &AppConfig) {
+        let Some(report) = &self.report else {
+            return;
+        };
+
+        let count = |kind| report.items.iter().filter(|item| item.kind == kind).count();
+        ui.label(format!(
+            "{} files checked: {} orphans, {} unneeded, {} duplicates",
+            report.files_checked,
+            count(CleanupKind::Orphan),
+            count(CleanupKind::Unneeded),
+            count(CleanupKind::Duplicate)
+        ));
+        for error in &report.errors {
+            ui.colored_label(egui::Color32::YELLOW, error);
+        }
+        if report.items.iter().any(|item| !item.movable()) {
+            ui.label("Unneeded files inside archives can't be moved on their own; rebuilding the set drops them.");
+        }
+
+        let mut move_selected = false;
+        ui.horizontal(|ui| {
+            if ui.button("Select all").clicked() {
+                self.selected = report
+                    .items
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, item)| item.movable())
+                    .map(|(index, _)| index)
+                    .collect();
+            }
+            if ui.button("Select none").clicked() {
+                self.selected.clear();
+            }
+            let can_move = !self.selected.is_empty() && config.quarantine_path.is_some();
+            move_selected = ui
+                .add_enabled(
+                    can_move,
+                    egui::Button::new(format!("📦 Move {} to quarantine", self.selected.len())),
+                )
+                .clicked();
+        });
+
+        let row_height = ui.spacing().interact_size.y;
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show_rows(ui, row_height, report.items.len(), |ui, range| {
+                for index in range {
+                    let item = &report.items[index];
+                    ui.horizontal(|ui| {
+                        let mut checked = self.selected.contains(&index);
+                        if ui
+                            .add_enabled(item.movable(), egui::Checkbox::without_text(&mut checked))
+                            .changed()
+                        {
+                            if checked {
+                                self.selected.insert(index);
+                            } else {
+                                self.selected.remove(&index);
+                            }
+                        }
+                        ui.label(item.kind.label());
+                        ui.monospace(item.display_path())
+                            .on_hover_text(&item.detail);
+                    });
+                }
+            });
+
+        if move_selected {
+            self.move_selected(config);
+        }
+    }
+
+    fn start(&mut self, config: &AppConfig, games: &[Game]) {
+        self.report = None;
+        self.selected.clear();
+        self.status = None;
+        self.error = None;
+        self.receiver = Some(spawn_cleanup_scan(
+            configured_rom_dirs(config),
+            config.software_rom_paths.clone(),
+            games.to_vec(),
+        ));
+    }
+
+    fn move_selected(&mut self, config: &AppConfig) {
+        let (Some(report), Some(quarantine_dir)) = (&mut self.report, &config.quarantine_path)
+        else {
+            return;
+        };
+
+        let chosen: Vec<&CleanupItem> = report
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.selected.contains(index))
+            .map(|(_, item)| item)
+            .collect();
+        let rom_dirs = configured_rom_dirs(config);
+        let moved: HashSet<PathBuf> = match quarantine(&chosen, &rom_dirs, quarantine_dir) {
+            Ok((_, manifest)) => {
+                self.status = Some(format!(
+                    "Moved {} files to quarantine",
+                    manifest.moves.len()
+                ));
+                self.error = None;
+                manifest
+                    .moves
+                    .into_iter()
+                    .map(|entry| entry.original)
+                    .collect()
+            }
+            Err(e) => {
+                self.error = Some(format!("Quarantine stopped: {:#}", e));
+                // Whatever moved before the error is gone from its old place
+                chosen
+                    .iter()
+                    .filter(|item| !item.path.exists())
+                    .map(|item| item.path.clone())
+                    .collect()
+            }
+        };
+
+        // Entries inside a moved archive went with it
+        report.items.retain(|item| !moved.contains(&item.path));
+        self.selected.clear();
+        self.batches = None;
+    }
+
+    fn process_messages(&mut self) {
+        let Some(rx) = &self.receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok(report) => {
+                self.report = Some(report);
+                self.receiver = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.error = Some("Cleanup scan stopped unexpectedly".to_string());
+                self.receiver = None;
+            }
+        }
+    }
+}
@@ -59,7 +59,7 @@ impl RomInfoDialog {
 
                 ui.horizontal(|ui| {
                     ui.label("Sample check:");
-                    ui.label("None required");
+                    ui.label(game.sample_status.description());
                 });
 
                 ui.group(|ui| {
@@ -0,0 +1,204 @@
+use crate::models::{AppConfig, Game, RomSetType};
+use crate::utils::rom_utils::configured_rom_dirs;
+use crate::utils::rom_utils::rebuilder::{
+    RebuildMessage, RebuildOptions, RebuildSummary, spawn_rebuild,
+};
+use eframe::egui;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Tools → Rebuild ROM Sets: converts the collection into another layout
+pub struct RomRebuildDialog {
+    target: RomSetType,
+    output_dir: Option<PathBuf>,
+    dry_run: bool,
+    receiver: Option<mpsc::Receiver<RebuildMessage>>,
+    progress: (usize, usize),
+    current_set: String,
+    planned: Vec<String>,
+    summary: Option<RebuildSummary>,
+    error: Option<String>,
+}
+
+impl Default for RomRebuildDialog {
+    fn default() -> Self {
+        Self {
+            target: RomSetType::NonMerged,
+            output_dir: None,
+            dry_run: true,
+            receiver: None,
+            progress: (0, 0),
+            current_set: String::new(),
+            planned: Vec::new(),
+            summary: None,
+            error: None,
+        }
+    }
+}
+
+impl RomRebuildDialog {
+    pub fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        config: &AppConfig,
+        games: &[Game],
+    ) {
+        self.process_messages();
+        if self.is_running() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        egui::Window::new("🧱 Rebuild ROM Sets")
+            .default_size([640.0, 480.0])
+            .open(open)
+            .show(ctx, |ui| {
+                ui.label("Writes the sets found in your ROM paths into a new folder using the chosen layout.");
+                ui.label("Source files are only read, never moved or changed.");
+                ui.separator();
+
+                ui.add_enabled_ui(!self.is_running(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Target layout:");
+                        ui.radio_value(&mut self.target, RomSetType::NonMerged, "Non-merged");
+                        ui.radio_value(&mut self.target, RomSetType::Split, "Split");
+                        ui.radio_value(&mut self.target, RomSetType::Merged, "Merged");
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Output folder:");
+                        let label = self
+                            .output_dir
+                            .as_ref()
+                            .map(|dir| dir.display().to_string())
+                            .unwrap_or_else(|| "(not set)".to_string());
+                        ui.monospace(label);
+                        if ui.button("Browse…").clicked()
+                            && let Some(dir) = rfd::FileDialog::new()
+                                .set_title("Select output folder for rebuilt sets")
+                                .pick_folder()
+                        {
+                            self.output_dir = Some(dir);
+                        }
+                    });
+
+                    ui.checkbox(&mut self.dry_run, "Dry run (only list planned file moves)");
+
+                    let can_start = self.output_dir.is_some() && !config.rom_paths.is_empty();
+                    if ui
+                        .add_enabled(can_start, egui::Button::new("▶ Start"))
+                        .clicked()
+                    {
+                        self.start(config, games);
+                    }
+                });
+
+                ui.separator();
+
+                if self.is_running() {
+                    let (done, total) = self.progress;
+                    let fraction = if total > 0 {
+                        done as f32 / total as f32
+                    } else {
+                        0.0
+                    };
+                    ui.add(egui::ProgressBar::new(fraction).text(format!("{} / {}", done, total)));
+                    ui.label(format!("Writing {}", self.current_set));
+                }
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::RED, format!("Rebuild failed: {}", error));
+                }
+
+                if let Some(summary) = &self.summary {
+                    if summary.dry_run {
+                        ui.label(format!(
+                            "{} planned file moves, {} files missing from your ROM paths",
+                            self.planned.len(),
+                            summary.missing_files
+                        ));
+                    } else {
+                        ui.label(format!(
+                            "Wrote {} sets ({} files, {} CHDs); {} files were missing",
+                            summary.sets_written,
+                            summary.files_written,
+                            summary.disks_copied,
+                            summary.missing_files
+                        ));
+                    }
+                    for error in &summary.errors {
+                        ui.colored_label(egui::Color32::YELLOW, error);
+                    }
+                }
+
+                if !self.planned.is_empty() {
+                    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                    egui::ScrollArea::vertical().auto_shrink([false, false]).show_rows(
+                        ui,
+                        row_height,
+                        self.planned.len(),
+                        |ui, range| {
+                            for line in &self.planned[range] {
+                                ui.monospace(line);
+                            }
+                        },
+                    );
+                }
+            });
+    }
+
+    fn start(&mut self, config: &AppConfig, games: &[Game]) {
+        let Some(output_dir) = self.output_dir.clone() else {
+            return;
+        };
+
+        self.planned.clear();
+        self.summary = None;
+        self.error = None;
+        self.progress = (0, 0);
+        self.receiver = Some(spawn_rebuild(
+            configured_rom_dirs(config),
+            games.to_vec(),
+            RebuildOptions {
+                target: self.target,
+                output_dir,
+                dry_run: self.dry_run,
+            },
+        ));
+    }
+
+    fn process_messages(&mut self) {
+        let Some(rx) = &self.receiver else {
+            return;
+        };
+
+        let mut finished = false;
+        while let Ok(msg) = rx.try_recv() {
+            match msg {
+                RebuildMessage::Started(total) => self.progress = (0, total),
+                RebuildMessage::Progress(done, total, set) => {
+                    self.progress = (done, total);
+                    self.current_set = set;
+                }
+                RebuildMessage::Planned(lines) => self.planned = lines,
+                RebuildMessage::Complete(summary) => {
+                    self.summary = Some(summary);
+                    finished = true;
+                }
+                RebuildMessage::Failed(error) => {
+                    self.error = Some(error);
+                    finished = true;
+                }
+            }
+        }
+
+        if finished {
+            self.receiver = None;
+        }
+    }
+}
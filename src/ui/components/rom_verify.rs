@@ -1,12 +1,20 @@
+use crate::mame::{DatFile, DatLoader, VerifyCommand, VerifyPool};
 use crate::models::{AppConfig, Game, RomStatus, VerificationStatus};
 use crate::ui::redesign::fonts;
+use crate::utils::rom_utils::RomAuditor;
+use crate::utils::rom_utils::audit_export::{
+    self, AuditExport, AuditStatus, DiffKind, DiffRow, FileAuditStatus,
+};
+use crate::utils::rom_utils::fixdat::{self, FixdatScope};
+use crate::utils::rom_utils::verify_history::{self, AuditRun, ChangeKind, VerificationHistory};
 use eframe::egui;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::process::Command;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 
 /// Global verification manager to track verification status across the application
 pub struct VerificationManager {
@@ -38,22 +46,19 @@ impl VerificationManager {
 
     /// Get verification status for a game
     pub fn get_verification_status(&self, game_name: &str) -> VerificationStatus {
-        if let Some(result) = self.get_result(game_name) {
-            match result.status {
-                VerifyStatus::Passed => VerificationStatus::Verified,
-                VerifyStatus::Failed => VerificationStatus::Failed,
-                VerifyStatus::Warning => VerificationStatus::Warning,
-                VerifyStatus::NotFound => VerificationStatus::NotFound,
-            }
-        } else {
-            VerificationStatus::NotVerified
-        }
+        self.get_result(game_name)
+            .map_or(VerificationStatus::NotVerified, |result| {
+                result.status.verification_status()
+            })
     }
 
-    /// Update game verification status
+    /// Update game verification status. Games without a result in this run
+    /// keep the status they had, which may come from the ROM scan cache.
     pub fn update_game_status(&self, game: &mut Game) {
-        let status = self.get_verification_status(&game.name);
-        game.update_verification_status(status);
+        if self.get_result(&game.name).is_some() {
+            let status = self.get_verification_status(&game.name);
+            game.update_verification_status(status);
+        }
     }
 
     /// Clear all verification results
@@ -135,11 +140,26 @@ pub struct RomVerifyDialog {
     stats: VerificationStats,
     // Export options
     export_format: ExportFormat,
+    // Fixdat export waiting for the app to resolve its scope
+    fixdat_request: Option<FixdatScope>,
+    // What the current results were checked with, for the JSON export
+    run_mame_version: String,
+    run_reference: String,
+    // Two imported JSON exports side by side
+    comparison: Option<AuditComparison>,
+    comparison_error: Option<String>,
+    // DAT audited against instead of the MAME data, when loaded
+    reference_dat: Option<DatFile>,
+    dat_receiver: Option<mpsc::Receiver<Result<DatFile, String>>>,
+    dat_error: Option<String>,
     // Verification manager
     verification_manager: Arc<VerificationManager>,
+    // Results of earlier sessions, loaded on first use
+    history: Option<VerificationHistory>,
+    show_history: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VerificationResult {
     pub game_name: String,
     pub description: String,
@@ -148,9 +168,44 @@ pub struct VerificationResult {
     pub incorrect_files: Vec<String>,
     pub extra_files: Vec<String>,
     pub chd_status: Option<String>,
+    /// Every ROM and disk of the set's own manifest
+    #[serde(default)]
+    pub files: Vec<FileCheck>,
+}
+
+/// How one ROM or disk of a set compared against the files on disk
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileCheck {
+    pub name: String,
+    /// CRC from the manifest; disks have none
+    pub crc: Option<String>,
+    pub status: FileCheckStatus,
+    /// What was expected and found, for files that don't match
+    pub expected: Option<String>,
+    pub actual: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileCheckStatus {
+    Good,
+    Incorrect,
+    Missing,
+    NoDump, // No known dump, nothing to check against
+}
+
+impl FileCheckStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            FileCheckStatus::Good => "good",
+            FileCheckStatus::Incorrect => "incorrect",
+            FileCheckStatus::Missing => "missing",
+            FileCheckStatus::NoDump => "no dump",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum VerifyStatus {
     Passed,
     Failed,
@@ -158,6 +213,27 @@ pub enum VerifyStatus {
     NotFound,
 }
 
+impl VerifyStatus {
+    /// The status shown for the game in the library
+    pub fn verification_status(&self) -> VerificationStatus {
+        match self {
+            VerifyStatus::Passed => VerificationStatus::Verified,
+            VerifyStatus::Failed => VerificationStatus::Failed,
+            VerifyStatus::Warning => VerificationStatus::Warning,
+            VerifyStatus::NotFound => VerificationStatus::NotFound,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VerifyStatus::Passed => "Passed",
+            VerifyStatus::Failed => "Failed",
+            VerifyStatus::Warning => "Warning",
+            VerifyStatus::NotFound => "Not found",
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct VerificationStats {
     pub total_verified: usize,
@@ -175,6 +251,15 @@ pub enum ExportFormat {
     Text,
     CSV,
     HTML,
+    Json,
+}
+
+/// Differences between two imported audit exports
+struct AuditComparison {
+    left: String,
+    right: String,
+    rows: Vec<DiffRow>,
+    kind_filter: Option<DiffKind>,
 }
 
 enum VerifyMessage {
@@ -210,8 +295,18 @@ impl Default for RomVerifyDialog {
             stats: VerificationStats::default(),
             // Export format
             export_format: ExportFormat::default(),
+            fixdat_request: None,
+            run_mame_version: String::new(),
+            run_reference: String::new(),
+            comparison: None,
+            comparison_error: None,
+            reference_dat: None,
+            dat_receiver: None,
+            dat_error: None,
             // Verification manager
             verification_manager: Arc::new(VerificationManager::new()),
+            history: None,
+            show_history: false,
         }
     }
 }
@@ -232,8 +327,50 @@ impl RomVerifyDialog {
         self.verification_manager.clone()
     }
 
+    /// Stored results and past runs
+    pub fn history(&mut self) -> &mut VerificationHistory {
+        self.history.get_or_insert_with(VerificationHistory::load)
+    }
+
+    /// Store the results of the run that just ended and return what changed.
+    /// `set_fingerprints` identifies the files each set was verified from.
+    pub fn record_history_run(
+        &mut self,
+        config: &AppConfig,
+        set_fingerprints: &HashMap<String, u32>,
+    ) -> Option<AuditRun> {
+        let mame_version = config
+            .mame_executables
+            .get(config.selected_mame_index)
+            .map(|mame| mame.version.clone())
+            .unwrap_or_default();
+        let fingerprint = verify_history::rom_fingerprint(config);
+        let results = std::mem::take(&mut self.verification_results);
+        let reference = self.run_reference.clone();
+
+        let history = self.history();
+        let run = history
+            .record_run(
+                &results,
+                &mame_version,
+                &reference,
+                &fingerprint,
+                set_fingerprints,
+            )
+            .cloned();
+        if run.is_some()
+            && let Err(e) = history.save()
+        {
+            eprintln!("Failed to save verification history: {:#}", e);
+        }
+        self.verification_results = results;
+        run
+    }
+
     // Modified show method for standalone window
     pub fn show_window(&mut self, ctx: &egui::Context, config: &AppConfig, games: &[Game]) {
+        self.show_comparison_window(ctx);
+
         // Check if we should show warning first
         if self.show_warning && !self.is_verifying && games.len() > 100 {
             let mut should_close = false;
@@ -350,6 +487,11 @@ impl RomVerifyDialog {
 
         ui.separator();
 
+        self.process_dat_load();
+        self.reference_row(ui);
+
+        ui.separator();
+
         // Enhanced stats display
         self.show_stats_panel(ui);
 
@@ -361,7 +503,7 @@ impl RomVerifyDialog {
             ui.horizontal_wrapped(|ui| {
                 if !self.is_verifying {
                     if ui.button("🔍 Verify All ROMs").clicked() {
-                        self.start_verification(config, games, None);
+                        self.start_verification(config, games, games, None);
                     }
 
                     if ui.button("Verify Available Only").clicked() {
@@ -370,7 +512,16 @@ impl RomVerifyDialog {
                             .filter(|g| matches!(g.status, RomStatus::Available))
                             .cloned()
                             .collect();
-                        self.start_verification(config, &available_games, None);
+                        self.start_verification(config, &available_games, games, None);
+                    }
+
+                    let history_label = if self.show_history {
+                        "📋 Current Results"
+                    } else {
+                        "🕘 History"
+                    };
+                    if ui.button(history_label).clicked() {
+                        self.show_history = !self.show_history;
                     }
 
                     if ui.button("Clear Results").clicked() {
@@ -406,10 +557,17 @@ impl RomVerifyDialog {
                     ui.radio_value(&mut self.export_format, ExportFormat::Text, "Text");
                     ui.radio_value(&mut self.export_format, ExportFormat::CSV, "CSV");
                     ui.radio_value(&mut self.export_format, ExportFormat::HTML, "HTML");
+                    ui.radio_value(&mut self.export_format, ExportFormat::Json, "JSON");
 
                     if ui.button("📄 Export Report").clicked() {
                         self.export_results();
                     }
+
+                    self.fixdat_menu(ui);
+                }
+
+                if ui.button("⚖ Compare Exports").clicked() {
+                    self.compare_exports();
                 }
             });
         });
@@ -437,7 +595,94 @@ impl RomVerifyDialog {
         ui.separator();
 
         // Results display with color coding
-        self.show_results_panel(ui);
+        if self.show_history {
+            self.show_history_panel(ui);
+        } else {
+            self.show_results_panel(ui);
+        }
+    }
+
+    fn show_history_panel(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🕘 Verification History");
+
+        let filter = self.filter_text.to_lowercase();
+        let runs = self.history().runs();
+        if runs.is_empty() {
+            ui.label("No verification run has been recorded yet.");
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (index, run) in runs.iter().enumerate().rev() {
+                    let counts: Vec<String> = ChangeKind::ALL
+                        .iter()
+                        .map(|&kind| (kind, run.count(kind)))
+                        .filter(|(_, count)| *count > 0)
+                        .map(|(kind, count)| format!("{} {}", count, kind.label().to_lowercase()))
+                        .collect();
+                    let summary = if counts.is_empty() {
+                        "no changes".to_string()
+                    } else {
+                        counts.join(", ")
+                    };
+                    let reference = if run.reference.is_empty() {
+                        format!("MAME {}", run.mame_version)
+                    } else {
+                        run.reference.clone()
+                    };
+                    let title = format!(
+                        "{} — {} — {} checked: {}",
+                        run.finished_at, reference, run.checked, summary
+                    );
+
+                    egui::CollapsingHeader::new(title)
+                        .id_salt(("verification_run", index))
+                        .default_open(index + 1 == runs.len())
+                        .show(ui, |ui| {
+                            for kind in ChangeKind::ALL {
+                                let changes: Vec<_> = run
+                                    .changes
+                                    .iter()
+                                    .filter(|change| change.kind() == kind)
+                                    .filter(|change| {
+                                        filter.is_empty()
+                                            || change.game_name.to_lowercase().contains(&filter)
+                                            || change.description.to_lowercase().contains(&filter)
+                                    })
+                                    .collect();
+                                if changes.is_empty() {
+                                    continue;
+                                }
+
+                                let color = match kind {
+                                    ChangeKind::NewlyBroken => egui::Color32::RED,
+                                    ChangeKind::NewlyFixed => egui::Color32::GREEN,
+                                    ChangeKind::NewlyAdded => egui::Color32::LIGHT_BLUE,
+                                    ChangeKind::Changed => egui::Color32::YELLOW,
+                                };
+                                ui.colored_label(
+                                    color,
+                                    format!("{} ({})", kind.label(), changes.len()),
+                                );
+                                for change in changes {
+                                    let before = change
+                                        .before
+                                        .as_ref()
+                                        .map_or("—", |status| status.label());
+                                    ui.label(format!(
+                                        "  • {} - {}: {} → {}",
+                                        change.game_name,
+                                        change.description,
+                                        before,
+                                        change.after.label()
+                                    ));
+                                }
+                            }
+                        });
+                }
+            });
     }
 
     fn show_stats_panel(&self, ui: &mut egui::Ui) {
@@ -713,51 +958,78 @@ impl RomVerifyDialog {
         &mut self,
         config: &AppConfig,
         games: &[Game],
+        all_games: &[Game],
         specific_game: Option<&str>,
     ) {
-        if let Some(mame) = config.mame_executables.get(config.selected_mame_index) {
-            self.is_verifying = true;
-            self.should_stop = false;
-            self.is_paused = false;
-            self.verification_results.clear();
-            // A previous worker may still finish an in-flight MAME process after
-            // Restart. Give the new run its own result store so stale writes stay
-            // isolated from the current statistics.
-            self.verification_manager = Arc::new(VerificationManager::new());
-            self.stats = VerificationStats::default();
-            self.current_progress = 0.0;
-            self.total_games = if specific_game.is_some() {
-                1
-            } else {
-                games.len()
+        // A loaded DAT replaces the MAME data for every run, and only the
+        // native auditor can check against it
+        let dat_games = self.reference_dat.as_ref().map(|dat| dat.games.clone());
+        let (games, all_games) = match &dat_games {
+            Some(dat_games) => (&dat_games[..], &dat_games[..]),
+            None => (games, all_games),
+        };
+        let use_native = dat_games.is_some() || !config.use_mame_audit;
+        let command = VerifyCommand::from_config(config);
+        if command.is_none() && !use_native {
+            return;
+        }
+
+        self.run_mame_version = config
+            .mame_executables
+            .get(config.selected_mame_index)
+            .map(|mame| mame.version.clone())
+            .unwrap_or_default();
+        self.run_reference = match &self.reference_dat {
+            Some(dat) => dat.label(),
+            None => verify_history::mame_reference(&self.run_mame_version),
+        };
+
+        self.is_verifying = true;
+        self.should_stop = false;
+        self.is_paused = false;
+        self.verification_results.clear();
+        // A previous worker may still finish an in-flight MAME process after
+        // Restart. Give the new run its own result store so stale writes stay
+        // isolated from the current statistics.
+        self.verification_manager = Arc::new(VerificationManager::new());
+        self.stats = VerificationStats::default();
+        self.current_progress = 0.0;
+        self.total_games = match specific_game {
+            Some(name) => games.iter().filter(|game| game.name == name).count(),
+            None => games.len(),
+        };
+        self.verified_games = 0;
+        self.start_time = Some(std::time::Instant::now());
+
+        let (tx, rx) = mpsc::channel();
+        let (pause_tx, pause_rx) = mpsc::channel();
+        let (stop_tx, stop_rx) = mpsc::channel();
+
+        self.receiver = Some(rx);
+        self.pause_sender = Some(pause_tx);
+        self.stop_sender = Some(stop_tx);
+
+        let mut games_to_verify = games.to_vec();
+        if let Some(game_name) = specific_game {
+            games_to_verify.retain(|game| game.name == game_name);
+        }
+        let verification_manager = self.verification_manager.clone();
+        let workers = config.verify_workers;
+        // The native auditor needs every manifest so parents, BIOS and
+        // device sets resolve even when only a subset is being verified.
+        let auditor = use_native.then(|| RomAuditor::from_config(config, all_games));
+
+        thread::spawn(move || {
+            let total = games_to_verify.len().max(1);
+            let report = |verified: usize, result: VerificationResult| {
+                let progress = verified as f32 / total as f32;
+                let _ = tx.send(VerifyMessage::Progress(progress, result.game_name.clone()));
+                verification_manager.update_result(result.game_name.clone(), result.clone());
+                let _ = tx.send(VerifyMessage::Result(result));
             };
-            self.verified_games = 0;
-            self.start_time = Some(std::time::Instant::now());
-
-            let (tx, rx) = mpsc::channel();
-            let (pause_tx, pause_rx) = mpsc::channel();
-            let (stop_tx, stop_rx) = mpsc::channel();
-
-            self.receiver = Some(rx);
-            self.pause_sender = Some(pause_tx);
-            self.stop_sender = Some(stop_tx);
-
-            let mame_path = mame.path.clone();
-            let games_to_verify = games.to_vec();
-            let specific_game = specific_game.map(|s| s.to_string());
-            let verification_manager = self.verification_manager.clone();
-
-            thread::spawn(move || {
-                if let Some(game_name) = specific_game {
-                    // Verify single game
-                    if let Some(game) = games_to_verify.iter().find(|g| g.name == game_name) {
-                        let result = Self::verify_single_game(&mame_path, game);
-                        verification_manager.update_result(game_name.clone(), result.clone());
-                        let _ = tx.send(VerifyMessage::Result(result));
-                        let _ = tx.send(VerifyMessage::Progress(1.0, game_name));
-                    }
-                } else {
-                    // Verify all games
+
+            match (auditor, command) {
+                (Some(mut auditor), _) => {
                     for (idx, game) in games_to_verify.iter().enumerate() {
                         // Check for stop signal
                         if let Ok(true) = stop_rx.try_recv() {
@@ -782,113 +1054,37 @@ impl RomVerifyDialog {
                             break;
                         }
 
-                        let progress = (idx + 1) as f32 / games_to_verify.len() as f32;
-                        let _ = tx.send(VerifyMessage::Progress(progress, game.name.clone()));
-
-                        let result = Self::verify_single_game(&mame_path, game);
-                        verification_manager.update_result(game.name.clone(), result.clone());
-                        let _ = tx.send(VerifyMessage::Result(result));
+                        report(idx + 1, auditor.audit(game));
                     }
                 }
-
-                let _ = tx.send(VerifyMessage::Complete);
-            });
-        }
-    }
-
-    fn verify_single_game(mame_path: &str, game: &Game) -> VerificationResult {
-        let output = Command::new(mame_path)
-            .arg("-verifyroms")
-            .arg(&game.name)
-            .output();
-
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                let combined_output = format!("{}\n{}", stdout, stderr);
-
-                Self::parse_verification_output(&game.name, &game.description, &combined_output)
-            }
-            Err(e) => VerificationResult {
-                game_name: game.name.clone(),
-                description: game.description.clone(),
-                status: VerifyStatus::Failed,
-                missing_files: vec![format!("Error running verification: {}", e)],
-                incorrect_files: vec![],
-                extra_files: vec![],
-                chd_status: None,
-            },
-        }
-    }
-
-    fn parse_verification_output(
-        game_name: &str,
-        description: &str,
-        output: &str,
-    ) -> VerificationResult {
-        let mut missing_files = Vec::new();
-        let mut incorrect_files = Vec::new();
-        let mut extra_files = Vec::new();
-        let mut chd_status = None;
-        let mut status = VerifyStatus::Passed;
-
-        for line in output.lines() {
-            let line = line.trim();
-
-            if line.contains("NOT FOUND") {
-                if line.contains(".chd") {
-                    chd_status = Some("CHD file not found".to_string());
-                    status = VerifyStatus::Failed;
-                } else if let Some(file) = Self::extract_filename(line) {
-                    missing_files.push(file);
-                    status = VerifyStatus::Failed;
-                }
-            } else if line.contains("INCORRECT") || line.contains("BAD") {
-                if let Some(file) = Self::extract_filename(line) {
-                    incorrect_files.push(file);
-                    status = VerifyStatus::Failed;
-                }
-            } else if line.contains("NO GOOD DUMP") {
-                status = VerifyStatus::Warning;
-            } else if line.contains("found") && line.contains("extra") {
-                if let Some(file) = Self::extract_filename(line) {
-                    extra_files.push(file);
-                    if status == VerifyStatus::Passed {
-                        status = VerifyStatus::Warning;
+                (None, Some(command)) => {
+                    // MAME checks a batch of sets per call, on several
+                    // processes at once; results arrive as batches finish
+                    let pool = VerifyPool::start(command, games_to_verify, workers);
+                    let mut verified = 0;
+                    loop {
+                        while let Ok(paused) = pause_rx.try_recv() {
+                            pool.set_paused(paused);
+                        }
+                        if let Ok(true) = stop_rx.try_recv() {
+                            pool.stop();
+                            break;
+                        }
+                        match pool.recv_timeout(Duration::from_millis(100)) {
+                            Ok(result) => {
+                                verified += 1;
+                                report(verified, result);
+                            }
+                            Err(mpsc::RecvTimeoutError::Timeout) => {}
+                            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                        }
                     }
                 }
-            } else if line.contains("is good") || line.contains("OK") {
-                // ROM is verified OK
+                (None, None) => {}
             }
-        }
-
-        VerificationResult {
-            game_name: game_name.to_string(),
-            description: description.to_string(),
-            status,
-            missing_files,
-            incorrect_files,
-            extra_files,
-            chd_status,
-        }
-    }
-
-    fn extract_filename(line: &str) -> Option<String> {
-        // Try to extract filename from various MAME output formats
-        if let Some(start) = line.find('"')
-            && let Some(end) = line[start + 1..].find('"')
-        {
-            return Some(line[start + 1..start + 1 + end].to_string());
-        }
 
-        // Alternative format
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() > 1 {
-            Some(parts[0].to_string())
-        } else {
-            None
-        }
+            let _ = tx.send(VerifyMessage::Complete);
+        });
     }
 
     fn export_results(&self) {
@@ -896,6 +1092,7 @@ impl RomVerifyDialog {
             ExportFormat::Text => "txt",
             ExportFormat::CSV => "csv",
             ExportFormat::HTML => "html",
+            ExportFormat::Json => "json",
         };
 
         let file_name = format!("rom_verification_report.{}", file_extension);
@@ -909,6 +1106,17 @@ impl RomVerifyDialog {
                 ExportFormat::Text => self.generate_text_report(),
                 ExportFormat::CSV => self.generate_csv_report(),
                 ExportFormat::HTML => self.generate_html_report(),
+                ExportFormat::Json => {
+                    let export = AuditExport::from_results(
+                        &self.verification_results,
+                        &self.run_mame_version,
+                        &self.run_reference,
+                    );
+                    if let Err(e) = export.save(&path) {
+                        eprintln!("Failed to save report: {:#}", e);
+                    }
+                    return;
+                }
             };
 
             if let Err(e) = fs::write(&path, content) {
@@ -917,6 +1125,311 @@ impl RomVerifyDialog {
         }
     }
 
+    pub fn reference_dat(&self) -> Option<&DatFile> {
+        self.reference_dat.as_ref()
+    }
+
+    pub fn is_loading_dat(&self) -> bool {
+        self.dat_receiver.is_some()
+    }
+
+    /// Ask for a Logiqx or ClrMamePro DAT and load it in the background
+    pub fn pick_reference_dat(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Select a DAT to verify against")
+            .add_filter("DAT files", &["dat", "xml"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel();
+        self.dat_receiver = Some(rx);
+        self.dat_error = None;
+        thread::spawn(move || {
+            let _ = tx.send(DatLoader::load(&path).map_err(|e| format!("{:#}", e)));
+        });
+    }
+
+    /// Go back to auditing against the running MAME's own data
+    pub fn clear_reference_dat(&mut self) {
+        self.reference_dat = None;
+        self.dat_error = None;
+    }
+
+    fn process_dat_load(&mut self) {
+        let Some(rx) = &self.dat_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(dat)) => {
+                self.reference_dat = Some(dat);
+                self.dat_receiver = None;
+            }
+            Ok(Err(error)) => {
+                self.dat_error = Some(error);
+                self.dat_receiver = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.dat_receiver = None,
+        }
+    }
+
+    /// Reference picker shared by the legacy window and the redesign page
+    fn reference_row(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Reference:");
+            if self.is_loading_dat() {
+                ui.spinner();
+                ui.label("Loading DAT…");
+            } else if let Some(dat) = &self.reference_dat {
+                ui.label(dat.label())
+                    .on_hover_text(dat.path.display().to_string());
+                if ui
+                    .add_enabled(!self.is_verifying, egui::Button::new("Use MAME data"))
+                    .clicked()
+                {
+                    self.clear_reference_dat();
+                }
+            } else {
+                ui.label("MAME -listxml");
+            }
+            if ui
+                .add_enabled(
+                    !self.is_verifying && !self.is_loading_dat(),
+                    egui::Button::new("📂 Load DAT…"),
+                )
+                .on_hover_text("Verify against a Logiqx XML or ClrMamePro DAT instead")
+                .clicked()
+            {
+                self.pick_reference_dat();
+            }
+        });
+        if let Some(error) = &self.dat_error {
+            ui.colored_label(egui::Color32::RED, format!("Could not load DAT: {}", error));
+        }
+    }
+
+    fn fixdat_menu(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("🧾 Export Fixdat", |ui| {
+            for scope in [
+                FixdatScope::All,
+                FixdatScope::CurrentFilter,
+                FixdatScope::Favorites,
+            ] {
+                if ui.button(scope.label()).clicked() {
+                    self.fixdat_request = Some(scope);
+                    ui.close();
+                }
+            }
+        });
+    }
+
+    /// Ask for two JSON audit exports and show how they differ
+    pub fn compare_exports(&mut self) {
+        let pick = |title: &str| {
+            rfd::FileDialog::new()
+                .set_title(title)
+                .add_filter("Audit export", &["json"])
+                .pick_file()
+        };
+        let Some(left_path) = pick("Select the first audit export") else {
+            return;
+        };
+        let Some(right_path) = pick("Select the audit export to compare with") else {
+            return;
+        };
+
+        match (
+            AuditExport::load(&left_path),
+            AuditExport::load(&right_path),
+        ) {
+            (Ok(left), Ok(right)) => {
+                self.comparison = Some(AuditComparison {
+                    left: left.label(),
+                    right: right.label(),
+                    rows: audit_export::diff_exports(&left, &right),
+                    kind_filter: None,
+                });
+                self.comparison_error = None;
+            }
+            (Err(e), _) | (_, Err(e)) => self.comparison_error = Some(format!("{:#}", e)),
+        }
+    }
+
+    fn show_comparison_window(&mut self, ctx: &egui::Context) {
+        use egui_extras::{Column, TableBuilder};
+
+        if let Some(error) = self.comparison_error.clone() {
+            let mut open = true;
+            egui::Window::new("⚖ Audit Comparison")
+                .open(&mut open)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.colored_label(egui::Color32::RED, error);
+                });
+            if !open {
+                self.comparison_error = None;
+            }
+        }
+
+        let Some(comparison) = &mut self.comparison else {
+            return;
+        };
+        let mut open = true;
+        let kind_label = |kind: DiffKind, comparison: &AuditComparison| match kind {
+            DiffKind::LeftOnly => format!("Only playable on {}", comparison.left),
+            DiffKind::RightOnly => format!("Only playable on {}", comparison.right),
+            DiffKind::Different => "Different status or files".to_string(),
+        };
+
+        egui::Window::new("⚖ Audit Comparison")
+            .open(&mut open)
+            .default_size([760.0, 480.0])
+            .resizable(true)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Show:");
+                    let selected = comparison
+                        .kind_filter
+                        .map_or("All differences".to_string(), |kind| {
+                            kind_label(kind, comparison)
+                        });
+                    egui::ComboBox::from_id_salt("audit_comparison_filter")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut comparison.kind_filter,
+                                None,
+                                "All differences",
+                            );
+                            for kind in DiffKind::ALL {
+                                let label = kind_label(kind, comparison);
+                                ui.selectable_value(&mut comparison.kind_filter, Some(kind), label);
+                            }
+                        });
+                });
+                let counts: Vec<String> = DiffKind::ALL
+                    .iter()
+                    .map(|&kind| {
+                        let count = comparison.rows.iter().filter(|r| r.kind() == kind).count();
+                        format!("{}: {}", kind_label(kind, comparison), count)
+                    })
+                    .collect();
+                ui.label(counts.join(" · "));
+                ui.separator();
+
+                let rows: Vec<&DiffRow> = comparison
+                    .rows
+                    .iter()
+                    .filter(|row| comparison.kind_filter.is_none_or(|kind| row.kind() == kind))
+                    .collect();
+                if rows.is_empty() {
+                    ui.label("Both exports agree.");
+                    return;
+                }
+
+                let status_text = |status: Option<AuditStatus>| {
+                    let (text, color) = match status {
+                        None => ("—", egui::Color32::GRAY),
+                        Some(AuditStatus::Passed) => ("Passed", egui::Color32::GREEN),
+                        Some(AuditStatus::Warning) => ("Warning", egui::Color32::YELLOW),
+                        Some(status) => (status.label(), egui::Color32::RED),
+                    };
+                    egui::RichText::new(text).color(color)
+                };
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .column(Column::initial(220.0).resizable(true))
+                    .column(Column::initial(130.0).resizable(true))
+                    .column(Column::initial(130.0).resizable(true))
+                    .column(Column::remainder())
+                    .header(20.0, |mut header| {
+                        header.col(|ui| {
+                            ui.strong("Set");
+                        });
+                        header.col(|ui| {
+                            ui.strong(&comparison.left);
+                        });
+                        header.col(|ui| {
+                            ui.strong(&comparison.right);
+                        });
+                        header.col(|ui| {
+                            ui.strong("Files");
+                        });
+                    })
+                    .body(|body| {
+                        body.rows(20.0, rows.len(), |mut table_row| {
+                            let row = rows[table_row.index()];
+                            table_row.col(|ui| {
+                                ui.label(&row.name).on_hover_text(&row.description);
+                            });
+                            table_row.col(|ui| {
+                                ui.label(status_text(row.left));
+                            });
+                            table_row.col(|ui| {
+                                ui.label(status_text(row.right));
+                            });
+                            table_row.col(|ui| {
+                                let files: Vec<String> = row
+                                    .files
+                                    .iter()
+                                    .map(|file| {
+                                        let side = |status: Option<FileAuditStatus>| {
+                                            status.map_or("—", FileAuditStatus::label)
+                                        };
+                                        format!(
+                                            "{}: {} ↔ {}",
+                                            file.name,
+                                            side(file.left),
+                                            side(file.right)
+                                        )
+                                    })
+                                    .collect();
+                                let summary = files.join(", ");
+                                ui.add(egui::Label::new(&summary).truncate())
+                                    .on_hover_text(files.join("\n"));
+                            });
+                        });
+                    });
+            });
+        if !open {
+            self.comparison = None;
+        }
+    }
+
+    /// Scope picked from the fixdat menu, if any. The app resolves the
+    /// scope to game names because only it knows the current filter.
+    pub fn take_fixdat_request(&mut self) -> Option<FixdatScope> {
+        self.fixdat_request.take()
+    }
+
+    /// Save a Logiqx fixdat of the missing and incorrect files, limited to
+    /// `include` when given
+    pub fn export_fixdat(&self, games: &[Game], include: Option<&HashSet<String>>) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_file_name("fixdat.dat")
+            .add_filter("Logiqx DAT", &["dat", "xml"])
+            .save_file()
+        {
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("fixdat")
+                .to_string();
+            let games = self
+                .reference_dat
+                .as_ref()
+                .map_or(games, |dat| dat.games.as_slice());
+            let content =
+                fixdat::generate_fixdat(&name, &self.verification_results, games, include);
+            if let Err(e) = fs::write(&path, content) {
+                eprintln!("Failed to save fixdat: {}", e);
+            }
+        }
+    }
+
     fn generate_text_report(&self) -> String {
         let mut content = String::new();
         content.push_str("ROM Verification Report - CLRMamePro Lite\n");
@@ -1099,7 +1612,7 @@ impl RomVerifyDialog {
 
     pub fn start_verification_all(&mut self, config: &AppConfig, games: &[Game]) {
         self.show_warning = false;
-        self.start_verification(config, games, None);
+        self.start_verification(config, games, games, None);
     }
 
     pub fn stop_verification(&mut self) {
@@ -1125,6 +1638,9 @@ impl RomVerifyDialog {
 
     pub fn show_redesign_panel(&mut self, ui: &mut egui::Ui) {
         self.process_pending_messages();
+        self.show_comparison_window(ui.ctx());
+        self.reference_row(ui);
+        ui.add_space(8.0);
         use crate::ui::redesign::tokens::RedesignTokens;
 
         let status_text = if self.is_verifying {
@@ -1237,11 +1753,27 @@ impl RomVerifyDialog {
                     self.export_format = ExportFormat::HTML;
                     self.export_results();
                 }
+                if ui.button("Export JSON").clicked() {
+                    self.export_format = ExportFormat::Json;
+                    self.export_results();
+                }
+                self.fixdat_menu(ui);
+                if ui.button("Compare…").clicked() {
+                    self.compare_exports();
+                }
             });
         } else {
             ui.horizontal(|ui| {
                 ui.checkbox(&mut self.show_only_issues, "Show only issues");
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("Compare…").clicked() {
+                        self.compare_exports();
+                    }
+                    self.fixdat_menu(ui);
+                    if ui.button("Export JSON").clicked() {
+                        self.export_format = ExportFormat::Json;
+                        self.export_results();
+                    }
                     if ui.button("Export HTML").clicked() {
                         self.export_format = ExportFormat::HTML;
                         self.export_results();
@@ -1448,6 +1980,7 @@ impl RomVerifyDialog {
     }
 
     fn process_pending_messages(&mut self) {
+        self.process_dat_load();
         if let Some(rx) = &self.receiver {
             let mut messages = Vec::new();
             while let Ok(msg) = rx.try_recv() {
@@ -10,6 +10,9 @@ pub enum DockTab {
     SoftwareLists,
     Artwork,
     History,
+    LaunchLog,
+    Sessions,
+    Recordings,
 }
 
 impl DockTab {
@@ -20,18 +23,26 @@ impl DockTab {
             DockTab::SoftwareLists => "Software Lists",
             DockTab::Artwork => "Artwork",
             DockTab::History => "History",
+            DockTab::LaunchLog => "MAME Log",
+            DockTab::Sessions => "Sessions",
+            DockTab::Recordings => "Recordings",
         }
     }
 }
 
 pub fn create_default_layout() -> DockState<DockTab> {
-    let mut dock_state = DockState::new(vec![DockTab::GameList, DockTab::SoftwareLists]);
+    let mut dock_state = DockState::new(vec![
+        DockTab::GameList,
+        DockTab::SoftwareLists,
+        DockTab::LaunchLog,
+        DockTab::Sessions,
+    ]);
     let surface = dock_state.main_surface_mut();
     surface.split_left(NodeIndex::root(), 0.22, vec![DockTab::Sidebar]);
     surface.split_right(
         NodeIndex::root(),
         0.72,
-        vec![DockTab::Artwork, DockTab::History],
+        vec![DockTab::Artwork, DockTab::History, DockTab::Recordings],
     );
     dock_state
 }
@@ -55,6 +66,9 @@ impl TabViewer for MameTabViewer<'_> {
             DockTab::SoftwareLists => self.app.render_software_list_panel(ui),
             DockTab::Artwork => self.app.render_artwork_panel(ui),
             DockTab::History => self.app.render_history_panel(ui),
+            DockTab::LaunchLog => self.app.render_launch_log_panel(ui),
+            DockTab::Sessions => self.app.render_session_panel(ui),
+            DockTab::Recordings => self.app.render_recordings_panel(ui),
         }
     }
 }
@@ -1,8 +1,7 @@
 // src/ui/artwork_loader.rs
+use crate::utils::archive;
 use eframe::egui;
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::Read;
 use std::path::Path;
 
 pub struct ArtworkLoader {
@@ -78,12 +77,11 @@ impl ArtworkLoader {
         let search_path = dir.to_path_buf();
 
         // Try to find and load the artwork file
-        // First try ZIP file
-        let zip_path = search_path.join(format!("{}.zip", game_name));
-        if zip_path.exists()
-            && let Some(image_data) = self.extract_from_zip(&zip_path, game_name)
-        {
-            return self.create_texture(ctx, &image_data, game_name);
+        // First try a per-game archive (<game>.zip or <game>.7z)
+        for archive_path in archive::find_archives(&search_path, game_name) {
+            if let Some(image_data) = self.extract_from_archive(&archive_path, game_name) {
+                return self.create_texture(ctx, &image_data, game_name);
+            }
         }
 
         // Try common image formats
@@ -143,44 +141,25 @@ impl ArtworkLoader {
         None
     }
 
-    /// Extract image from ZIP file
-    fn extract_from_zip(&self, zip_path: &Path, game_name: &str) -> Option<Vec<u8>> {
-        let file = File::open(zip_path).ok()?;
-        let mut archive = zip::ZipArchive::new(file).ok()?;
-
-        let archive_len = archive.len();
-
-        // Look for image files in the ZIP
-        for i in 0..archive_len {
-            if let Ok(mut file) = archive.by_index(i) {
-                let file_name = file.name().to_lowercase();
-
-                // Check if this is an image file
-                if file_name.ends_with(".png")
-                    || file_name.ends_with(".jpg")
-                    || file_name.ends_with(".jpeg")
-                    || file_name.ends_with(".bmp")
-                    || file_name.ends_with(".gif")
-                {
-                    // Some ZIPs might have the game name in the filename
-                    if file_name.contains(game_name) ||
-                       file_name == format!("{}.png", game_name) ||
-                       file_name == format!("{}.jpg", game_name) ||
-                       file_name == "0000.png" || // Some ZIPs use 0000.png as the main image
-                       archive_len == 1
-                    {
-                        // If only one file, use it
-
-                        let mut buffer = Vec::new();
-                        if file.read_to_end(&mut buffer).is_ok() {
-                            return Some(buffer);
-                        }
-                    }
-                }
-            }
-        }
-
-        None
+    /// Extract image from a zip or 7z archive
+    fn extract_from_archive(&self, archive_path: &Path, game_name: &str) -> Option<Vec<u8>> {
+        let entries = archive::list_entries(archive_path).ok()?;
+        let single_file = entries.len() == 1;
+
+        archive::read_entry(archive_path, |entry| {
+            let file_name = entry.name.to_lowercase();
+
+            // Check if this is an image file
+            let is_image = [".png", ".jpg", ".jpeg", ".bmp", ".gif"]
+                .iter()
+                .any(|ext| file_name.ends_with(ext));
+
+            // Some archives have the game name in the filename, others use
+            // 0000.png as the main image. If there is only one file, use it.
+            is_image && (file_name.contains(game_name) || file_name == "0000.png" || single_file)
+        })
+        .ok()
+        .flatten()
     }
 
     /// Create egui texture from image data
@@ -217,6 +196,13 @@ impl ArtworkLoader {
         self.texture_cache.remove(&cache_key);
         self.miss_cache.remove(&cache_key);
     }
+
+    /// Drop every artwork type of a game, e.g. after its files changed on disk
+    pub fn forget_game(&mut self, game_name: &str) {
+        for artwork_type in ArtworkType::ALL {
+            self.remove_from_cache(game_name, artwork_type);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -228,3 +214,14 @@ pub enum ArtworkType {
     Flyer,
     Pcb,
 }
+
+impl ArtworkType {
+    pub const ALL: [ArtworkType; 6] = [
+        ArtworkType::Screenshot,
+        ArtworkType::Cabinet,
+        ArtworkType::Marquee,
+        ArtworkType::Title,
+        ArtworkType::Flyer,
+        ArtworkType::Pcb,
+    ];
+}
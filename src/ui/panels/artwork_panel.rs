@@ -18,6 +18,11 @@ impl ArtworkPanel {
         }
     }
 
+    /// Forget cached artwork of a game whose files changed on disk
+    pub fn forget_game(&mut self, game_name: &str) {
+        self.artwork_loader.forget_game(game_name);
+    }
+
     /// Display the artwork panel
     /// This is now a method (takes &mut self) so it can be called on an instance
     /// The selected_game parameter is an Option<usize> representing the index
@@ -159,7 +159,7 @@ impl GameIndexManager {
         // Prefix keeps these final-result entries separate from the legacy list
         // widgets, which still cache plain text queries in the same GameIndex.
         format!(
-            "__manager_v2__|q={:?}|mode={:?}|availability={}:{}|status={}:{}|other={}:{}:{}|mfr={:?}|year={:?}:{:?}|hardware={:?}:{:?}:{:?}",
+            "__manager_v2__|q={:?}|mode={:?}|availability={}:{}|status={}:{}|other={}:{}:{}:{}|mfr={:?}|year={:?}:{:?}|hardware={:?}:{:?}:{:?}",
             filters.search_text,
             filters.search_mode,
             filters.availability_filters.show_available,
@@ -169,6 +169,7 @@ impl GameIndexManager {
             filters.other_filters.show_favorites,
             filters.other_filters.show_parents_only,
             filters.other_filters.show_chd_games,
+            filters.other_filters.show_samples_missing,
             manufacturers,
             filters.year_from,
             filters.year_to,
@@ -223,13 +224,17 @@ impl GameIndexManager {
                 let others_match = {
                     let others = &filters.other_filters;
                     // If no filters selected, show all
-                    if !others.show_favorites && !others.show_parents_only && !others.show_chd_games
+                    if !others.show_favorites
+                        && !others.show_parents_only
+                        && !others.show_chd_games
+                        && !others.show_samples_missing
                     {
                         true
                     } else {
                         (others.show_favorites && favorites.contains(&game.name))
                             || (others.show_parents_only && !game.is_clone)
                             || (others.show_chd_games && game.requires_chd)
+                            || (others.show_samples_missing && game.sample_status.is_missing())
                     }
                 };
 
@@ -508,6 +513,16 @@ impl GameIndexManager {
         self.filter_cache_dirty = true;
     }
 
+    /// Refresh the index entries of games changed in place, without a rebuild
+    pub fn update_games(&mut self, games: &[Game], changed: &[usize]) {
+        if let Some(index) = &mut self.game_index {
+            for &idx in changed {
+                index.update_game(idx, games[idx].clone());
+            }
+        }
+        self.filter_cache_dirty = true;
+    }
+
     /// Configure enhanced search settings
     pub fn configure_enhanced_search(&mut self, config: SearchConfig) {
         if let Some(ref mut search_engine) = self.enhanced_search {
@@ -619,6 +634,8 @@ mod tests {
             requires_chd: false,
             chd_name: None,
             verification_status: None,
+            sample_status: Default::default(),
+            rom_set: Default::default(),
         }
     }
 
@@ -888,13 +888,12 @@ impl GameList {
                 };
 
                 ui.painter().rect_filled(cell_rect, 0.0, bg_color);
-                let play_count = game_stats
-                    .get(&game.name)
-                    .map(|stats| stats.play_count)
-                    .unwrap_or(0);
+                let stats = game_stats.get(&game.name);
+                let play_count = stats.map(|stats| stats.play_count).unwrap_or(0);
+                let last_crashed = stats.is_some_and(|stats| stats.last_crashed);
 
                 if play_count > 0 {
-                    // Badge style for play count
+                    // Badge style for play count, red when the last session crashed
                     let text = play_count.to_string();
                     let galley = ui.painter().layout_no_wrap(
                         text.clone(),
@@ -907,18 +906,26 @@ impl GameList {
                         galley.size() + egui::vec2(16.0, 4.0),
                     );
 
-                    ui.painter().rect_filled(
-                        rect,
-                        12.0,
-                        egui::Color32::from_rgba_premultiplied(100, 149, 255, 30),
-                    );
+                    let badge = if last_crashed {
+                        egui::Color32::from_rgba_premultiplied(255, 90, 90, 40)
+                    } else {
+                        egui::Color32::from_rgba_premultiplied(100, 149, 255, 30)
+                    };
+                    ui.painter().rect_filled(rect, 12.0, badge);
 
                     ui.painter().galley(
                         rect.center() - galley.size() / 2.0,
                         galley,
                         egui::Color32::WHITE,
                     );
-                    ui.allocate_rect(rect, egui::Sense::hover());
+                    let response = ui.allocate_rect(rect, egui::Sense::hover());
+                    if let Some(stats) = stats.filter(|stats| stats.crash_count > 0) {
+                        response.on_hover_text(format!(
+                            "Crashed {} time(s); last session: {}",
+                            stats.crash_count,
+                            stats.last_exit.as_deref().unwrap_or("unknown")
+                        ));
+                    }
                 } else {
                     ui.label("-");
                 }
@@ -1469,12 +1476,14 @@ impl GameList {
                         if !others.show_favorites
                             && !others.show_parents_only
                             && !others.show_chd_games
+                            && !others.show_samples_missing
                         {
                             true
                         } else {
                             (others.show_favorites && favorites.contains(&game.name))
                                 || (others.show_parents_only && !game.is_clone)
                                 || (others.show_chd_games && game.requires_chd)
+                                || (others.show_samples_missing && game.sample_status.is_missing())
                         }
                     };
 
@@ -1575,14 +1584,17 @@ impl GameList {
                     }
                 }
                 RomSetType::Merged => {
-                    // For merged sets, show only parent games (clones are merged into parent)
-                    filtered_indices.retain(|&idx| {
-                        if let Some(game) = games.get(idx) {
-                            !game.is_clone
-                        } else {
-                            false
-                        }
-                    });
+                    // For merged sets, clones live inside the parent archive;
+                    // show them only when the user asked for it
+                    if !filters.show_clones_in_merged {
+                        filtered_indices.retain(|&idx| {
+                            if let Some(game) = games.get(idx) {
+                                !game.is_clone
+                            } else {
+                                false
+                            }
+                        });
+                    }
                 }
                 RomSetType::Unknown => {
                     // If type is unknown, try to detect based on clone ratio
@@ -1117,12 +1117,14 @@ impl GameListView {
                         if !others.show_favorites
                             && !others.show_parents_only
                             && !others.show_chd_games
+                            && !others.show_samples_missing
                         {
                             true
                         } else {
                             (others.show_favorites && favorites.contains(&game.name))
                                 || (others.show_parents_only && !game.is_clone)
                                 || (others.show_chd_games && game.requires_chd)
+                                || (others.show_samples_missing && game.sample_status.is_missing())
                         }
                     };
 
@@ -1223,14 +1225,17 @@ impl GameListView {
                     }
                 }
                 RomSetType::Merged => {
-                    // For merged sets, show only parent games (clones are merged into parent)
-                    filtered_indices.retain(|&idx| {
-                        if let Some(game) = games.get(idx) {
-                            !game.is_clone
-                        } else {
-                            false
-                        }
-                    });
+                    // For merged sets, clones live inside the parent archive;
+                    // show them only when the user asked for it
+                    if !filters.show_clones_in_merged {
+                        filtered_indices.retain(|&idx| {
+                            if let Some(game) = games.get(idx) {
+                                !game.is_clone
+                            } else {
+                                false
+                            }
+                        });
+                    }
                 }
                 RomSetType::Unknown => {
                     // If type is unknown, try to detect based on clone ratio
@@ -1405,6 +1410,7 @@ impl GameListView {
         filters.other_filters.show_favorites.hash(&mut hasher);
         filters.other_filters.show_parents_only.hash(&mut hasher);
         filters.other_filters.show_chd_games.hash(&mut hasher);
+        filters.other_filters.show_samples_missing.hash(&mut hasher);
 
         // Hash catver category filter - CRITICAL for cache invalidation
         if let Some(ref catver_category) = filters.catver_category {
@@ -1425,6 +1431,7 @@ impl GameListView {
         // Hash ROM set type and related settings
         filters.rom_set_type.hash(&mut hasher);
         filters.show_clones_in_split.hash(&mut hasher);
+        filters.show_clones_in_merged.hash(&mut hasher);
         filters.auto_expand_clones.hash(&mut hasher);
 
         filters.cpu_filter.hash(&mut hasher);
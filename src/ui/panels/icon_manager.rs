@@ -4,13 +4,18 @@
 
 use super::icon_performance_monitor::IconPerformanceMonitor;
 use crate::models::{AppConfig, IconInfo};
+use crate::utils::archive::{self, IndexedArchive};
 use eframe::egui;
 use rayon::prelude::*;
 use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex, mpsc};
+use std::sync::{Arc, Mutex, OnceLock, mpsc};
 use std::time::{Duration, Instant};
 
+/// icons.zip / icons.7z of an icons folder, opened by the first loader
+/// thread that needs them and shared by the rest
+type IconArchives = Arc<OnceLock<Vec<IndexedArchive>>>;
+
 // Icon loading result from background thread
 #[derive(Debug)]
 pub struct IconLoadResult {
@@ -31,6 +36,7 @@ pub struct IconManager {
     pub icon_load_queue: Arc<Mutex<VecDeque<String>>>,
     pub icon_info: HashMap<String, IconInfo>,
     pub last_icon_cleanup: Instant,
+    icon_archives: Option<(PathBuf, IconArchives)>,
 
     // Thread pool results
     pub icon_results_rx: Option<mpsc::Receiver<IconLoadResult>>,
@@ -59,6 +65,7 @@ impl IconManager {
             icon_load_queue: Arc::new(Mutex::new(VecDeque::new())),
             icon_info: HashMap::new(),
             last_icon_cleanup: Instant::now(),
+            icon_archives: None,
             icon_results_rx: Some(rx),
             icon_results_tx: Some(tx),
             max_cached_icons: config.max_cached_icons,
@@ -100,50 +107,60 @@ impl IconManager {
     fn load_icon_from_file_threaded(
         rom_name: String,
         icons_path: PathBuf,
+        archives: &IconArchives,
         icon_size: u32,
     ) -> IconLoadResult {
         let start_time = Instant::now();
-        // Try to load .ico file
-        let ico_path = icons_path.join(format!("{}.ico", rom_name));
-
-        if ico_path.exists() {
-            // Read the ico file
-            if let Ok(ico_data) = std::fs::read(&ico_path) {
-                // Try to load as ICO format
-                if let Ok(image) =
-                    image::load_from_memory_with_format(&ico_data, image::ImageFormat::Ico)
-                {
-                    // Convert to RGBA8
-                    let rgba_image = image.to_rgba8();
-
-                    // Resize to configured icon size if needed
-                    let resized =
-                        if rgba_image.width() != icon_size || rgba_image.height() != icon_size {
-                            image::imageops::resize(
-                                &rgba_image,
-                                icon_size,
-                                icon_size,
-                                image::imageops::FilterType::Lanczos3,
-                            )
-                        } else {
-                            rgba_image
-                        };
-
-                    let width = resized.width();
-                    let height = resized.height();
-                    let pixels = resized.into_raw();
-
-                    let load_time = start_time.elapsed();
-                    return IconLoadResult {
-                        rom_name,
-                        icon_data: Some(pixels),
-                        width,
-                        height,
-                        load_time: Some(load_time),
-                        success: true,
-                    };
-                }
-            }
+        // Try to load a loose .ico file, then fall back to icons.zip / icons.7z
+        let ico_name = format!("{}.ico", rom_name);
+        let ico_path = icons_path.join(&ico_name);
+        let ico_data = if ico_path.exists() {
+            std::fs::read(&ico_path).ok()
+        } else {
+            archives
+                .get_or_init(|| {
+                    archive::find_archives(&icons_path, "icons")
+                        .iter()
+                        .filter_map(|path| IndexedArchive::open(path).ok())
+                        .collect()
+                })
+                .iter()
+                .find_map(|archive| archive.read(&ico_name))
+        };
+
+        // Try to load as ICO format
+        if let Some(ico_data) = ico_data
+            && let Ok(image) =
+                image::load_from_memory_with_format(&ico_data, image::ImageFormat::Ico)
+        {
+            // Convert to RGBA8
+            let rgba_image = image.to_rgba8();
+
+            // Resize to configured icon size if needed
+            let resized = if rgba_image.width() != icon_size || rgba_image.height() != icon_size {
+                image::imageops::resize(
+                    &rgba_image,
+                    icon_size,
+                    icon_size,
+                    image::imageops::FilterType::Lanczos3,
+                )
+            } else {
+                rgba_image
+            };
+
+            let width = resized.width();
+            let height = resized.height();
+            let pixels = resized.into_raw();
+
+            let load_time = start_time.elapsed();
+            return IconLoadResult {
+                rom_name,
+                icon_data: Some(pixels),
+                width,
+                height,
+                load_time: Some(load_time),
+                success: true,
+            };
         }
 
         // Return empty result if loading failed
@@ -247,10 +264,23 @@ impl IconManager {
             return;
         };
 
+        let archives = match &self.icon_archives {
+            Some((path, archives)) if *path == icons_path => archives.clone(),
+            _ => {
+                let archives = IconArchives::default();
+                self.icon_archives = Some((icons_path.clone(), archives.clone()));
+                archives
+            }
+        };
+
         let load_icons = || {
             icons_to_load.into_par_iter().for_each(|rom_name| {
-                let result =
-                    Self::load_icon_from_file_threaded(rom_name, icons_path.clone(), icon_size);
+                let result = Self::load_icon_from_file_threaded(
+                    rom_name,
+                    icons_path.clone(),
+                    &archives,
+                    icon_size,
+                );
                 let _ = tx.send(result); // Ignore send errors
             });
         };
@@ -366,6 +396,7 @@ impl IconManager {
     pub fn clear_cache(&mut self) {
         self.rom_icons.clear();
         self.icon_info.clear();
+        self.icon_archives = None;
         if let Ok(mut queue) = self.icon_load_queue.lock() {
             queue.clear();
         }
@@ -0,0 +1,219 @@
+// src/ui/panels/launch_log_panel.rs
+use crate::mame::{LaunchLogger, LaunchRecord, OutputStream};
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+
+/// Shows what MAME printed for each launch of this session, with the line
+/// a failure was read from highlighted
+pub struct LaunchLogPanel {
+    /// Launch being shown; the newest one when unset
+    selected: Option<u64>,
+    search: String,
+    stderr_only: bool,
+}
+
+impl LaunchLogPanel {
+    pub fn new() -> Self {
+        Self {
+            selected: None,
+            search: String::new(),
+            stderr_only: false,
+        }
+    }
+
+    /// Show a launch, e.g. the one a failure notification was about
+    pub fn select(&mut self, launch_id: u64) {
+        self.selected = Some(launch_id);
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, logger: &LaunchLogger) {
+        let launches = logger.launches();
+        if launches.is_empty() {
+            ui.add_space(20.0);
+            ui.vertical_centered(|ui| {
+                ui.label("Nothing launched yet.");
+                ui.label(
+                    egui::RichText::new("MAME's output for each game you run appears here.").weak(),
+                );
+            });
+            return;
+        }
+
+        let record = self
+            .selected
+            .and_then(|id| launches.iter().find(|record| record.id == id))
+            .unwrap_or(&launches[launches.len() - 1]);
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("launch_log_select")
+                .width(260.0)
+                .selected_text(launch_label(record))
+                .show_ui(ui, |ui| {
+                    for launch in launches.iter().rev() {
+                        if ui
+                            .selectable_label(launch.id == record.id, launch_label(launch))
+                            .clicked()
+                        {
+                            self.selected = Some(launch.id);
+                        }
+                    }
+                });
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search)
+                    .hint_text("Filter lines")
+                    .desired_width(160.0),
+            );
+            ui.checkbox(&mut self.stderr_only, "stderr only");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(&record.command_line).monospace().weak())
+                .on_hover_text("Command line");
+            if ui
+                .small_button("📋")
+                .on_hover_text("Copy command line")
+                .clicked()
+            {
+                ui.ctx().copy_text(record.command_line.clone());
+            }
+        });
+        if let Some(path) = logger.path() {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(format!("Log file: {}", path.display())).small());
+                if ui
+                    .small_button("📋")
+                    .on_hover_text("Copy log path")
+                    .clicked()
+                {
+                    ui.ctx().copy_text(path.display().to_string());
+                }
+            });
+        }
+
+        if let Some(failure) = &record.failure {
+            let error = ui.visuals().error_fg_color;
+            egui::Frame::group(ui.style())
+                .stroke(egui::Stroke::new(1.0, error))
+                .show(ui, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(
+                            egui::RichText::new(failure.kind.label())
+                                .strong()
+                                .color(error),
+                        );
+                        if let Some(option) = &failure.option {
+                            ui.label("option");
+                            ui.label(
+                                egui::RichText::new(option)
+                                    .monospace()
+                                    .strong()
+                                    .background_color(error.gamma_multiply(0.25)),
+                            );
+                        }
+                    });
+                    if !failure.detail.is_empty() {
+                        ui.label(&failure.detail);
+                    }
+                });
+        }
+        ui.separator();
+
+        let search = self.search.to_lowercase();
+        let visible: Vec<usize> = record
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !self.stderr_only || line.stream == OutputStream::Stderr)
+            .filter(|(_, line)| search.is_empty() || line.text.to_lowercase().contains(&search))
+            .map(|(index, _)| index)
+            .collect();
+
+        if visible.is_empty() {
+            ui.label(
+                egui::RichText::new(if record.is_running() {
+                    "No output yet."
+                } else {
+                    "No matching output."
+                })
+                .weak(),
+            );
+            return;
+        }
+
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::both()
+            .auto_shrink([false, false])
+            .stick_to_bottom(true)
+            .show_rows(ui, row_height, visible.len(), |ui, rows| {
+                for &index in &visible[rows] {
+                    ui.label(line_job(ui, record, index));
+                }
+            });
+    }
+}
+
+impl Default for LaunchLogPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn launch_label(record: &LaunchRecord) -> String {
+    format!(
+        "#{} {} · {} · {}",
+        record.id,
+        record.game,
+        record.started,
+        record.status()
+    )
+}
+
+/// One output line; the failure line is marked and the blamed option in it
+/// picked out
+fn line_job(ui: &egui::Ui, record: &LaunchRecord, index: usize) -> LayoutJob {
+    let line = &record.lines[index];
+    let visuals = ui.visuals();
+    let font = egui::TextStyle::Monospace.resolve(ui.style());
+    let color = match line.stream {
+        OutputStream::Stdout => visuals.text_color(),
+        OutputStream::Stderr => visuals.warn_fg_color,
+    };
+    let plain = TextFormat::simple(font, color);
+
+    let failure = record
+        .failure
+        .as_ref()
+        .filter(|failure| failure.line == Some(index));
+    let mut job = LayoutJob::default();
+    let Some(failure) = failure else {
+        job.append(&line.text, 0.0, plain);
+        return job;
+    };
+
+    let marked = TextFormat {
+        color: visuals.error_fg_color,
+        background: visuals.error_fg_color.gamma_multiply(0.12),
+        ..plain
+    };
+    let option_at = failure
+        .option
+        .as_ref()
+        .and_then(|option| Some((line.text.find(option.as_str())?, option.len())));
+    match option_at {
+        Some((start, len)) => {
+            job.append(&line.text[..start], 0.0, marked.clone());
+            job.append(
+                &line.text[start..start + len],
+                0.0,
+                TextFormat {
+                    background: visuals.error_fg_color.gamma_multiply(0.35),
+                    underline: egui::Stroke::new(1.0, visuals.error_fg_color),
+                    ..marked.clone()
+                },
+            );
+            job.append(&line.text[start + len..], 0.0, marked);
+        }
+        None => job.append(&line.text, 0.0, marked),
+    }
+    job
+}
@@ -9,7 +9,10 @@ pub mod game_list_view;
 pub mod history_panel;
 pub mod icon_manager;
 pub mod icon_performance_monitor;
+pub mod launch_log_panel;
 pub mod performance_manager;
+pub mod recordings_panel;
+pub mod session_panel;
 pub mod sidebar;
 pub mod software_list_panel;
 
@@ -19,6 +22,9 @@ pub use game_list::GameList;
 pub use game_list_view::GameListView;
 pub use history_panel::HistoryPanel;
 pub use icon_manager::IconManager;
+pub use launch_log_panel::LaunchLogPanel;
 pub use performance_manager::PerformanceManager;
+pub use recordings_panel::{RecordingRequest, RecordingsPanel};
+pub use session_panel::SessionPanel;
 pub use sidebar::Sidebar;
 pub use software_list_panel::SoftwareListPanel;
@@ -0,0 +1,277 @@
+// src/ui/panels/recordings_panel.rs
+use crate::mame::{
+    Recording, delete_recording, list_recordings, new_recording_name, rename_recording,
+};
+use crate::models::{AppConfig, Game};
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use std::path::PathBuf;
+
+/// A launch the panel asks the app for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordingRequest {
+    Record {
+        game: String,
+        file_name: String,
+    },
+    Playback {
+        game: String,
+        file_name: String,
+        exit_after: bool,
+    },
+}
+
+/// The selected game's `.inp` recordings in the input directory, with
+/// record, playback, rename and delete
+pub struct RecordingsPanel {
+    game: Option<String>,
+    dir: Option<PathBuf>,
+    recordings: Vec<Recording>,
+    stale: bool,
+    /// Recording being renamed and the name typed so far
+    renaming: Option<(PathBuf, String)>,
+    confirm_delete: Option<PathBuf>,
+    exit_after_playback: bool,
+    error: Option<String>,
+    request: Option<RecordingRequest>,
+}
+
+impl RecordingsPanel {
+    pub fn new() -> Self {
+        Self {
+            game: None,
+            dir: None,
+            recordings: Vec::new(),
+            stale: true,
+            renaming: None,
+            confirm_delete: None,
+            exit_after_playback: false,
+            error: None,
+            request: None,
+        }
+    }
+
+    /// Re-read the folder on the next frame, e.g. after a recording session
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    pub fn take_request(&mut self) -> Option<RecordingRequest> {
+        self.request.take()
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, config: &AppConfig, selected_game: Option<&Game>) {
+        let Some(game) = selected_game else {
+            ui.add_space(20.0);
+            ui.vertical_centered(|ui| ui.label("Select a game to see its recordings."));
+            return;
+        };
+        let Some(dir) = config.input_path.clone() else {
+            ui.add_space(20.0);
+            ui.vertical_centered(|ui| {
+                ui.label("No input folder is set.");
+                ui.label(
+                    egui::RichText::new(
+                        "Set Input Configuration (input) in Directories to record and play back .inp files.",
+                    )
+                    .weak(),
+                );
+            });
+            return;
+        };
+
+        if self.game.as_deref() != Some(game.name.as_str()) || self.dir.as_ref() != Some(&dir) {
+            self.game = Some(game.name.clone());
+            self.dir = Some(dir.clone());
+            self.renaming = None;
+            self.confirm_delete = None;
+            self.error = None;
+            self.stale = true;
+        }
+        if self.stale {
+            self.recordings = list_recordings(&dir, &game.name);
+            self.stale = false;
+        }
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("⏺ Record")
+                .on_hover_text("Start the game and record its inputs to a new file")
+                .clicked()
+            {
+                self.request = Some(RecordingRequest::Record {
+                    game: game.name.clone(),
+                    file_name: new_recording_name(&dir, &game.name),
+                });
+            }
+            if ui.button("🔄 Refresh").clicked() {
+                self.stale = true;
+            }
+            ui.checkbox(&mut self.exit_after_playback, "Quit after playback");
+        });
+        ui.label(
+            egui::RichText::new(dir.display().to_string())
+                .small()
+                .weak(),
+        );
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        ui.separator();
+
+        if self.recordings.is_empty() {
+            ui.label(
+                egui::RichText::new(format!("No recordings of {} yet.", game.description)).weak(),
+            );
+            return;
+        }
+
+        let mut action = None;
+        TableBuilder::new(ui)
+            .id_salt("recordings_table")
+            .striped(true)
+            .column(Column::remainder().at_least(160.0))
+            .column(Column::auto().at_least(120.0))
+            .column(Column::auto().at_least(60.0))
+            .column(Column::auto().at_least(150.0))
+            .header(20.0, |mut header| {
+                for title in ["File", "Recorded", "Size", ""] {
+                    header.col(|ui| {
+                        ui.strong(title);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for recording in &self.recordings {
+                    body.row(24.0, |mut row| {
+                        row.col(|ui| match &mut self.renaming {
+                            Some((path, name)) if *path == recording.path => {
+                                let response = ui.text_edit_singleline(name);
+                                if response.lost_focus()
+                                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                                {
+                                    action = Some(Action::Rename(recording.clone(), name.clone()));
+                                }
+                            }
+                            _ => {
+                                ui.label(&recording.file_name);
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(
+                                recording
+                                    .modified
+                                    .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                                    .unwrap_or_default(),
+                            );
+                        });
+                        row.col(|ui| {
+                            ui.label(format_size(recording.size));
+                        });
+                        row.col(|ui| {
+                            if ui.small_button("▶").on_hover_text("Play back").clicked() {
+                                action = Some(Action::Play(recording.clone()));
+                            }
+                            let renaming = self
+                                .renaming
+                                .as_ref()
+                                .is_some_and(|(path, _)| *path == recording.path);
+                            if renaming {
+                                if ui.small_button("✔").on_hover_text("Save name").clicked()
+                                    && let Some((_, name)) = &self.renaming
+                                {
+                                    action = Some(Action::Rename(recording.clone(), name.clone()));
+                                }
+                                if ui.small_button("✖").on_hover_text("Cancel").clicked() {
+                                    action = Some(Action::CancelRename);
+                                }
+                            } else if ui.small_button("✏").on_hover_text("Rename").clicked() {
+                                action = Some(Action::StartRename(recording.clone()));
+                            }
+                            if self.confirm_delete.as_ref() == Some(&recording.path) {
+                                if ui
+                                    .small_button(
+                                        egui::RichText::new("Delete?")
+                                            .color(ui.visuals().error_fg_color),
+                                    )
+                                    .clicked()
+                                {
+                                    action = Some(Action::Delete(recording.clone()));
+                                }
+                            } else if ui.small_button("🗑").on_hover_text("Delete").clicked() {
+                                action = Some(Action::ConfirmDelete(recording.clone()));
+                            }
+                        });
+                    });
+                }
+            });
+
+        if let Some(action) = action {
+            self.apply(action, game);
+        }
+    }
+
+    fn apply(&mut self, action: Action, game: &Game) {
+        self.error = None;
+        match action {
+            Action::Play(recording) => {
+                self.request = Some(RecordingRequest::Playback {
+                    game: game.name.clone(),
+                    file_name: recording.file_name,
+                    exit_after: self.exit_after_playback,
+                });
+            }
+            Action::StartRename(recording) => {
+                let name = recording
+                    .file_name
+                    .strip_suffix(".inp")
+                    .unwrap_or(&recording.file_name)
+                    .to_string();
+                self.renaming = Some((recording.path, name));
+                self.confirm_delete = None;
+            }
+            Action::CancelRename => self.renaming = None,
+            Action::Rename(recording, name) => match rename_recording(&recording, &name) {
+                Ok(_) => {
+                    self.renaming = None;
+                    self.stale = true;
+                }
+                Err(e) => self.error = Some(e.to_string()),
+            },
+            Action::ConfirmDelete(recording) => {
+                self.confirm_delete = Some(recording.path);
+                self.renaming = None;
+            }
+            Action::Delete(recording) => {
+                if let Err(e) = delete_recording(&recording) {
+                    self.error = Some(e.to_string());
+                }
+                self.confirm_delete = None;
+                self.stale = true;
+            }
+        }
+    }
+}
+
+impl Default for RecordingsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum Action {
+    Play(Recording),
+    StartRename(Recording),
+    CancelRename,
+    Rename(Recording, String),
+    ConfirmDelete(Recording),
+    Delete(Recording),
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
@@ -0,0 +1,122 @@
+// src/ui/panels/session_panel.rs
+use crate::mame::{GameSession, SessionManager};
+use crate::models::InstancePolicy;
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+use std::time::Duration;
+
+/// Lists the games running now with a stop button for each, and how the
+/// last few sessions ended
+pub struct SessionPanel {
+    stop_request: Option<u64>,
+}
+
+impl SessionPanel {
+    pub fn new() -> Self {
+        Self { stop_request: None }
+    }
+
+    /// Session the user asked to stop, if any
+    pub fn take_stop_request(&mut self) -> Option<u64> {
+        self.stop_request.take()
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, sessions: &SessionManager, policy: InstancePolicy) {
+        ui.horizontal(|ui| {
+            ui.heading("Running games");
+            ui.label(egui::RichText::new(format!("· {}", policy.display_name())).weak())
+                .on_hover_text("Change in Preferences → General");
+        });
+        ui.add_space(4.0);
+
+        if sessions.active().is_empty() {
+            ui.label(egui::RichText::new("No games are running.").weak());
+        } else {
+            self.show_active(ui, sessions.active());
+            // Keep uptimes ticking
+            ui.ctx().request_repaint_after(Duration::from_secs(1));
+        }
+
+        ui.add_space(12.0);
+        ui.heading("Recent sessions");
+        ui.add_space(4.0);
+        let mut any = false;
+        egui::Grid::new("recent_sessions")
+            .num_columns(4)
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for session in sessions.recent() {
+                    any = true;
+                    ui.label(&session.ended);
+                    ui.label(&session.description).on_hover_text(&session.game);
+                    ui.label(format_duration(Duration::from_secs(
+                        session.play_time as u64,
+                    )));
+                    let outcome = egui::RichText::new(session.outcome());
+                    ui.label(if session.crashed() {
+                        outcome.color(ui.visuals().error_fg_color)
+                    } else {
+                        outcome
+                    });
+                    ui.end_row();
+                }
+            });
+        if !any {
+            ui.label(egui::RichText::new("Nothing has finished yet.").weak());
+        }
+    }
+
+    fn show_active(&mut self, ui: &mut egui::Ui, active: &[GameSession]) {
+        TableBuilder::new(ui)
+            .id_salt("running_sessions")
+            .striped(true)
+            .column(Column::remainder().at_least(160.0))
+            .column(Column::auto().at_least(70.0))
+            .column(Column::auto().at_least(60.0))
+            .column(Column::auto().at_least(90.0))
+            .header(20.0, |mut header| {
+                for title in ["Game", "Uptime", "PID", ""] {
+                    header.col(|ui| {
+                        ui.strong(title);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for session in active {
+                    body.row(24.0, |mut row| {
+                        row.col(|ui| {
+                            ui.label(&session.description).on_hover_text(&session.game);
+                        });
+                        row.col(|ui| {
+                            ui.monospace(format_duration(session.uptime()));
+                        });
+                        row.col(|ui| {
+                            ui.monospace(session.pid().to_string());
+                        });
+                        row.col(|ui| {
+                            let (label, hint) = if session.stop_requested {
+                                ("⚠ Force kill", "The game did not quit; kill it outright")
+                            } else {
+                                ("⏹ Stop", "Ask MAME to quit")
+                            };
+                            if ui.button(label).on_hover_text(hint).clicked() {
+                                self.stop_request = Some(session.id);
+                            }
+                        });
+                    });
+                }
+            });
+    }
+}
+
+impl Default for SessionPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
@@ -367,6 +367,22 @@ impl Sidebar {
                     egui::RichText::new("CHD Games").color(egui::Color32::from_rgb(0, 188, 212)),
                 );
             });
+
+            ui.horizontal(|ui| {
+                let mut samples_missing = filter_settings.other_filters.show_samples_missing;
+                if ui
+                    .add(egui::Checkbox::new(&mut samples_missing, ""))
+                    .clicked()
+                {
+                    filter_settings.other_filters.show_samples_missing = samples_missing;
+                }
+
+                ui.add_space(4.0);
+                ui.label(
+                    egui::RichText::new("Samples Missing")
+                        .color(egui::Color32::from_rgb(255, 152, 0)),
+                );
+            });
         });
 
         ui.add_space(16.0);
@@ -414,6 +430,7 @@ impl Sidebar {
         filters.other_filters.show_favorites = false;
         filters.other_filters.show_parents_only = false;
         filters.other_filters.show_chd_games = false;
+        filters.other_filters.show_samples_missing = false;
         filters.cpu_filter.clear();
         filters.device_filter.clear();
         filters.sound_filter.clear();
@@ -430,6 +447,7 @@ impl Sidebar {
         filters.other_filters.show_favorites = true;
         filters.other_filters.show_parents_only = true;
         filters.other_filters.show_chd_games = true;
+        filters.other_filters.show_samples_missing = true;
     }
 
     fn show_manufacturer_filters(
@@ -1,5 +1,10 @@
-use crate::mame::{SoftwareEntry, SoftwareListLoader, SoftwareListSummary};
-use crate::models::AppConfig;
+use crate::mame::{
+    SoftwareEntry, SoftwareLaunch, SoftwareListLoader, SoftwareListSummary, compatible_systems,
+};
+use crate::models::{AppConfig, Game, SoftwareListRef, SoftwareListStatus};
+use crate::utils::rom_utils::software_audit::{
+    SoftwareAuditResult, SoftwareAuditor, SoftwareMediaStatus,
+};
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
 use std::collections::HashSet;
@@ -23,6 +28,30 @@ enum SoftwareListWorkerMessage {
     Finished(Result<PreparedSoftwareListData, String>),
 }
 
+enum SoftwareAuditMessage {
+    Progress {
+        completed: usize,
+        total: usize,
+    },
+    Finished(Vec<SoftwareAuditResult>),
+    /// Results for the entries at these indices, after their media changed
+    Updated(Vec<(usize, SoftwareAuditResult)>),
+}
+
+/// Software lists of the machine selected in the game list
+struct MachineLists {
+    name: String,
+    description: String,
+    lists: Vec<SoftwareListRef>,
+}
+
+impl MachineLists {
+    /// The machine's entry for the item's list, if its filter accepts the item
+    fn list_for(&self, entry: &SoftwareEntry) -> Option<&SoftwareListRef> {
+        self.lists.iter().find(|list| entry.is_accepted_by(list))
+    }
+}
+
 #[derive(Default)]
 struct MediaPathIndex {
     keys: HashSet<String>,
@@ -55,6 +84,24 @@ pub struct SoftwareListPanel {
     filter_cache_valid: bool,
     search_text: String,
     selected_list: Option<String>,
+    /// Media audit results, one per entry, once an audit has run
+    audit_results: Option<Vec<SoftwareAuditResult>>,
+    audit_receiver: Option<mpsc::Receiver<SoftwareAuditMessage>>,
+    audit_progress: (usize, usize),
+    /// Changed media paths waiting for the running audit to finish
+    pending_reaudit: Vec<PathBuf>,
+    status_filter: Option<SoftwareMediaStatus>,
+    cached_status_filter: Option<SoftwareMediaStatus>,
+    /// Entry picked in the table, and the systems that can run it
+    selected_entry: Option<usize>,
+    launch_systems: Vec<(String, String)>,
+    launch_systems_key: Option<(usize, usize)>,
+    launch_system: Option<String>,
+    launch_request: Option<SoftwareLaunch>,
+    /// Restrict the table to what the selected machine can load
+    machine: Option<MachineLists>,
+    machine_only: bool,
+    cached_machine_filter: Option<String>,
 }
 
 impl SoftwareListPanel {
@@ -78,16 +125,80 @@ impl SoftwareListPanel {
             filter_cache_valid: false,
             search_text: String::new(),
             selected_list: None,
+            audit_results: None,
+            audit_receiver: None,
+            audit_progress: (0, 0),
+            pending_reaudit: Vec::new(),
+            status_filter: None,
+            cached_status_filter: None,
+            selected_entry: None,
+            launch_systems: Vec::new(),
+            launch_systems_key: None,
+            launch_system: None,
+            launch_request: None,
+            machine: None,
+            machine_only: false,
+            cached_machine_filter: None,
         }
     }
 
+    /// Software the user asked to run, if any. The app launches it so the
+    /// process is tracked like any other running game.
+    pub fn take_launch_request(&mut self) -> Option<SoftwareLaunch> {
+        self.launch_request.take()
+    }
+
     pub fn invalidate(&mut self) {
         self.loaded_hash_path = None;
         self.loaded_software_rom_paths.clear();
         self.load_receiver = None;
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui, config: &AppConfig) {
+    /// Re-check the media path matches for software whose files changed
+    /// under the loaded software-list ROM roots, without reloading the lists
+    pub fn media_paths_changed(&mut self, paths: &[PathBuf], ctx: &egui::Context) {
+        if self.load_receiver.is_some() || self.media_paths.checked_root_count == 0 {
+            return;
+        }
+
+        let mut rechecked = false;
+        for path in paths {
+            let Some((list_name, software_name)) = self
+                .loaded_software_rom_paths
+                .iter()
+                .find_map(|root| media_item(root, path))
+            else {
+                continue;
+            };
+            let key = media_path_key(&list_name, &software_name);
+            if media_item_present(&self.loaded_software_rom_paths, &list_name, &software_name) {
+                self.media_paths.keys.insert(key);
+            } else {
+                self.media_paths.keys.remove(&key);
+            }
+            rechecked = true;
+        }
+
+        if rechecked {
+            self.reaudit_changed(paths, ctx);
+            self.path_found_entry_count = self
+                .entries
+                .iter()
+                .filter(|entry| {
+                    media_path_presence(entry, &self.media_paths) == MediaPathPresence::PathFound
+                })
+                .count();
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &AppConfig,
+        games: &[Game],
+        selected_machine: Option<&Game>,
+    ) {
+        self.set_machine(selected_machine);
         ui.add_space(12.0);
 
         ui.horizontal(|ui| {
@@ -100,6 +211,19 @@ impl SoftwareListPanel {
                 if ui.button("Refresh").clicked() {
                     self.invalidate();
                 }
+                let can_audit = self.load_receiver.is_none()
+                    && self.audit_receiver.is_none()
+                    && !self.entries.is_empty()
+                    && !config.software_rom_paths.is_empty();
+                if ui
+                    .add_enabled(can_audit, egui::Button::new("Audit media"))
+                    .on_hover_text(
+                        "Check every item's ROMs and CHDs against the hashes in the hash XML",
+                    )
+                    .clicked()
+                {
+                    self.start_audit(&config.software_rom_paths, ui.ctx());
+                }
             });
         });
 
@@ -117,6 +241,7 @@ impl SoftwareListPanel {
 
         self.ensure_loaded(hash_path, &config.software_rom_paths, ui.ctx());
         self.poll_worker();
+        self.poll_audit(ui.ctx());
 
         ui.horizontal_wrapped(|ui| {
             ui.label("Hash:");
@@ -131,7 +256,29 @@ impl SoftwareListPanel {
                 ui.label(format!("{} entries", self.entries.len()));
                 if !config.software_rom_paths.is_empty() {
                     ui.separator();
-                    if self.media_paths.checked_root_count == 0 {
+                    if self.audit_receiver.is_some() {
+                        let (completed, total) = self.audit_progress;
+                        ui.spinner();
+                        ui.label(format!("Auditing media {completed} of {total}"));
+                    } else if let Some(results) = &self.audit_results {
+                        let count = |status| {
+                            results
+                                .iter()
+                                .filter(|result| result.status == status)
+                                .count()
+                        };
+                        let mut summary = format!(
+                            "{} good, {} bad, {} missing",
+                            count(SoftwareMediaStatus::Good),
+                            count(SoftwareMediaStatus::Bad),
+                            count(SoftwareMediaStatus::Missing)
+                        );
+                        let undumped = count(SoftwareMediaStatus::NoDump);
+                        if undumped > 0 {
+                            summary.push_str(&format!(", {} not dumped", undumped));
+                        }
+                        ui.label(summary);
+                    } else if self.media_paths.checked_root_count == 0 {
                         ui.label("media paths not checked")
                             .on_hover_text("No configured software-list ROM root could be read.");
                     } else {
@@ -176,7 +323,124 @@ impl SoftwareListPanel {
         self.show_filters(ui);
         ui.add_space(8.0);
         self.show_warnings(ui);
-        self.show_table(ui);
+        self.show_launch_bar(ui, games);
+        ui.add_space(6.0);
+        self.show_table(ui, games);
+    }
+
+    fn set_machine(&mut self, game: Option<&Game>) {
+        if self.machine.as_ref().map(|machine| machine.name.as_str())
+            == game.map(|g| g.name.as_str())
+        {
+            return;
+        }
+        self.machine = game.map(|game| MachineLists {
+            name: game.name.clone(),
+            description: game.description.clone(),
+            lists: game.rom_set.software_lists.clone(),
+        });
+    }
+
+    /// Machine the table is restricted to, if any. A machine without
+    /// software lists restricts nothing, so the table does not go empty
+    /// behind a checkbox that can no longer be unticked.
+    fn filtering_machine(&self) -> Option<&MachineLists> {
+        self.machine
+            .as_ref()
+            .filter(|machine| self.machine_only && !machine.lists.is_empty())
+    }
+
+    /// Name of the machine the table is restricted to, if any
+    fn machine_filter(&self) -> Option<&str> {
+        self.filtering_machine()
+            .map(|machine| machine.name.as_str())
+    }
+
+    fn show_launch_bar(&mut self, ui: &mut egui::Ui, games: &[Game]) {
+        let Some(entry_index) = self.selected_entry else {
+            ui.weak("Select an item to run it; double-click runs it on the default system.");
+            return;
+        };
+        self.refresh_launch_systems(entry_index, games);
+        // Filtering by a machine makes it the one to run on
+        let preferred = self
+            .launch_system
+            .clone()
+            .or_else(|| self.machine_filter().map(str::to_string));
+        let entry = &self.entries[entry_index];
+
+        ui.horizontal_wrapped(|ui| {
+            ui.strong(&entry.description);
+            ui.label(format!("({}:{})", entry.list_name, entry.name));
+            ui.separator();
+
+            let Some((default_system, _)) = self.launch_systems.first() else {
+                ui.colored_label(
+                    egui::Color32::from_rgb(222, 181, 86),
+                    format!(
+                        "No machine in the MAME data uses the {} list",
+                        entry.list_name
+                    ),
+                );
+                return;
+            };
+            let current = preferred
+                .clone()
+                .filter(|name| self.launch_systems.iter().any(|(system, _)| system == name))
+                .unwrap_or_else(|| default_system.clone());
+            let label = |index: usize, (name, description): &(String, String)| {
+                if index == 0 {
+                    format!("{} ({}) — default", description, name)
+                } else {
+                    format!("{} ({})", description, name)
+                }
+            };
+
+            ui.label("Run on:");
+            let selected_text = self
+                .launch_systems
+                .iter()
+                .enumerate()
+                .find(|(_, (name, _))| *name == current)
+                .map(|(index, system)| label(index, system))
+                .unwrap_or_default();
+            egui::ComboBox::from_id_salt("software_launch_system")
+                .selected_text(selected_text)
+                .width(260.0)
+                .show_ui(ui, |ui| {
+                    for (index, system) in self.launch_systems.iter().enumerate() {
+                        if ui
+                            .selectable_label(system.0 == current, label(index, system))
+                            .clicked()
+                        {
+                            self.launch_system = Some(system.0.clone());
+                        }
+                    }
+                });
+            if entry.part_count > 1 {
+                ui.label(format!("{} parts", entry.part_count));
+            }
+            if ui.button("▶ Launch").clicked() {
+                self.launch_request = games
+                    .iter()
+                    .find(|game| game.name == current)
+                    .map(|system| SoftwareLaunch::new(system, entry));
+            }
+        });
+    }
+
+    /// Compatible systems of the selected entry, recomputed when the
+    /// selection or the game list changes
+    fn refresh_launch_systems(&mut self, entry_index: usize, games: &[Game]) {
+        let key = (entry_index, games.len());
+        if self.launch_systems_key == Some(key) {
+            return;
+        }
+        self.launch_systems = compatible_systems(&self.entries[entry_index], games)
+            .into_iter()
+            .map(|game| (game.name.clone(), game.description.clone()))
+            .collect();
+        self.launch_systems_key = Some(key);
     }
 
     fn ensure_loaded(
@@ -261,6 +525,136 @@ impl SoftwareListPanel {
         });
     }
 
+    fn start_audit(&mut self, software_rom_paths: &[PathBuf], ctx: &egui::Context) {
+        let entries = self.entries.clone();
+        let software_rom_paths = software_rom_paths.to_vec();
+        let repaint_ctx = ctx.clone();
+        let (sender, receiver) = mpsc::channel();
+        self.audit_receiver = Some(receiver);
+        self.audit_progress = (0, entries.len());
+
+        thread::spawn(move || {
+            let mut auditor = SoftwareAuditor::new(&software_rom_paths);
+            let total = entries.len();
+            let mut results = Vec::with_capacity(total);
+            for (index, entry) in entries.iter().enumerate() {
+                results.push(auditor.audit(entry));
+                if (index + 1) % 64 == 0 {
+                    let _ = sender.send(SoftwareAuditMessage::Progress {
+                        completed: index + 1,
+                        total,
+                    });
+                    repaint_ctx.request_repaint();
+                }
+            }
+            let _ = sender.send(SoftwareAuditMessage::Finished(results));
+            repaint_ctx.request_repaint();
+        });
+    }
+
+    fn poll_audit(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = self.audit_receiver.take() else {
+            return;
+        };
+        let finished = self.receive_audit(receiver);
+        if finished && !self.pending_reaudit.is_empty() {
+            let paths = std::mem::take(&mut self.pending_reaudit);
+            self.reaudit_changed(&paths, ctx);
+        }
+    }
+
+    /// Apply what the audit worker sent; true once it is done
+    fn receive_audit(&mut self, receiver: mpsc::Receiver<SoftwareAuditMessage>) -> bool {
+        loop {
+            match receiver.try_recv() {
+                Ok(SoftwareAuditMessage::Progress { completed, total }) => {
+                    self.audit_progress = (completed, total);
+                }
+                Ok(SoftwareAuditMessage::Finished(results)) => {
+                    // A reload while the audit ran leaves results for stale entries
+                    if results.len() == self.entries.len() {
+                        self.audit_results = Some(results);
+                        self.invalidate_filter_cache();
+                    }
+                    return true;
+                }
+                Ok(SoftwareAuditMessage::Updated(updated)) => {
+                    if let Some(results) = self.audit_results.as_mut() {
+                        for (index, result) in updated {
+                            if let Some(slot) = results.get_mut(index) {
+                                *slot = result;
+                            }
+                        }
+                        self.invalidate_filter_cache();
+                    }
+                    return true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.warnings
+                        .push("Software media audit stopped unexpectedly".to_string());
+                    return true;
+                }
+            }
+        }
+
+        self.audit_receiver = Some(receiver);
+        false
+    }
+
+    /// Audit again, in the background, the entries whose media changed on
+    /// disk. Changes seen while an audit runs are picked up after it.
+    fn reaudit_changed(&mut self, paths: &[PathBuf], ctx: &egui::Context) {
+        if self.audit_results.is_none() {
+            return;
+        }
+        if self.audit_receiver.is_some() {
+            self.pending_reaudit.extend_from_slice(paths);
+            return;
+        }
+
+        let changed: HashSet<String> = paths
+            .iter()
+            .filter_map(|path| {
+                self.loaded_software_rom_paths
+                    .iter()
+                    .find_map(|root| media_item(root, path))
+            })
+            .map(|(list_name, software_name)| media_path_key(&list_name, &software_name))
+            .collect();
+        let touched: Vec<(usize, SoftwareEntry)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                // Clones read from their parent's set as well
+                std::iter::once(&entry.name)
+                    .chain(&entry.clone_of)
+                    .any(|name| changed.contains(&media_path_key(&entry.list_name, name)))
+            })
+            .map(|(index, entry)| (index, entry.clone()))
+            .collect();
+        if touched.is_empty() {
+            return;
+        }
+
+        let software_rom_paths = self.loaded_software_rom_paths.clone();
+        let repaint_ctx = ctx.clone();
+        let (sender, receiver) = mpsc::channel();
+        self.audit_receiver = Some(receiver);
+        self.audit_progress = (0, touched.len());
+
+        thread::spawn(move || {
+            let mut auditor = SoftwareAuditor::new(&software_rom_paths);
+            let updated = touched
+                .into_iter()
+                .map(|(index, entry)| (index, auditor.audit(&entry)))
+                .collect();
+            let _ = sender.send(SoftwareAuditMessage::Updated(updated));
+            repaint_ctx.request_repaint();
+        });
+    }
+
     fn poll_worker(&mut self) {
         let Some(receiver) = self.load_receiver.take() else {
             return;
@@ -328,6 +722,11 @@ impl SoftwareListPanel {
         self.path_found_entry_count = 0;
         self.normalized_search_text.clear();
         self.filtered_indices.clear();
+        self.audit_results = None;
+        self.audit_receiver = None;
+        self.pending_reaudit.clear();
+        self.selected_entry = None;
+        self.launch_systems_key = None;
         self.invalidate_filter_cache();
     }
 
@@ -419,6 +818,56 @@ impl SoftwareListPanel {
                         }
                     }
                 });
+
+            ui.separator();
+
+            ui.add_enabled_ui(self.audit_results.is_some(), |ui| {
+                egui::ComboBox::from_id_salt("software_media_status_filter")
+                    .selected_text(
+                        self.status_filter
+                            .map_or("All media", |status| status.label()),
+                    )
+                    .width(110.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.status_filter, None, "All media");
+                        for status in SoftwareMediaStatus::ALL {
+                            ui.selectable_value(
+                                &mut self.status_filter,
+                                Some(status),
+                                status.label(),
+                            );
+                        }
+                    })
+                    .response
+                    .on_disabled_hover_text("Run a media audit to filter by its results");
+            });
+
+            ui.separator();
+
+            let (label, lists) = match &self.machine {
+                Some(machine) => (
+                    format!("Only for {}", machine.description),
+                    machine
+                        .lists
+                        .iter()
+                        .map(|list| {
+                            let mut line = format!("{} ({})", list.name, list.status.label());
+                            if let Some(filter) = &list.filter {
+                                line.push_str(&format!(", filter {}", filter));
+                            }
+                            line
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+                None => ("Only for selected machine".to_string(), Vec::new()),
+            };
+            ui.add_enabled_ui(!lists.is_empty(), |ui| {
+                ui.checkbox(&mut self.machine_only, label)
+                    .on_hover_text(lists.join("\n"))
+                    .on_disabled_hover_text(
+                        "Select a machine with software lists in the game list",
+                    );
+            });
         });
     }
 
@@ -443,15 +892,18 @@ impl SoftwareListPanel {
         ui.add_space(8.0);
     }
 
-    fn show_table(&mut self, ui: &mut egui::Ui) {
+    fn show_table(&mut self, ui: &mut egui::Ui, games: &[Game]) {
         self.refresh_filter_cache_if_needed();
         ui.label(format!("Showing {} entries", self.filtered_indices.len()));
         ui.add_space(6.0);
 
         let table_height = ui.available_height().max(160.0);
+        let mut clicked = None;
+        let machine = self.filtering_machine();
         TableBuilder::new(ui)
             .striped(true)
             .resizable(true)
+            .sense(egui::Sense::click())
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
             .max_scroll_height(table_height)
             .column(Column::initial(110.0).at_least(80.0).resizable(true))
@@ -482,8 +934,10 @@ impl SoftwareListPanel {
                     ui.strong("Status");
                 });
                 header.col(|ui| {
-                    ui.strong("Media path")
-                        .on_hover_text(Self::presence_disclaimer());
+                    let header = ui.strong("Media");
+                    if self.audit_results.is_none() {
+                        header.on_hover_text(Self::presence_disclaimer());
+                    }
                 });
                 header.col(|ui| {
                     ui.strong("Parts");
@@ -491,10 +945,25 @@ impl SoftwareListPanel {
             })
             .body(|body| {
                 body.rows(28.0, self.filtered_indices.len(), |mut row| {
-                    let entry = &self.entries[self.filtered_indices[row.index()]];
+                    let entry_index = self.filtered_indices[row.index()];
+                    let entry = &self.entries[entry_index];
+                    let audit = self
+                        .audit_results
+                        .as_ref()
+                        .and_then(|results| results.get(entry_index));
+                    row.set_selected(self.selected_entry == Some(entry_index));
                     row.col(|ui| {
-                        ui.label(&entry.list_name)
-                            .on_hover_text(&entry.list_description);
+                        // Lists the filtered machine only shares are marked
+                        match machine.and_then(|machine| machine.list_for(entry)) {
+                            Some(list) if list.status == SoftwareListStatus::Compatible => {
+                                ui.label(format!("{} · compatible", entry.list_name))
+                                    .on_hover_text(&entry.list_description);
+                            }
+                            _ => {
+                                ui.label(&entry.list_name)
+                                    .on_hover_text(&entry.list_description);
+                            }
+                        }
                     });
                     row.col(|ui| {
                         let response = ui.label(&entry.name);
@@ -518,6 +987,21 @@ impl SoftwareListPanel {
                         ui.label(Self::support_label(&entry.supported));
                     });
                     row.col(|ui| {
+                        if let Some(audit) = audit {
+                            let color = match audit.status {
+                                SoftwareMediaStatus::Good => egui::Color32::from_rgb(94, 206, 118),
+                                SoftwareMediaStatus::Bad => egui::Color32::from_rgb(224, 92, 92),
+                                SoftwareMediaStatus::Missing => {
+                                    egui::Color32::from_rgb(222, 181, 86)
+                                }
+                                SoftwareMediaStatus::NoDump => ui.visuals().weak_text_color(),
+                            };
+                            let response = ui.colored_label(color, audit.status.label());
+                            if !audit.problems.is_empty() {
+                                response.on_hover_text(audit.problems.join("\n"));
+                            }
+                            return;
+                        }
                         match media_path_presence(entry, &self.media_paths) {
                             MediaPathPresence::PathFound => {
                                 ui.colored_label(
@@ -550,15 +1034,40 @@ impl SoftwareListPanel {
                         };
                         ui.label(format!("{} {}", entry.part_count, interfaces));
                     });
+
+                    let response = row.response();
+                    if response.double_clicked() {
+                        clicked = Some((entry_index, true));
+                    } else if response.clicked() {
+                        clicked = Some((entry_index, false));
+                    }
                 });
             });
+
+        if let Some((entry_index, launch)) = clicked {
+            self.selected_entry = Some(entry_index);
+            if launch {
+                // Double-click runs on the default system
+                self.refresh_launch_systems(entry_index, games);
+                let entry = &self.entries[entry_index];
+                self.launch_request = self.launch_systems.first().and_then(|(system, _)| {
+                    games
+                        .iter()
+                        .find(|game| &game.name == system)
+                        .map(|system| SoftwareLaunch::new(system, entry))
+                });
+            }
+        }
     }
 
     fn refresh_filter_cache_if_needed(&mut self) {
         let query = normalize_search_query(&self.search_text);
+        let machine_filter = self.machine_filter().map(str::to_string);
         if self.filter_cache_valid
             && self.cached_query == query
             && self.cached_selected_list == self.selected_list
+            && self.cached_status_filter == self.status_filter
+            && self.cached_machine_filter == machine_filter
         {
             return;
         }
@@ -569,8 +1078,19 @@ impl SoftwareListPanel {
             &query,
             self.selected_list.as_deref(),
         );
+        if let (Some(status), Some(results)) = (self.status_filter, &self.audit_results) {
+            self.filtered_indices
+                .retain(|&index| results.get(index).is_some_and(|r| r.status == status));
+        }
+        if let Some(machine) = self.machine.as_ref().filter(|_| machine_filter.is_some()) {
+            let entries = &self.entries;
+            self.filtered_indices
+                .retain(|&index| machine.list_for(&entries[index]).is_some());
+        }
         self.cached_query = query;
         self.cached_selected_list = self.selected_list.clone();
+        self.cached_status_filter = self.status_filter;
+        self.cached_machine_filter = machine_filter;
         self.filter_cache_valid = true;
     }
 
@@ -579,7 +1099,7 @@ impl SoftwareListPanel {
     }
 
     fn presence_disclaimer() -> &'static str {
-        "Best-effort path presence only. Archive and CHD contents are not audited, and merged set layouts may differ."
+        "Best-effort path presence only. Archive and CHD contents are checked by Audit media, and merged set layouts may differ."
     }
 
     fn list_label(name: &str) -> String {
@@ -693,6 +1213,35 @@ fn build_media_path_index(paths: &[PathBuf]) -> MediaPathIndex {
     index
 }
 
+/// The `(list, software)` a path under a software-list root belongs to:
+/// `<root>/<list>/<software>.zip` or anything inside `<root>/<list>/<software>/`
+fn media_item(root: &Path, path: &Path) -> Option<(String, String)> {
+    let mut components = path.strip_prefix(root).ok()?.components();
+    let list_name = components.next()?.as_os_str().to_str()?.to_string();
+    let item = Path::new(components.next()?.as_os_str());
+    let software_name = if components.next().is_some() {
+        item.to_str()?
+    } else {
+        item.file_stem()?.to_str()?
+    };
+    Some((list_name, software_name.to_string()))
+}
+
+/// Same rule as `build_media_path_index`, for a single software item
+fn media_item_present(roots: &[PathBuf], list_name: &str, software_name: &str) -> bool {
+    roots.iter().any(|root| {
+        let list_dir = root.join(list_name);
+        ["zip", "7z"].iter().any(|ext| {
+            list_dir
+                .join(format!("{}.{}", software_name, ext))
+                .is_file()
+        }) || {
+            let folder = list_dir.join(software_name);
+            folder.is_dir() && directory_contains_regular_file(&folder)
+        }
+    })
+}
+
 fn directory_contains_regular_file(path: &Path) -> bool {
     WalkDir::new(path)
         .follow_links(false)
@@ -46,6 +46,7 @@ impl RedesignCollection {
             Self::Issues => matches!(
                 status,
                 RomStatus::ChdMissing
+                    | RomStatus::ChdIncorrect
                     | RomStatus::ChdRequired
                     | RomStatus::Incorrect
                     | RomStatus::NotWorking
@@ -294,6 +295,7 @@ mod tests {
 
         for status in [
             RomStatus::ChdMissing,
+            RomStatus::ChdIncorrect,
             RomStatus::ChdRequired,
             RomStatus::Incorrect,
             RomStatus::NotWorking,
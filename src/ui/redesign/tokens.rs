@@ -100,6 +100,7 @@ impl RedesignTokens {
         match status {
             RomStatus::Available => Self::STATUS_OK,
             RomStatus::ChdMissing
+            | RomStatus::ChdIncorrect
             | RomStatus::ChdRequired
             | RomStatus::Incorrect
             | RomStatus::NotWorking
@@ -114,6 +115,7 @@ impl RedesignTokens {
         match status {
             RomStatus::Available => "ROM OK",
             RomStatus::ChdMissing => "CHD missing",
+            RomStatus::ChdIncorrect => "CHD incorrect",
             RomStatus::ChdRequired => "CHD required",
             RomStatus::Missing => "Missing",
             RomStatus::Incorrect => "Incorrect",
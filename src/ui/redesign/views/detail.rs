@@ -3,7 +3,7 @@ use super::super::state::RedesignState;
 use super::super::tokens::RedesignTokens;
 use super::super::widgets::{accent_button, card_frame, secondary_button, status_dot, text_link};
 use crate::app::MameApp;
-use crate::models::{AppConfig, Game, RomStatus};
+use crate::models::{AppConfig, Game, RomStatus, SampleStatus};
 use crate::ui::panels::artwork_loader::{ArtworkLoader, ArtworkType};
 use eframe::egui;
 
@@ -209,6 +209,42 @@ fn show_info_card(ui: &mut egui::Ui, app: &mut MameApp, game: &Game, narrow: boo
 
         info_row(ui, "ROM set", &format!("{}.zip", game.name), label_width);
         info_row_status(ui, "Status", game.status, label_width);
+        if game.sample_status != SampleStatus::NotRequired {
+            info_row(
+                ui,
+                "Samples",
+                &format!(
+                    "{} ({} samples)",
+                    game.sample_status.description(),
+                    game.rom_set.samples.len()
+                ),
+                label_width,
+            );
+        }
+        if !game.rom_set.software_lists.is_empty() {
+            let lists: Vec<String> = game
+                .rom_set
+                .software_lists
+                .iter()
+                .map(|list| match &list.filter {
+                    Some(filter) => {
+                        format!("{} ({}, {})", list.name, list.status.label(), filter)
+                    }
+                    None => format!("{} ({})", list.name, list.status.label()),
+                })
+                .collect();
+            let count = lists.len();
+            info_row(
+                ui,
+                "Software",
+                &format!(
+                    "{count} compatible software list{} — {}",
+                    if count == 1 { "" } else { "s" },
+                    lists.join(", ")
+                ),
+                label_width,
+            );
+        }
         if game.is_clone {
             if let Some(parent) = &game.parent {
                 let parent_title = app
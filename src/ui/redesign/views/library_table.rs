@@ -876,6 +876,8 @@ mod tests {
             requires_chd: false,
             chd_name: None,
             verification_status: None,
+            sample_status: Default::default(),
+            rom_set: Default::default(),
         }
     }
 
@@ -21,21 +21,26 @@ pub fn show(ui: &mut egui::Ui, app: &mut MameApp) {
 }
 
 fn show_content(ui: &mut egui::Ui, app: &mut MameApp, compact: bool) {
-    let version = app
-        .config
-        .mame_executables
-        .get(app.config.selected_mame_index)
-        .map(|m| m.version.as_str())
-        .unwrap_or("—")
-        .to_owned();
+    let reference = match app.dialog_manager.rom_verify_dialog().reference_dat() {
+        Some(dat) => dat.label(),
+        None => {
+            let version = app
+                .config
+                .mame_executables
+                .get(app.config.selected_mame_index)
+                .map(|m| m.version.as_str())
+                .unwrap_or("—");
+            format!("MAME {version} datfile")
+        }
+    };
 
     if compact {
-        show_heading(ui, &version);
+        show_heading(ui, &reference);
         ui.add_space(10.0);
         ui.horizontal_wrapped(|ui| show_actions(ui, app));
     } else {
         ui.horizontal(|ui| {
-            show_heading(ui, &version);
+            show_heading(ui, &reference);
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 show_actions(ui, app);
             });
@@ -57,7 +62,7 @@ fn show_content(ui: &mut egui::Ui, app: &mut MameApp, compact: bool) {
     });
 }
 
-fn show_heading(ui: &mut egui::Ui, version: &str) {
+fn show_heading(ui: &mut egui::Ui, reference: &str) {
     ui.vertical(|ui| {
         ui.label(
             egui::RichText::new("ROM Verification")
@@ -65,11 +70,9 @@ fn show_heading(ui: &mut egui::Ui, version: &str) {
                 .color(RedesignTokens::TEXT_BRIGHT),
         );
         ui.label(
-            egui::RichText::new(format!(
-                "CLRMamePro Lite · CRC check against MAME {version} datfile"
-            ))
-            .font(fonts::regular(12.0))
-            .color(RedesignTokens::TEXT_MUTED),
+            egui::RichText::new(format!("CLRMamePro Lite · CRC check against {reference}"))
+                .font(fonts::regular(12.0))
+                .color(RedesignTokens::TEXT_MUTED),
         );
     });
 }
@@ -99,5 +102,22 @@ fn show_actions(ui: &mut egui::Ui, app: &mut MameApp) {
         if secondary_button(ui, "Stop").clicked() {
             dialog.stop_verification();
         }
+    } else {
+        let mut changed = ui
+            .checkbox(&mut app.config.use_mame_audit, "Use mame -verifyroms")
+            .on_hover_text(
+                "Slower: runs MAME on batches of sets instead of reading archive CRCs directly",
+            )
+            .changed();
+        if app.config.use_mame_audit {
+            ui.label("Workers");
+            changed |= ui
+                .add(egui::DragValue::new(&mut app.config.verify_workers).range(1..=16))
+                .on_hover_text("MAME processes verifying at the same time")
+                .changed();
+        }
+        if changed {
+            app.save_config();
+        }
     }
 }
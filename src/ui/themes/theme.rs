@@ -103,6 +103,7 @@ impl UiTheme {
             crate::models::RomStatus::Preliminary => self.working_status_color,
             crate::models::RomStatus::ChdRequired => self.working_status_color,
             crate::models::RomStatus::ChdMissing => self.missing_status_color,
+            crate::models::RomStatus::ChdIncorrect => self.missing_status_color,
             crate::models::RomStatus::Unknown => self.secondary_accent_color,
         }
     }
@@ -221,6 +222,7 @@ impl ThemeHelper {
             crate::models::RomStatus::Preliminary => "Preliminary",
             crate::models::RomStatus::ChdRequired => "CHD Required",
             crate::models::RomStatus::ChdMissing => "CHD Missing",
+            crate::models::RomStatus::ChdIncorrect => "CHD Incorrect",
             crate::models::RomStatus::Unknown => "Unknown",
         };
 
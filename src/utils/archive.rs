@@ -0,0 +1,325 @@
+// src/utils/archive.rs
+//! Format-agnostic access to ROM and artwork archives (zip and 7z).
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Archive extensions MAME can load sets from, in lookup order
+pub const ARCHIVE_EXTENSIONS: [&str; 2] = ["zip", "7z"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    SevenZip,
+}
+
+impl ArchiveKind {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+        if ext.eq_ignore_ascii_case("zip") {
+            Some(ArchiveKind::Zip)
+        } else if ext.eq_ignore_ascii_case("7z") {
+            Some(ArchiveKind::SevenZip)
+        } else {
+            None
+        }
+    }
+}
+
+/// One file stored in an archive (or a loose set folder)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub name: String, // File name without any directory prefix
+    pub size: u64,
+    pub crc: u32,
+}
+
+/// List the files of an archive with the CRCs recorded in its headers.
+/// Nothing is decompressed for either format.
+pub fn list_entries(path: &Path) -> Result<Vec<ArchiveEntry>> {
+    match ArchiveKind::from_path(path) {
+        Some(ArchiveKind::Zip) => list_zip(path),
+        Some(ArchiveKind::SevenZip) => list_7z(path),
+        None => Err(anyhow!("Not a supported archive: {}", path.display())),
+    }
+}
+
+/// Read the first file accepted by `wanted`, decompressing only what is needed
+pub fn read_entry<F>(path: &Path, mut wanted: F) -> Result<Option<Vec<u8>>>
+where
+    F: FnMut(&ArchiveEntry) -> bool,
+{
+    let mut found = None;
+    visit_entries(path, |entry, data| {
+        if !wanted(entry) {
+            return Ok(true);
+        }
+        let mut buffer = Vec::with_capacity(entry.size as usize);
+        data.read_to_end(&mut buffer)?;
+        found = Some(buffer);
+        Ok(false)
+    })?;
+    Ok(found)
+}
+
+/// Read every file accepted by `wanted` in a single pass over the archive
+pub fn read_entries<F>(path: &Path, mut wanted: F) -> Result<Vec<(ArchiveEntry, Vec<u8>)>>
+where
+    F: FnMut(&ArchiveEntry) -> bool,
+{
+    let mut files = Vec::new();
+    visit_entries(path, |entry, data| {
+        if wanted(entry) {
+            let mut buffer = Vec::with_capacity(entry.size as usize);
+            data.read_to_end(&mut buffer)?;
+            files.push((entry.clone(), buffer));
+        }
+        Ok(true)
+    })?;
+    Ok(files)
+}
+
+/// Walk the files of an archive in storage order. The callback gets each
+/// entry with a reader for its data and returns false to stop early.
+fn visit_entries<F>(path: &Path, mut visit: F) -> Result<()>
+where
+    F: FnMut(&ArchiveEntry, &mut dyn Read) -> io::Result<bool>,
+{
+    match ArchiveKind::from_path(path) {
+        Some(ArchiveKind::Zip) => {
+            let mut archive = open_zip(path)?;
+            for index in 0..archive.len() {
+                let mut file = archive.by_index(index)?;
+                if file.is_dir() {
+                    continue;
+                }
+                let entry = ArchiveEntry {
+                    name: base_name(file.name()),
+                    size: file.size(),
+                    crc: file.crc32(),
+                };
+                if !visit(&entry, &mut file)? {
+                    break;
+                }
+            }
+            Ok(())
+        }
+        Some(ArchiveKind::SevenZip) => {
+            let mut reader = sevenz_rust::SevenZReader::open(path, sevenz_rust::Password::empty())
+                .with_context(|| format!("Failed to read 7z {}", path.display()))?;
+            reader
+                .for_each_entries(|file, data| {
+                    if file.is_directory {
+                        return Ok(true);
+                    }
+                    let mut data = data.take(file.size);
+                    let keep_going = visit(&seven_zip_entry(file), &mut data)?;
+                    // Solid archives decode sequentially, so unread data must be drained
+                    io::copy(&mut data, &mut io::sink())?;
+                    Ok(keep_going)
+                })
+                .with_context(|| format!("Failed to extract from {}", path.display()))?;
+            Ok(())
+        }
+        None => Err(anyhow!("Not a supported archive: {}", path.display())),
+    }
+}
+
+/// An archive opened once for many lookups by file name, e.g. icons.zip.
+/// Zips keep their handle and central directory; solid 7z archives can
+/// only be decoded front to back, so their files are read in one pass.
+pub enum IndexedArchive {
+    Zip {
+        archive: Mutex<zip::ZipArchive<File>>,
+        /// Lowercase base name to index in the archive
+        index: HashMap<String, usize>,
+    },
+    SevenZip {
+        files: HashMap<String, Vec<u8>>,
+    },
+}
+
+impl IndexedArchive {
+    pub fn open(path: &Path) -> Result<Self> {
+        match ArchiveKind::from_path(path) {
+            Some(ArchiveKind::Zip) => {
+                let mut archive = open_zip(path)?;
+                let mut index = HashMap::with_capacity(archive.len());
+                for i in 0..archive.len() {
+                    let file = archive.by_index_raw(i)?;
+                    if !file.is_dir() {
+                        index
+                            .entry(base_name(file.name()).to_lowercase())
+                            .or_insert(i);
+                    }
+                }
+                Ok(IndexedArchive::Zip {
+                    archive: Mutex::new(archive),
+                    index,
+                })
+            }
+            Some(ArchiveKind::SevenZip) => {
+                let mut files = HashMap::new();
+                for (entry, data) in read_entries(path, |_| true)? {
+                    files.entry(entry.name.to_lowercase()).or_insert(data);
+                }
+                Ok(IndexedArchive::SevenZip { files })
+            }
+            None => Err(anyhow!("Not a supported archive: {}", path.display())),
+        }
+    }
+
+    /// Contents of the file called `name`, ignoring case and folders
+    pub fn read(&self, name: &str) -> Option<Vec<u8>> {
+        let name = name.to_lowercase();
+        match self {
+            IndexedArchive::Zip { archive, index } => {
+                let i = *index.get(&name)?;
+                let mut archive = archive.lock().ok()?;
+                let mut file = archive.by_index(i).ok()?;
+                let mut buffer = Vec::with_capacity(file.size() as usize);
+                file.read_to_end(&mut buffer).ok()?;
+                Some(buffer)
+            }
+            IndexedArchive::SevenZip { files } => files.get(&name).cloned(),
+        }
+    }
+}
+
+/// Archive files inside a folder that share `stem`, e.g. `<dir>/<set>.zip`
+pub fn find_archives(dir: &Path, stem: &str) -> Vec<std::path::PathBuf> {
+    ARCHIVE_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", stem, ext)))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+fn open_zip(path: &Path) -> Result<zip::ZipArchive<File>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    zip::ZipArchive::new(file).with_context(|| format!("Failed to read zip {}", path.display()))
+}
+
+fn list_zip(path: &Path) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = open_zip(path)?;
+
+    let mut entries = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let file = archive.by_index_raw(index)?;
+        if file.is_dir() {
+            continue;
+        }
+        entries.push(ArchiveEntry {
+            name: base_name(file.name()),
+            size: file.size(),
+            crc: file.crc32(),
+        });
+    }
+
+    Ok(entries)
+}
+
+fn list_7z(path: &Path) -> Result<Vec<ArchiveEntry>> {
+    let archive = sevenz_rust::Archive::open(path)
+        .with_context(|| format!("Failed to read 7z {}", path.display()))?;
+
+    Ok(archive
+        .files
+        .iter()
+        .filter(|file| !file.is_directory)
+        .map(seven_zip_entry)
+        .collect())
+}
+
+fn seven_zip_entry(file: &sevenz_rust::SevenZArchiveEntry) -> ArchiveEntry {
+    ArchiveEntry {
+        name: base_name(file.name()),
+        size: file.size,
+        // Empty files carry no CRC in 7z headers; zero is their CRC32 anyway
+        crc: if file.has_crc { file.crc as u32 } else { 0 },
+    }
+}
+
+fn base_name(name: &str) -> String {
+    name.rsplit(['/', '\\']).next().unwrap_or(name).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn lists_and_reads_zip_and_7z_alike() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("pacman");
+        std::fs::create_dir(&source).unwrap();
+        std::fs::write(source.join("pacman.6e"), b"first rom").unwrap();
+        std::fs::write(source.join("pacman.6f"), b"second rom").unwrap();
+
+        let seven_zip = dir.path().join("pacman.7z");
+        sevenz_rust::compress_to_path(&source, &seven_zip).unwrap();
+
+        let zip_path = dir.path().join("pacman.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        for name in ["pacman.6e", "pacman.6f"] {
+            writer
+                .start_file(
+                    format!("pacman/{}", name),
+                    zip::write::SimpleFileOptions::default(),
+                )
+                .unwrap();
+            writer
+                .write_all(&std::fs::read(source.join(name)).unwrap())
+                .unwrap();
+        }
+        writer.finish().unwrap();
+
+        for path in [&zip_path, &seven_zip] {
+            let mut entries = list_entries(path).unwrap();
+            entries.sort_by(|a, b| a.name.cmp(&b.name));
+            assert_eq!(
+                entries,
+                vec![
+                    ArchiveEntry {
+                        name: "pacman.6e".to_string(),
+                        size: 9,
+                        crc: crc32fast::hash(b"first rom"),
+                    },
+                    ArchiveEntry {
+                        name: "pacman.6f".to_string(),
+                        size: 10,
+                        crc: crc32fast::hash(b"second rom"),
+                    },
+                ]
+            );
+
+            let data = read_entry(path, |entry| entry.name == "pacman.6f").unwrap();
+            assert_eq!(data.as_deref(), Some(&b"second rom"[..]));
+
+            let all = read_entries(path, |_| true).unwrap();
+            assert_eq!(all.len(), 2);
+            assert!(
+                all.iter()
+                    .all(|(entry, data)| crc32fast::hash(data) == entry.crc)
+            );
+
+            let indexed = IndexedArchive::open(path).unwrap();
+            assert_eq!(
+                indexed.read("PACMAN.6E").as_deref(),
+                Some(&b"first rom"[..])
+            );
+            assert_eq!(
+                indexed.read("pacman.6f").as_deref(),
+                Some(&b"second rom"[..])
+            );
+            assert!(indexed.read("pacman.5e").is_none());
+        }
+
+        assert_eq!(find_archives(dir.path(), "pacman").len(), 2);
+    }
+}
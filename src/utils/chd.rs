@@ -0,0 +1,176 @@
+// src/utils/chd.rs
+//! Reads the header of MAME CHD disk images (versions 3 to 5) so disks can be
+//! checked against the `<disk sha1>` values from -listxml without hashing them.
+use anyhow::{Context, Result, anyhow};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const CHD_TAG: &[u8; 8] = b"MComprHD";
+const V3_HEADER_SIZE: usize = 120;
+const V4_HEADER_SIZE: usize = 108;
+const V5_HEADER_SIZE: usize = 124;
+
+/// Identity of a CHD as recorded in its header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChdHeader {
+    pub version: u32,
+    /// Size of the uncompressed disk data in bytes
+    pub logical_size: u64,
+    /// Combined data and metadata SHA1, the value -listxml lists (lowercase hex)
+    pub sha1: String,
+    /// SHA1 of the parent CHD for delta images, if any
+    pub parent_sha1: Option<String>,
+}
+
+/// Read and validate the header of a CHD file
+pub fn read_header(path: &Path) -> Result<ChdHeader> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut header = Vec::with_capacity(V5_HEADER_SIZE);
+    file.take(V5_HEADER_SIZE as u64)
+        .read_to_end(&mut header)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    parse_header(&header).with_context(|| format!("Invalid CHD {}", path.display()))
+}
+
+/// Parse a CHD header from the first bytes of the file
+pub fn parse_header(data: &[u8]) -> Result<ChdHeader> {
+    if data.len() < 16 || &data[..8] != CHD_TAG {
+        return Err(anyhow!("Missing MComprHD signature"));
+    }
+
+    let length = be_u32(data, 8) as usize;
+    let version = be_u32(data, 12);
+    let expected = match version {
+        3 => V3_HEADER_SIZE,
+        4 => V4_HEADER_SIZE,
+        5 => V5_HEADER_SIZE,
+        _ => return Err(anyhow!("Unsupported CHD version {}", version)),
+    };
+    if length != expected || data.len() < expected {
+        return Err(anyhow!("Truncated v{} header", version));
+    }
+
+    // Field offsets from MAME's chd.cpp header layouts
+    let (logical_size, sha1, parent_sha1) = match version {
+        3 => (be_u64(data, 28), &data[80..100], &data[100..120]),
+        4 => (be_u64(data, 28), &data[48..68], &data[68..88]),
+        _ => (be_u64(data, 32), &data[84..104], &data[104..124]),
+    };
+
+    Ok(ChdHeader {
+        version,
+        logical_size,
+        sha1: hex(sha1),
+        parent_sha1: parent_sha1
+            .iter()
+            .any(|&b| b != 0)
+            .then(|| hex(parent_sha1)),
+    })
+}
+
+fn be_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn be_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_be_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Build a header for tests elsewhere in the crate
+#[cfg(test)]
+pub(crate) fn test_header(version: u32, logical_size: u64, sha1: [u8; 20]) -> Vec<u8> {
+    let length = match version {
+        3 => V3_HEADER_SIZE,
+        4 => V4_HEADER_SIZE,
+        _ => V5_HEADER_SIZE,
+    };
+    let mut data = vec![0u8; length];
+    data[..8].copy_from_slice(CHD_TAG);
+    data[8..12].copy_from_slice(&(length as u32).to_be_bytes());
+    data[12..16].copy_from_slice(&version.to_be_bytes());
+    let (size_at, sha1_at) = match version {
+        3 => (28, 80),
+        4 => (28, 48),
+        _ => (32, 84),
+    };
+    data[size_at..size_at + 8].copy_from_slice(&logical_size.to_be_bytes());
+    data[sha1_at..sha1_at + 20].copy_from_slice(&sha1);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_v3_v4_and_v5_headers() {
+        let sha1 = [0xab; 20];
+        for version in [3, 4, 5] {
+            let header = parse_header(&test_header(version, 1 << 30, sha1)).unwrap();
+            assert_eq!(header.version, version);
+            assert_eq!(header.logical_size, 1 << 30);
+            assert_eq!(header.sha1, "ab".repeat(20));
+            assert_eq!(header.parent_sha1, None);
+        }
+
+        let mut truncated = test_header(5, 0, sha1);
+        truncated.truncate(64);
+        assert!(parse_header(&truncated).is_err());
+        assert!(parse_header(b"not a chd file at all").is_err());
+        assert!(parse_header(&test_header(2, 0, sha1)).is_err());
+    }
+
+    /// Header bytes laid out field by field after chd.h, every field filled
+    /// so a misplaced offset reads the wrong value
+    fn header_bytes(hex: &str) -> Vec<u8> {
+        let digits: Vec<u8> = hex.bytes().filter(u8::is_ascii_hexdigit).collect();
+        digits
+            .chunks(2)
+            .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn reads_sha1_and_size_from_fixed_headers() {
+        // v5 CD image: cdlz/cdzl/cdfl compressors, 19584-byte hunks of
+        // 2448-byte frames, raw SHA1 at 64 ahead of the combined SHA1 at 84
+        let v5 = header_bytes(
+            "4d436f6d 70724844 0000007c 00000005
+             63646c7a 63647a6c 6364666c 00000000
+             00000000 2a3b8c00 00000000 2a1c5e10
+             00000000 0000007c 00004c80 00000990
+             5e0ffa8d 32a1c7f4 e2b9d0c3 a1f8e7d6
+             c5b4a392 3f1d9c2a b7e04c5d 86f1a2b3
+             c4d5e6f7 08192a3b 00000000 00000000
+             00000000 00000000 00000000",
+        );
+        let header = parse_header(&v5).unwrap();
+        assert_eq!(header.version, 5);
+        assert_eq!(header.logical_size, 708_545_536);
+        assert_eq!(header.sha1, "3f1d9c2ab7e04c5d86f1a2b3c4d5e6f708192a3b");
+        assert_eq!(header.parent_sha1, None);
+
+        // v4 hard disk: flags, compression and hunk count come before the
+        // logical size, and the raw SHA1 follows the parent SHA1
+        let v4 = header_bytes(
+            "4d436f6d 70724844 0000006c 00000004
+             00000002 00000003 0003a4c0 00000000
+             3a4c0000 00000000 3a4c7f20 00001000
+             c6b0e3a2 194d7f58 a0e1d2c3 b4a59687
+             78695a4b 00000000 00000000 00000000
+             00000000 00000000 0a1b2c3d 4e5f6071
+             8293a4b5 c6d7e8f9 01234567",
+        );
+        let header = parse_header(&v4).unwrap();
+        assert_eq!(header.version, 4);
+        assert_eq!(header.logical_size, 978_059_264);
+        assert_eq!(header.sha1, "c6b0e3a2194d7f58a0e1d2c3b4a5968778695a4b");
+        assert_eq!(header.parent_sha1, None);
+    }
+}
@@ -0,0 +1,286 @@
+// src/utils/fs_watcher.rs
+//! Watches the ROM, software-list and artwork folders (inotify on Linux) so
+//! files added, replaced or removed while the app is open are picked up
+//! without a rescan. Events are collected until the folders have been quiet
+//! for the debounce period, then handed over as one batch.
+use crate::models::AppConfig;
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Quiet period before a batch is released; long enough for a file copy to
+/// finish writing before its archive is re-read.
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(1500);
+
+/// What a watched folder feeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    Roms,
+    SoftwareRoms,
+    Artwork,
+    Icons,
+}
+
+/// Paths that changed during one burst of events, grouped by folder kind.
+/// A path under folders of several kinds appears in each group.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WatchBatch {
+    pub roms: Vec<PathBuf>,
+    pub software_roms: Vec<PathBuf>,
+    pub artwork: Vec<PathBuf>,
+    pub icons: Vec<PathBuf>,
+}
+
+impl WatchBatch {
+    pub fn is_empty(&self) -> bool {
+        self.roms.is_empty()
+            && self.software_roms.is_empty()
+            && self.artwork.is_empty()
+            && self.icons.is_empty()
+    }
+}
+
+/// Changed paths waiting for the folders to go quiet
+#[derive(Debug)]
+struct PendingChanges {
+    paths: BTreeSet<PathBuf>,
+    last_event: Option<Instant>,
+    debounce: Duration,
+}
+
+impl PendingChanges {
+    fn new(debounce: Duration) -> Self {
+        Self {
+            paths: BTreeSet::new(),
+            last_event: None,
+            debounce,
+        }
+    }
+
+    fn record(&mut self, path: PathBuf, at: Instant) {
+        self.paths.insert(path);
+        self.last_event = Some(at);
+    }
+
+    /// Release the collected paths once no event arrived for the debounce period
+    fn take_if_quiet(
+        &mut self,
+        now: Instant,
+        roots: &[(PathBuf, WatchKind)],
+    ) -> Option<WatchBatch> {
+        let last_event = self.last_event?;
+        if now.duration_since(last_event) < self.debounce {
+            return None;
+        }
+        self.last_event = None;
+
+        let mut batch = WatchBatch::default();
+        for path in std::mem::take(&mut self.paths) {
+            for (_, kind) in roots.iter().filter(|(root, _)| path.starts_with(root)) {
+                let group = match kind {
+                    WatchKind::Roms => &mut batch.roms,
+                    WatchKind::SoftwareRoms => &mut batch.software_roms,
+                    WatchKind::Artwork => &mut batch.artwork,
+                    WatchKind::Icons => &mut batch.icons,
+                };
+                if group.last() != Some(&path) {
+                    group.push(path.clone());
+                }
+            }
+        }
+        (!batch.is_empty()).then_some(batch)
+    }
+}
+
+/// Recursive watch over the configured folders
+pub struct FsWatcher {
+    // Dropping the watcher stops the inotify thread
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<PathBuf>,
+    roots: Vec<(PathBuf, WatchKind)>,
+    pending: PendingChanges,
+}
+
+impl FsWatcher {
+    /// Watch the folders from the config. `on_event` runs on the watcher
+    /// thread for every change, e.g. to wake the UI so the batch gets polled.
+    pub fn from_config<F>(config: &AppConfig, on_event: F) -> Result<Self>
+    where
+        F: Fn() + Send + 'static,
+    {
+        Self::new(watch_roots(config), WATCH_DEBOUNCE, on_event)
+    }
+
+    pub fn new<F>(roots: Vec<(PathBuf, WatchKind)>, debounce: Duration, on_event: F) -> Result<Self>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            let event = match result {
+                Ok(event) => event,
+                Err(e) => {
+                    eprintln!("File watcher error: {}", e);
+                    return;
+                }
+            };
+            // Opening and reading files is not a change
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+            for path in event.paths {
+                let _ = sender.send(path);
+            }
+            on_event();
+        })?;
+
+        let mut watched: Vec<(PathBuf, WatchKind)> = Vec::new();
+        for (root, kind) in roots {
+            // One folder can serve several kinds; inotify only needs it once
+            if watched.iter().any(|(other, _)| other == &root) {
+                watched.push((root, kind));
+                continue;
+            }
+            match watcher.watch(&root, RecursiveMode::Recursive) {
+                Ok(()) => watched.push((root, kind)),
+                Err(e) => eprintln!("Not watching {}: {}", root.display(), e),
+            }
+        }
+        println!("Watching {} folders for changes", watched.len());
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            roots: watched,
+            pending: PendingChanges::new(debounce),
+        })
+    }
+
+    /// Collect new events and return a batch once the folders have gone quiet
+    pub fn poll(&mut self) -> Option<WatchBatch> {
+        let now = Instant::now();
+        while let Ok(path) = self.events.try_recv() {
+            self.pending.record(path, now);
+        }
+        self.pending.take_if_quiet(now, &self.roots)
+    }
+
+    /// Time left until the pending batch is released, for repaint scheduling
+    pub fn time_until_ready(&self) -> Option<Duration> {
+        let last_event = self.pending.last_event?;
+        Some(self.pending.debounce.saturating_sub(last_event.elapsed()))
+    }
+}
+
+/// Folders to watch: ROMs, software-list media, artwork and icons. Missing
+/// folders are skipped.
+pub fn watch_roots(config: &AppConfig) -> Vec<(PathBuf, WatchKind)> {
+    let roms = config
+        .rom_paths
+        .iter()
+        .chain(&config.extra_rom_dirs)
+        .map(|path| (path, WatchKind::Roms));
+    let software = config
+        .software_rom_paths
+        .iter()
+        .map(|path| (path, WatchKind::SoftwareRoms));
+    let artwork = [
+        &config.snap_path,
+        &config.cabinet_path,
+        &config.marquee_path,
+        &config.title_path,
+        &config.flyer_path,
+        &config.pcb_path,
+    ]
+    .into_iter()
+    .flatten()
+    .map(|path| (path, WatchKind::Artwork));
+    let icons = config
+        .icons_path
+        .iter()
+        .map(|path| (path, WatchKind::Icons));
+
+    roms.chain(software)
+        .chain(artwork)
+        .chain(icons)
+        .filter(|(path, _)| path.is_dir())
+        .map(|(path, kind)| (path.clone(), kind))
+        .collect()
+}
+
+/// Name a changed artwork or icon file stands for: the file stem, or the
+/// folder name for files in a per-game folder (`snap/<game>/0000.png`)
+pub fn changed_name(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let mut components = relative.components();
+    let first = Path::new(components.next()?.as_os_str());
+    if components.next().is_some() {
+        first.to_str().map(str::to_string)
+    } else {
+        first
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_are_released_after_a_quiet_period_and_grouped_by_kind() {
+        let roots = vec![
+            (PathBuf::from("/roms"), WatchKind::Roms),
+            (PathBuf::from("/art/snap"), WatchKind::Artwork),
+            (PathBuf::from("/art"), WatchKind::Icons),
+        ];
+        let mut pending = PendingChanges::new(Duration::from_millis(100));
+        let start = Instant::now();
+
+        pending.record(PathBuf::from("/roms/pacman.zip"), start);
+        pending.record(PathBuf::from("/roms/pacman.zip"), start);
+        pending.record(
+            PathBuf::from("/art/snap/pacman.png"),
+            start + Duration::from_millis(60),
+        );
+        pending.record(
+            PathBuf::from("/elsewhere/file"),
+            start + Duration::from_millis(60),
+        );
+
+        // Still inside the debounce window of the last event
+        assert!(
+            pending
+                .take_if_quiet(start + Duration::from_millis(120), &roots)
+                .is_none()
+        );
+
+        let batch = pending
+            .take_if_quiet(start + Duration::from_millis(200), &roots)
+            .unwrap();
+        assert_eq!(batch.roms, vec![PathBuf::from("/roms/pacman.zip")]);
+        assert_eq!(batch.artwork, vec![PathBuf::from("/art/snap/pacman.png")]);
+        assert_eq!(batch.icons, vec![PathBuf::from("/art/snap/pacman.png")]);
+        assert!(batch.software_roms.is_empty());
+        assert!(
+            pending
+                .take_if_quiet(start + Duration::from_secs(1), &roots)
+                .is_none()
+        );
+
+        let root = Path::new("/art/snap");
+        assert_eq!(
+            changed_name(root, Path::new("/art/snap/pacman.png")).as_deref(),
+            Some("pacman")
+        );
+        assert_eq!(
+            changed_name(root, Path::new("/art/snap/galaga/0000.png")).as_deref(),
+            Some("galaga")
+        );
+        assert_eq!(changed_name(root, Path::new("/roms/pacman.zip")), None);
+    }
+}
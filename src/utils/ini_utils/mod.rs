@@ -1,4 +1,6 @@
 // src/ini_utils/mod.rs
+pub mod options_ini;
+
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
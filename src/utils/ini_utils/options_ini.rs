@@ -0,0 +1,1319 @@
+// src/utils/ini_utils/options_ini.rs
+//! MAME's own option files in the ini folder: `mame.ini`, `vertical.ini` and
+//! `horizontal.ini`, `source/<driver>.ini` and `<game>.ini`. Reading maps the
+//! options MAMEUIx knows onto the property levels; writing produces files
+//! MAME reads back. Only mame.ini lists every option, the other files keep
+//! just what their level sets, and options MAMEUIx doesn't know (rompath,
+//! plugins, ...) are carried over from the existing file untouched.
+use crate::models::property_layers::{apply_delta, option_value};
+use crate::models::{AppConfig, Game, GameProperties, PropertyDelta};
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Options of one ini file in file order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IniOptions {
+    entries: Vec<(String, String)>,
+}
+
+impl IniOptions {
+    pub fn parse(text: &str) -> Self {
+        let entries = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (key, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(value);
+                (key.to_string(), value.to_string())
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// An empty set when the file doesn't exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self::parse(&text))
+    }
+
+    /// Later lines win, as in MAME
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// How an ini value maps onto the property's serde form
+enum Kind {
+    Bool,
+    Number,
+    Text,
+    /// None is written as this word (`auto`, `none`, or empty)
+    Optional(&'static str),
+    /// Ini value ↔ enum variant
+    Choice(&'static [(&'static str, &'static str)]),
+}
+
+struct IniOption {
+    section: &'static str,
+    key: &'static str,
+    path: &'static str,
+    kind: Kind,
+}
+
+const PERFORMANCE: &str = "CORE PERFORMANCE OPTIONS";
+const ROTATION: &str = "CORE ROTATION OPTIONS";
+const RENDER: &str = "CORE RENDER OPTIONS";
+const SCREEN: &str = "CORE SCREEN OPTIONS";
+const SOUND: &str = "CORE SOUND OPTIONS";
+const INPUT: &str = "CORE INPUT OPTIONS";
+const STATE: &str = "CORE STATE/PLAYBACK OPTIONS";
+const DEBUGGING: &str = "CORE DEBUGGING OPTIONS";
+const MISC: &str = "CORE MISC OPTIONS";
+const OSD_INPUT: &str = "OSD INPUT OPTIONS";
+const OSD_OUTPUT: &str = "OSD OUTPUT OPTIONS";
+const OSD_VIDEO: &str = "OSD VIDEO OPTIONS";
+const OSD_WINDOW: &str = "OSD PER-WINDOW VIDEO OPTIONS";
+const OSD_FULL_SCREEN: &str = "OSD FULL SCREEN OPTIONS";
+const OSD_SOUND: &str = "OSD SOUND OPTIONS";
+const OPENGL: &str = "OSD ACCELERATED VIDEO OPTIONS";
+const BGFX: &str = "BGFX POST-PROCESSING OPTIONS";
+const SDL_PERFORMANCE: &str = "SDL PERFORMANCE OPTIONS";
+const SDL_VIDEO: &str = "SDL VIDEO OPTIONS";
+const SDL_FULL_SCREEN: &str = "SDL FULL SCREEN OPTIONS";
+const SDL_KEYBOARD: &str = "SDL KEYBOARD MAPPING";
+const SDL_INPUT: &str = "SDL INPUT OPTIONS";
+const SDL_LIGHTGUN: &str = "SDL LIGHTGUN MAPPING";
+const SDL_DRIVERS: &str = "SDL LOW-LEVEL DRIVER OPTIONS";
+
+const PROVIDERS: &[(&str, &str)] = &[("auto", "Auto"), ("sdl", "SDL"), ("none", "None")];
+
+macro_rules! option {
+    ($section:expr, $key:literal, $path:literal, $kind:expr) => {
+        IniOption {
+            section: $section,
+            key: $key,
+            path: $path,
+            kind: $kind,
+        }
+    };
+}
+
+/// Options with a one-to-one property, in the order mame.ini lists them
+const OPTIONS: &[IniOption] = &[
+    option!(
+        PERFORMANCE,
+        "autoframeskip",
+        "screen.auto_frameskip",
+        Kind::Bool
+    ),
+    option!(
+        PERFORMANCE,
+        "frameskip",
+        "screen.frameskip_value",
+        Kind::Number
+    ),
+    option!(
+        PERFORMANCE,
+        "seconds_to_run",
+        "screen.seconds_to_run",
+        Kind::Number
+    ),
+    option!(PERFORMANCE, "throttle", "display.throttle", Kind::Bool),
+    option!(PERFORMANCE, "sleep", "screen.sleep_when_idle", Kind::Bool),
+    option!(PERFORMANCE, "speed", "screen.emulation_speed", Kind::Number),
+    option!(
+        PERFORMANCE,
+        "refreshspeed",
+        "screen.refresh_speed",
+        Kind::Bool
+    ),
+    option!(PERFORMANCE, "lowlatency", "screen.low_latency", Kind::Bool),
+    option!(ROTATION, "autoror", "display.auto_rotate_right", Kind::Bool),
+    option!(ROTATION, "autorol", "display.auto_rotate_left", Kind::Bool),
+    option!(
+        RENDER,
+        "keepaspect",
+        "display.enforce_aspect_ratio",
+        Kind::Bool
+    ),
+    option!(
+        RENDER,
+        "unevenstretch",
+        "display.use_non_integer_scaling",
+        Kind::Bool
+    ),
+    option!(
+        RENDER,
+        "unevenstretchx",
+        "display.stretch_only_x_axis",
+        Kind::Bool
+    ),
+    option!(
+        RENDER,
+        "unevenstretchy",
+        "display.stretch_only_y_axis",
+        Kind::Bool
+    ),
+    option!(
+        RENDER,
+        "autostretchxy",
+        "display.auto_select_stretch_axis",
+        Kind::Bool
+    ),
+    option!(
+        RENDER,
+        "intoverscan",
+        "display.overscan_on_targets",
+        Kind::Bool
+    ),
+    option!(
+        RENDER,
+        "intscalex",
+        "display.horizontal_scale_factor",
+        Kind::Number
+    ),
+    option!(
+        RENDER,
+        "intscaley",
+        "display.vertical_scale_factor",
+        Kind::Number
+    ),
+    option!(RENDER, "artwork_crop", "miscellaneous.crop", Kind::Bool),
+    option!(
+        SCREEN,
+        "brightness",
+        "display.brightness_correction",
+        Kind::Number
+    ),
+    option!(
+        SCREEN,
+        "contrast",
+        "display.contrast_correction",
+        Kind::Number
+    ),
+    option!(SCREEN, "gamma", "display.gamma_correction", Kind::Number),
+    option!(
+        SCREEN,
+        "pause_brightness",
+        "display.pause_brightness",
+        Kind::Number
+    ),
+    option!(SCREEN, "effect", "screen.effect", Kind::Optional("none")),
+    option!(SOUND, "samplerate", "sound.sample_rate", Kind::Number),
+    option!(SOUND, "samples", "sound.use_samples", Kind::Bool),
+    option!(SOUND, "volume", "sound.volume_attenuation", Kind::Number),
+    option!(INPUT, "steadykey", "miscellaneous.steady_key", Kind::Bool),
+    option!(INPUT, "ui_active", "miscellaneous.ui_active", Kind::Bool),
+    option!(
+        INPUT,
+        "offscreen_reload",
+        "miscellaneous.offscreen_reload",
+        Kind::Bool
+    ),
+    option!(
+        INPUT,
+        "joystick_contradictory",
+        "miscellaneous.contradictory",
+        Kind::Bool
+    ),
+    option!(INPUT, "natural", "miscellaneous.natural", Kind::Bool),
+    option!(
+        INPUT,
+        "multikeyboard",
+        "miscellaneous.multi_keyboard",
+        Kind::Bool
+    ),
+    option!(INPUT, "multimouse", "miscellaneous.multi_mouse", Kind::Bool),
+    option!(STATE, "autosave", "miscellaneous.auto_save", Kind::Bool),
+    option!(STATE, "rewind", "miscellaneous.rewind", Kind::Bool),
+    option!(
+        STATE,
+        "exit_after_playback",
+        "miscellaneous.exit_after",
+        Kind::Bool
+    ),
+    option!(STATE, "burnin", "miscellaneous.burnin", Kind::Bool),
+    option!(DEBUGGING, "verbose", "miscellaneous.verbose", Kind::Bool),
+    option!(DEBUGGING, "log", "miscellaneous.log", Kind::Bool),
+    option!(DEBUGGING, "oslog", "miscellaneous.oslog", Kind::Bool),
+    option!(DEBUGGING, "debug", "miscellaneous.debug", Kind::Bool),
+    option!(
+        DEBUGGING,
+        "update_in_pause",
+        "miscellaneous.update_pause",
+        Kind::Bool
+    ),
+    option!(DEBUGGING, "debuglog", "miscellaneous.debuglog", Kind::Bool),
+    option!(DEBUGGING, "drc_use_c", "miscellaneous.drc_c", Kind::Bool),
+    option!(
+        DEBUGGING,
+        "drc_log_uml",
+        "miscellaneous.log_uml",
+        Kind::Bool
+    ),
+    option!(
+        DEBUGGING,
+        "drc_log_native",
+        "miscellaneous.log_native",
+        Kind::Bool
+    ),
+    option!(
+        MISC,
+        "writeconfig",
+        "miscellaneous.write_config",
+        Kind::Bool
+    ),
+    option!(MISC, "cheat", "miscellaneous.cheat", Kind::Bool),
+    option!(MISC, "skip_gameinfo", "miscellaneous.skip", Kind::Bool),
+    option!(MISC, "confirm_quit", "miscellaneous.confirm", Kind::Bool),
+    option!(MISC, "console", "miscellaneous.console", Kind::Bool),
+    option!(
+        OSD_INPUT,
+        "uimodekey",
+        "osd_options.ui_mode_key",
+        Kind::Optional("auto")
+    ),
+    option!(
+        OSD_INPUT,
+        "controller_map",
+        "osd_options.controller_map_file",
+        Kind::Optional("none")
+    ),
+    option!(
+        OSD_INPUT,
+        "background_input",
+        "osd_options.background_input",
+        Kind::Bool
+    ),
+    option!(
+        OSD_INPUT,
+        "uifontprovider",
+        "osd_options.ui_font_provider",
+        Kind::Choice(PROVIDERS)
+    ),
+    option!(
+        OSD_INPUT,
+        "keyboardprovider",
+        "osd_options.keyboard_provider",
+        Kind::Choice(PROVIDERS)
+    ),
+    option!(
+        OSD_INPUT,
+        "mouseprovider",
+        "osd_options.mouse_provider",
+        Kind::Choice(PROVIDERS)
+    ),
+    option!(
+        OSD_INPUT,
+        "lightgunprovider",
+        "osd_options.lightgun_provider",
+        Kind::Choice(&[
+            ("auto", "Auto"),
+            ("sdl", "SDL"),
+            ("x11", "X11"),
+            ("none", "None")
+        ])
+    ),
+    option!(
+        OSD_INPUT,
+        "joystickprovider",
+        "osd_options.joystick_provider",
+        Kind::Choice(&[
+            ("auto", "Auto"),
+            ("sdlgame", "SDLGame"),
+            ("sdljoy", "SDLJoy"),
+            ("none", "None"),
+        ])
+    ),
+    option!(
+        OSD_OUTPUT,
+        "output",
+        "osd_options.output_provider",
+        Kind::Choice(&[
+            ("none", "None"),
+            ("console", "Console"),
+            ("network", "Network")
+        ])
+    ),
+    option!(
+        OSD_VIDEO,
+        "video",
+        "display.video_mode",
+        Kind::Choice(&[
+            ("auto", "Auto"),
+            ("opengl", "OpenGL"),
+            ("d3d", "Direct3D"),
+            ("soft", "Software"),
+            ("bgfx", "BGFX"),
+        ])
+    ),
+    option!(
+        OSD_VIDEO,
+        "numscreens",
+        "miscellaneous.num_screens",
+        Kind::Number
+    ),
+    option!(OSD_VIDEO, "window", "display.run_in_window", Kind::Bool),
+    option!(
+        OSD_VIDEO,
+        "maximize",
+        "display.start_out_maximized",
+        Kind::Bool
+    ),
+    option!(
+        OSD_VIDEO,
+        "waitvsync",
+        "screen.wait_for_vertical_sync",
+        Kind::Bool
+    ),
+    option!(
+        OSD_VIDEO,
+        "syncrefresh",
+        "screen.sync_to_monitor_refresh",
+        Kind::Bool
+    ),
+    option!(
+        OSD_VIDEO,
+        "prescale",
+        "display.bitmap_prescaling",
+        Kind::Number
+    ),
+    option!(
+        OSD_VIDEO,
+        "filter",
+        "display.use_bilinear_filtering",
+        Kind::Bool
+    ),
+    option!(
+        OSD_VIDEO,
+        "numprocessors",
+        "miscellaneous.num_processors",
+        Kind::Optional("auto")
+    ),
+    option!(
+        OSD_FULL_SCREEN,
+        "switchres",
+        "miscellaneous.switchres",
+        Kind::Bool
+    ),
+    option!(
+        OSD_FULL_SCREEN,
+        "triplebuffer",
+        "screen.triple_buffering",
+        Kind::Bool
+    ),
+    option!(
+        OSD_FULL_SCREEN,
+        "full_screen_brightness",
+        "screen.full_screen_brightness",
+        Kind::Number
+    ),
+    option!(
+        OSD_FULL_SCREEN,
+        "full_screen_contrast",
+        "screen.full_screen_contrast",
+        Kind::Number
+    ),
+    option!(
+        OSD_FULL_SCREEN,
+        "full_screen_gamma",
+        "screen.full_screen_gamma",
+        Kind::Number
+    ),
+    option!(
+        OSD_SOUND,
+        "sound",
+        "sound.sound_mode",
+        Kind::Choice(&[
+            ("auto", "Auto"),
+            ("sdl", "SDL"),
+            ("portaudio", "PortAudio"),
+            ("pulse", "PulseAudio"),
+            ("none", "None"),
+        ])
+    ),
+    option!(
+        OSD_SOUND,
+        "audio_latency",
+        "sound.audio_latency",
+        Kind::Number
+    ),
+    option!(
+        OPENGL,
+        "gl_forcepow2texture",
+        "advanced.force_power_of_two_textures",
+        Kind::Bool
+    ),
+    option!(
+        OPENGL,
+        "gl_notexturerect",
+        "advanced.dont_use_gl_arb_texture_rectangle",
+        Kind::Bool
+    ),
+    option!(OPENGL, "gl_vbo", "advanced.enable_vbo", Kind::Bool),
+    option!(OPENGL, "gl_pbo", "advanced.enable_pbo", Kind::Bool),
+    option!(OPENGL, "gl_glsl", "advanced.enable_glsl", Kind::Bool),
+    option!(
+        OPENGL,
+        "gl_glsl_filter",
+        "advanced.glsl_filter",
+        Kind::Choice(&[("0", "Plain"), ("1", "Bilinear"), ("2", "Bicubic")])
+    ),
+    option!(
+        BGFX,
+        "bgfx_backend",
+        "advanced.bgfx_settings.backend",
+        Kind::Choice(&[
+            ("auto", "Auto"),
+            ("d3d9", "D3D9"),
+            ("d3d11", "D3D11"),
+            ("d3d12", "D3D12"),
+            ("opengl", "OpenGL"),
+            ("metal", "Metal"),
+            ("vulkan", "Vulkan"),
+        ])
+    ),
+    option!(
+        BGFX,
+        "bgfx_debug",
+        "advanced.bgfx_settings.enable_debug",
+        Kind::Bool
+    ),
+    option!(
+        BGFX,
+        "bgfx_screen_chains",
+        "advanced.bgfx_settings.screen_chains",
+        Kind::Text
+    ),
+    option!(
+        BGFX,
+        "bgfx_shadow_mask",
+        "advanced.bgfx_settings.shadow_mask",
+        Kind::Optional("")
+    ),
+    option!(
+        BGFX,
+        "bgfx_lut",
+        "advanced.bgfx_settings.lut_texture",
+        Kind::Optional("")
+    ),
+    option!(
+        SDL_PERFORMANCE,
+        "sdlvideofps",
+        "sdl_options.show_video_fps",
+        Kind::Bool
+    ),
+    option!(
+        SDL_VIDEO,
+        "centerh",
+        "sdl_options.center_horizontal",
+        Kind::Bool
+    ),
+    option!(
+        SDL_VIDEO,
+        "centerv",
+        "sdl_options.center_vertical",
+        Kind::Bool
+    ),
+    option!(
+        SDL_VIDEO,
+        "scalemode",
+        "sdl_options.scale_mode",
+        Kind::Choice(&[
+            ("none", "None"),
+            ("hwblit", "HWBlit"),
+            ("hwbest", "HWBest"),
+            ("yv12", "YV12"),
+            ("yuy2", "YUY2"),
+            ("yv12x2", "YV12x2"),
+            ("yuy2x2", "YUY2x2"),
+        ])
+    ),
+    option!(
+        SDL_FULL_SCREEN,
+        "useallheads",
+        "sdl_options.use_all_heads",
+        Kind::Bool
+    ),
+    option!(
+        SDL_FULL_SCREEN,
+        "attach_window",
+        "sdl_options.attach_window",
+        Kind::Optional("")
+    ),
+    option!(
+        SDL_KEYBOARD,
+        "keymap",
+        "sdl_options.enable_keymap",
+        Kind::Bool
+    ),
+    option!(
+        SDL_KEYBOARD,
+        "keymap_file",
+        "sdl_options.keymap_file",
+        Kind::Optional("")
+    ),
+    option!(
+        SDL_INPUT,
+        "enable_touch",
+        "sdl_options.enable_touch",
+        Kind::Bool
+    ),
+    option!(
+        SDL_INPUT,
+        "sixaxis",
+        "sdl_options.sixaxis_support",
+        Kind::Bool
+    ),
+    option!(
+        SDL_INPUT,
+        "dual_lightgun",
+        "sdl_options.dual_lightgun",
+        Kind::Bool
+    ),
+    option!(
+        SDL_DRIVERS,
+        "videodriver",
+        "sdl_options.video_driver",
+        Kind::Optional("auto")
+    ),
+    option!(
+        SDL_DRIVERS,
+        "renderdriver",
+        "sdl_options.render_driver",
+        Kind::Optional("auto")
+    ),
+    option!(
+        SDL_DRIVERS,
+        "audiodriver",
+        "sdl_options.audio_driver",
+        Kind::Optional("auto")
+    ),
+    option!(
+        SDL_DRIVERS,
+        "gl_lib",
+        "sdl_options.gl_lib",
+        Kind::Optional("auto")
+    ),
+];
+
+/// Numbered options kept in one list property: prefix, path, first number,
+/// slot count and the word MAME uses for an empty slot
+const SLOTS: &[(&str, &str, usize, usize, &str)] = &[
+    (
+        "glsl_shader_mame",
+        "advanced.glsl_shader_mame",
+        0,
+        10,
+        "none",
+    ),
+    (
+        "glsl_shader_screen",
+        "advanced.glsl_shader_screen",
+        0,
+        10,
+        "none",
+    ),
+    (
+        "lightgun_index",
+        "sdl_options.lightgun_mappings",
+        1,
+        8,
+        "auto",
+    ),
+];
+
+/// One `key value` line and the properties it comes from
+struct Line {
+    section: &'static str,
+    key: String,
+    value: String,
+    paths: Vec<&'static str>,
+}
+
+impl Line {
+    fn new(section: &'static str, key: &str, value: String, paths: &[&'static str]) -> Self {
+        Self {
+            section,
+            key: key.to_string(),
+            value,
+            paths: paths.to_vec(),
+        }
+    }
+}
+
+/// Options of an ini file as a delta, with the keys MAMEUIx has no
+/// property for
+pub fn read_delta(options: &IniOptions) -> (PropertyDelta, Vec<String>) {
+    let mut delta = PropertyDelta::new();
+    let mut set = |path: &str, value: Value| {
+        delta.insert(path.to_string(), value.to_string());
+    };
+
+    for option in OPTIONS {
+        if let Some(value) = options
+            .get(option.key)
+            .and_then(|value| import_value(&option.kind, value))
+        {
+            set(option.path, value);
+        }
+    }
+
+    // Rotation and flips; 180° has no option of its own and reads back as flips
+    let flag = |key: &str| options.get(key).map(|value| value == "1");
+    if let Some(ror) = flag("ror") {
+        let rotation = match (ror, flag("rol") == Some(true)) {
+            (true, _) => "Rotate90",
+            (false, true) => "Rotate270",
+            (false, false) => "Default",
+        };
+        set("display.rotation", Value::from(rotation));
+    } else if flag("rol") == Some(true) {
+        set("display.rotation", Value::from("Rotate270"));
+    }
+    if let Some(flipx) = flag("flipx") {
+        set("display.flip_screen_left_right", Value::from(flipx));
+    }
+    if let Some(flipy) = flag("flipy") {
+        set("display.flip_screen_upside_down", Value::from(flipy));
+    }
+
+    if let Some(aspect) = options.get("aspect") {
+        let ratio = aspect
+            .split_once(':')
+            .and_then(|(x, y)| Some((x.parse::<u8>().ok()?, y.parse::<u8>().ok()?)));
+        set(
+            "miscellaneous.autoselect_aspect",
+            Value::from(ratio.is_none()),
+        );
+        if let Some((x, y)) = ratio {
+            set("miscellaneous.aspect_ratio", serde_json::json!([x, y]));
+        }
+    }
+    if let Some(resolution) = options.get("resolution") {
+        let (size, refresh) = match resolution.split_once('@') {
+            Some((size, refresh)) => (size, refresh.parse::<u32>().ok().filter(|r| *r > 0)),
+            None => (resolution, None),
+        };
+        let size = size
+            .split_once('x')
+            .and_then(|(w, h)| Some((w.parse::<u32>().ok()?, h.parse::<u32>().ok()?)))
+            .filter(|(w, h)| *w > 0 && *h > 0);
+        set(
+            "miscellaneous.resolution",
+            match (size, refresh) {
+                (Some((w, h)), Some(r)) => serde_json::json!({ "CustomRefresh": [w, h, r] }),
+                (Some((w, h)), None) => serde_json::json!({ "Custom": [w, h] }),
+                (None, _) => Value::from("Auto"),
+            },
+        );
+    }
+    if let Some(screen) = options.get("screen") {
+        set(
+            "miscellaneous.screen_number",
+            match screen.parse::<u8>() {
+                Ok(n) => serde_json::json!({ "Screen": n }),
+                Err(_) => Value::from("Default"),
+            },
+        );
+    }
+    if let Some(view) = options.get("view") {
+        set(
+            "miscellaneous.view",
+            if view.is_empty() || view == "auto" {
+                Value::from("Auto")
+            } else {
+                serde_json::json!({ "Custom": view })
+            },
+        );
+    }
+
+    // Slots start from the empty defaults so one file never inherits another's
+    let defaults = GameProperties::default();
+    for (prefix, path, first, count, empty) in SLOTS {
+        let mut slots: Vec<String> = option_value(&defaults, path)
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+        slots.resize(*count, String::new());
+        let mut found = false;
+        for (slot, slot_value) in slots.iter_mut().enumerate() {
+            if let Some(ini) = options.get(&format!("{}{}", prefix, first + slot)) {
+                *slot_value = if ini == *empty {
+                    String::new()
+                } else {
+                    ini.to_string()
+                };
+                found = true;
+            }
+        }
+        if found {
+            set(path, Value::from(slots));
+        }
+    }
+
+    let known: HashSet<String> = lines(&defaults).into_iter().map(|line| line.key).collect();
+    let unknown = options
+        .entries
+        .iter()
+        .map(|(key, _)| key.clone())
+        .filter(|key| !known.contains(key))
+        .collect();
+    (delta, unknown)
+}
+
+/// Every option MAMEUIx can write, grouped in mame.ini's order
+fn lines(properties: &GameProperties) -> Vec<Line> {
+    let mut lines: Vec<Line> = OPTIONS
+        .iter()
+        .filter_map(|option| {
+            let value = export_value(&option.kind, &option_value(properties, option.path)?)?;
+            Some(Line::new(option.section, option.key, value, &[option.path]))
+        })
+        .collect();
+
+    let display = &properties.display;
+    let misc = &properties.miscellaneous;
+    let half_turn = matches!(display.rotation, crate::models::RotationMode::Rotate180);
+    let flag = |on: bool| if on { "1" } else { "0" }.to_string();
+    let rotation = ["display.rotation"];
+    lines.extend([
+        Line::new(
+            ROTATION,
+            "ror",
+            flag(matches!(
+                display.rotation,
+                crate::models::RotationMode::Rotate90
+            )),
+            &rotation,
+        ),
+        Line::new(
+            ROTATION,
+            "rol",
+            flag(matches!(
+                display.rotation,
+                crate::models::RotationMode::Rotate270
+            )),
+            &rotation,
+        ),
+        Line::new(
+            ROTATION,
+            "flipx",
+            flag(display.flip_screen_left_right != half_turn),
+            &["display.flip_screen_left_right", "display.rotation"],
+        ),
+        Line::new(
+            ROTATION,
+            "flipy",
+            flag(display.flip_screen_upside_down != half_turn),
+            &["display.flip_screen_upside_down", "display.rotation"],
+        ),
+        Line::new(
+            OSD_WINDOW,
+            "screen",
+            match misc.screen_number {
+                crate::models::ScreenSelection::Screen(n) => n.to_string(),
+                crate::models::ScreenSelection::Default => "auto".to_string(),
+            },
+            &["miscellaneous.screen_number"],
+        ),
+        Line::new(
+            OSD_WINDOW,
+            "aspect",
+            if misc.autoselect_aspect {
+                "auto".to_string()
+            } else {
+                format!("{}:{}", misc.aspect_ratio.0, misc.aspect_ratio.1)
+            },
+            &[
+                "miscellaneous.autoselect_aspect",
+                "miscellaneous.aspect_ratio",
+            ],
+        ),
+        Line::new(
+            OSD_WINDOW,
+            "resolution",
+            match misc.resolution {
+                crate::models::Resolution::Custom(w, h) => format!("{}x{}", w, h),
+                crate::models::Resolution::CustomRefresh(w, h, r) => format!("{}x{}@{}", w, h, r),
+                crate::models::Resolution::Auto => "auto".to_string(),
+            },
+            &["miscellaneous.resolution"],
+        ),
+        Line::new(
+            OSD_WINDOW,
+            "view",
+            match &misc.view {
+                crate::models::ViewSelection::Custom(view) => view.clone(),
+                crate::models::ViewSelection::Auto => "auto".to_string(),
+            },
+            &["miscellaneous.view"],
+        ),
+    ]);
+
+    for (prefix, path, first, count, empty) in SLOTS {
+        let slots: Vec<String> = option_value(properties, path)
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+        let section = if path.starts_with("sdl_options") {
+            SDL_LIGHTGUN
+        } else {
+            OPENGL
+        };
+        for slot in 0..*count {
+            let value = slots
+                .get(slot)
+                .filter(|value| !value.is_empty())
+                .cloned()
+                .unwrap_or_else(|| empty.to_string());
+            lines.push(Line::new(
+                section,
+                &format!("{}{}", prefix, first + slot),
+                value,
+                &[path],
+            ));
+        }
+    }
+
+    // Keep mame.ini's section order
+    let order: Vec<&str> = OPTIONS.iter().map(|option| option.section).collect();
+    lines.sort_by_key(|line| {
+        order
+            .iter()
+            .position(|section| *section == line.section)
+            .unwrap_or(usize::MAX)
+    });
+    lines
+}
+
+/// Ini text `render` produced
+pub struct Rendered {
+    pub text: String,
+    /// MAMEUIx option lines written
+    pub options: usize,
+    /// Paths of the delta no ini option carries
+    pub unwritten: Vec<String>,
+}
+
+/// Ini text for `properties`. With `only`, just the options that delta sets
+/// are written; options of `existing` MAMEUIx doesn't know are kept.
+pub fn render(
+    properties: &GameProperties,
+    only: Option<&PropertyDelta>,
+    existing: &IniOptions,
+) -> Rendered {
+    let all = lines(properties);
+    let known: HashSet<&str> = all.iter().map(|line| line.key.as_str()).collect();
+    let kept: Vec<&(String, String)> = existing
+        .entries
+        .iter()
+        .filter(|(key, _)| !known.contains(key.as_str()))
+        .collect();
+
+    let mut text = String::new();
+    let mut section = "";
+    let mut options = 0;
+    for line in all.iter().filter(|line| {
+        only.is_none_or(|delta| line.paths.iter().any(|path| delta.contains_key(*path)))
+    }) {
+        options += 1;
+        if line.section != section {
+            section = line.section;
+            push_header(&mut text, section);
+        }
+        push_line(&mut text, &line.key, &line.value);
+    }
+    if !kept.is_empty() {
+        push_header(&mut text, "OTHER OPTIONS");
+        for (key, value) in kept {
+            push_line(&mut text, key, value);
+        }
+    }
+    let written: HashSet<&str> = all.iter().flat_map(|line| line.paths.clone()).collect();
+    let unwritten = only
+        .into_iter()
+        .flat_map(|delta| delta.keys())
+        .filter(|path| !written.contains(path.as_str()))
+        .cloned()
+        .collect();
+    Rendered {
+        text,
+        options,
+        unwritten,
+    }
+}
+
+fn push_header(text: &mut String, section: &str) {
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(&format!("#\n# {}\n#\n", section));
+}
+
+fn push_line(text: &mut String, key: &str, value: &str) {
+    if value.contains(char::is_whitespace) {
+        text.push_str(&format!("{:<25} \"{}\"\n", key, value));
+    } else {
+        text.push_str(&format!("{:<25} {}\n", key, value));
+    }
+}
+
+fn import_value(kind: &Kind, value: &str) -> Option<Value> {
+    match kind {
+        Kind::Bool => match value {
+            "1" => Some(Value::from(true)),
+            "0" => Some(Value::from(false)),
+            _ => None,
+        },
+        Kind::Number => value
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| value.parse::<f64>().map(Value::from))
+            .ok(),
+        Kind::Text => Some(Value::from(value)),
+        Kind::Optional(none) if value.is_empty() || value.eq_ignore_ascii_case(none) => {
+            Some(Value::Null)
+        }
+        Kind::Optional(_) => Some(
+            value
+                .parse::<u64>()
+                .map(Value::from)
+                .unwrap_or_else(|_| Value::from(value)),
+        ),
+        Kind::Choice(choices) => choices
+            .iter()
+            .find(|(ini, _)| ini.eq_ignore_ascii_case(value))
+            .map(|(_, variant)| Value::from(*variant)),
+    }
+}
+
+fn export_value(kind: &Kind, value: &Value) -> Option<String> {
+    match (kind, value) {
+        (Kind::Bool, Value::Bool(on)) => Some(if *on { "1" } else { "0" }.to_string()),
+        (Kind::Number, Value::Number(number)) => Some(match number.as_i64() {
+            Some(integer) => integer.to_string(),
+            // f32 fields come back as e.g. 1.100000023841858
+            None => {
+                let text = format!("{:.6}", number.as_f64()?);
+                text.trim_end_matches('0').trim_end_matches('.').to_string()
+            }
+        }),
+        (Kind::Text, Value::String(text)) => Some(text.clone()),
+        (Kind::Optional(none), Value::Null) => Some(none.to_string()),
+        (Kind::Optional(_), Value::String(text)) => Some(text.clone()),
+        (Kind::Optional(_), Value::Number(number)) => Some(number.to_string()),
+        (Kind::Choice(choices), Value::String(variant)) => choices
+            .iter()
+            .find(|(_, v)| v == variant)
+            .map(|(ini, _)| ini.to_string()),
+        _ => None,
+    }
+}
+
+/// What an import or export touched
+#[derive(Debug, Default)]
+pub struct IniSyncSummary {
+    pub files: usize,
+    pub options: usize,
+    /// Option names MAMEUIx left alone
+    pub skipped: BTreeSet<String>,
+    /// Stored settings no ini option carries, so not exported
+    pub unwritten: BTreeSet<String>,
+    /// Existing files copied to `.bak` before being overwritten
+    pub backups: usize,
+}
+
+impl IniSyncSummary {
+    pub fn describe(&self) -> String {
+        let mut text = format!("{} options in {} ini files", self.options, self.files);
+        if !self.skipped.is_empty() {
+            text.push_str(&format!(
+                ", {} options without a MAMEUIx setting left alone",
+                self.skipped.len()
+            ));
+        }
+        if !self.unwritten.is_empty() {
+            text.push_str(&format!(
+                ", {} settings without an ini option not written ({})",
+                self.unwritten.len(),
+                self.unwritten
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        if self.backups > 0 {
+            text.push_str(&format!(", {} previous files kept as .bak", self.backups));
+        }
+        text
+    }
+}
+
+/// Read the ini folder into the default properties and the orientation,
+/// source and game levels. Game files are only taken for known machines,
+/// so catver.ini and friends are ignored.
+pub fn import_ini_folder(
+    dir: &Path,
+    config: &mut AppConfig,
+    games: &[Game],
+) -> Result<IniSyncSummary> {
+    if !dir.is_dir() {
+        bail!("INI folder {} does not exist", dir.display());
+    }
+    let mut summary = IniSyncSummary::default();
+    let mut read = |path: &Path| -> Result<PropertyDelta> {
+        let (delta, unknown) = read_delta(&IniOptions::load(path)?);
+        summary.files += 1;
+        summary.options += delta.len();
+        summary.skipped.extend(unknown);
+        Ok(delta)
+    };
+
+    let mame_ini = dir.join("mame.ini");
+    if mame_ini.exists() {
+        let delta = read(&mame_ini)?;
+        config.default_game_properties = apply_delta(&config.default_game_properties, &delta);
+    }
+    let layers = &mut config.property_layers;
+    for (file, delta) in [
+        ("vertical.ini", &mut layers.vertical),
+        ("horizontal.ini", &mut layers.horizontal),
+    ] {
+        let path = dir.join(file);
+        if path.exists() {
+            delta.extend(read(&path)?);
+        }
+    }
+
+    for (path, stem) in ini_files(&dir.join("source"))? {
+        let delta = read(&path)?;
+        layers.source.entry(stem).or_default().extend(delta);
+    }
+    let names: HashSet<&str> = games.iter().map(|game| game.name.as_str()).collect();
+    for (path, stem) in ini_files(dir)? {
+        if names.contains(stem.as_str()) {
+            let delta = read(&path)?;
+            layers.game.entry(stem).or_default().extend(delta);
+        }
+    }
+    Ok(summary)
+}
+
+/// Files `export_ini_folder` writes: mame.ini with the defaults, then one
+/// file per stored level
+fn export_files<'a>(
+    dir: &Path,
+    config: &'a AppConfig,
+) -> Vec<(std::path::PathBuf, Option<&'a PropertyDelta>)> {
+    let layers = &config.property_layers;
+    let mut files: Vec<(std::path::PathBuf, Option<&PropertyDelta>)> =
+        vec![(dir.join("mame.ini"), None)];
+    for (file, delta) in [
+        ("vertical.ini", &layers.vertical),
+        ("horizontal.ini", &layers.horizontal),
+    ] {
+        if !delta.is_empty() {
+            files.push((dir.join(file), Some(delta)));
+        }
+    }
+    for (stem, delta) in &layers.source {
+        files.push((
+            dir.join("source").join(format!("{}.ini", stem)),
+            Some(delta),
+        ));
+    }
+    for (name, delta) in &layers.game {
+        files.push((dir.join(format!("{}.ini", name)), Some(delta)));
+    }
+    files
+}
+
+/// Existing files an export would overwrite, to confirm first
+pub fn export_overwrites(dir: &Path, config: &AppConfig) -> Vec<std::path::PathBuf> {
+    export_files(dir, config)
+        .into_iter()
+        .map(|(path, _)| path)
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Write mame.ini and one file per stored level into the ini folder.
+/// Files already there are copied to `<name>.ini.bak` first, unless an
+/// earlier export left one: that keeps the user's own file, not ours.
+pub fn export_ini_folder(dir: &Path, config: &AppConfig) -> Result<IniSyncSummary> {
+    let defaults = &config.default_game_properties;
+    let mut summary = IniSyncSummary::default();
+    for (path, delta) in export_files(dir, config) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let backup = path.with_extension("ini.bak");
+        if path.is_file() && !backup.exists() {
+            fs::copy(&path, &backup)
+                .with_context(|| format!("Failed to back up {}", path.display()))?;
+            summary.backups += 1;
+        }
+        let properties = match delta {
+            Some(delta) => apply_delta(defaults, delta),
+            None => defaults.clone(),
+        };
+        let rendered = render(&properties, delta, &IniOptions::load(&path)?);
+        fs::write(&path, rendered.text)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        summary.files += 1;
+        summary.options += rendered.options;
+        summary.unwritten.extend(rendered.unwritten);
+    }
+    Ok(summary)
+}
+
+/// `*.ini` files directly in a folder, with their stems
+fn ini_files(dir: &Path) -> Result<Vec<(std::path::PathBuf, String)>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("ini"))
+            && path.is_file()
+            && let Some(stem) = path.file_stem()
+        {
+            files.push((path.clone(), stem.to_string_lossy().into_owned()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{RomStatus, RotationMode, VideoMode};
+
+    fn game(name: &str) -> Game {
+        Game {
+            name: name.to_string(),
+            description: name.to_string(),
+            status: RomStatus::Available,
+            ..Game::default()
+        }
+    }
+
+    #[test]
+    fn imports_and_exports_the_ini_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("mame.ini"),
+            "#\n# CORE SEARCH PATH OPTIONS\n#\n\
+             rompath                   \"/home/me/mame roms\"\n\
+             window                    0\n\
+             video                     bgfx\n\
+             gamma                     1.2\n\
+             aspect                    16:9\n\
+             lightgun_index2           \"Wiimote 1\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pacman.ini"),
+            "ror 1\nresolution 640x480@60\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("catver.ini"), "[Category]\npacman=Maze\n").unwrap();
+        fs::create_dir(dir.path().join("source")).unwrap();
+        fs::write(
+            dir.path().join("source").join("cps1.ini"),
+            "samplerate 44100\n",
+        )
+        .unwrap();
+
+        let mut config = AppConfig::default();
+        let summary =
+            import_ini_folder(dir.path(), &mut config, &[game("pacman"), game("sf2")]).unwrap();
+        assert_eq!(summary.files, 3);
+        assert!(summary.skipped.contains("rompath"));
+
+        let defaults = &config.default_game_properties;
+        assert!(!defaults.display.run_in_window);
+        assert_eq!(defaults.display.video_mode, VideoMode::BGFX);
+        assert!((defaults.display.gamma_correction - 1.2).abs() < 1e-6);
+        assert!(!defaults.miscellaneous.autoselect_aspect);
+        assert_eq!(defaults.miscellaneous.aspect_ratio, (16, 9));
+        assert_eq!(defaults.sdl_options.lightgun_mappings[1], "Wiimote 1");
+        assert_eq!(config.property_layers.game["pacman"].len(), 2);
+        assert_eq!(
+            config.property_layers.source["cps1"]["sound.sample_rate"],
+            "44100"
+        );
+        let pacman = apply_delta(defaults, &config.property_layers.game["pacman"]);
+        assert_eq!(pacman.display.rotation, RotationMode::Rotate90);
+
+        config
+            .property_layers
+            .game
+            .get_mut("pacman")
+            .unwrap()
+            .insert(
+                "miscellaneous.custom_args".to_string(),
+                "-nothrottle".to_string(),
+            );
+        let original = fs::read_to_string(dir.path().join("mame.ini")).unwrap();
+        assert!(export_overwrites(dir.path(), &config).contains(&dir.path().join("mame.ini")));
+        let summary = export_ini_folder(dir.path(), &config).unwrap();
+        assert!(summary.backups > 0);
+        assert!(summary.unwritten.contains("miscellaneous.custom_args"));
+        let mame_options = render(
+            &config.default_game_properties,
+            None,
+            &IniOptions::default(),
+        )
+        .options;
+        // mame.ini, the rotation lines and resolution for pacman, samplerate for cps1
+        assert_eq!(summary.options, mame_options + 6);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("mame.ini.bak")).unwrap(),
+            original
+        );
+        // A second export keeps the user's file, not the first export
+        export_ini_folder(dir.path(), &config).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("mame.ini.bak")).unwrap(),
+            original
+        );
+        let mame_ini = fs::read_to_string(dir.path().join("mame.ini")).unwrap();
+        assert!(mame_ini.contains("# OSD VIDEO OPTIONS"));
+        assert!(mame_ini.contains("rompath                   \"/home/me/mame roms\""));
+        let options = IniOptions::parse(&mame_ini);
+        assert_eq!(options.get("gamma"), Some("1.2"));
+        assert_eq!(options.get("video"), Some("bgfx"));
+        assert_eq!(options.get("lightgun_index1"), Some("auto"));
+
+        // Level files stay sparse
+        let pacman_ini =
+            IniOptions::parse(&fs::read_to_string(dir.path().join("pacman.ini")).unwrap());
+        assert_eq!(pacman_ini.get("ror"), Some("1"));
+        assert_eq!(pacman_ini.get("resolution"), Some("640x480@60"));
+        assert_eq!(pacman_ini.get("window"), None);
+        let cps1 = fs::read_to_string(dir.path().join("source").join("cps1.ini")).unwrap();
+        assert_eq!(IniOptions::parse(&cps1).entries.len(), 1);
+    }
+}
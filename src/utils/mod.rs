@@ -1,7 +1,10 @@
 // src/utils/mod.rs
 // Utility functions and helpers
 
+pub mod archive;
+pub mod chd;
 pub mod enhanced_search;
+pub mod fs_watcher;
 pub mod graphics;
 pub mod hardware_filter;
 pub mod ini_utils;
@@ -0,0 +1,407 @@
+// src/utils/rom_utils/audit_export.rs
+//! Machine-readable audit results. The JSON layout is a stable, versioned
+//! schema so exports from different cabinets can be compared; it only uses
+//! its own snake_case names and never the in-app types' serde forms.
+use crate::ui::components::rom_verify::{
+    FileCheck, FileCheckStatus, VerificationResult, VerifyStatus,
+};
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+pub const AUDIT_SCHEMA: &str = "mameuix-audit";
+
+/// Bump when fields change meaning; adding optional fields keeps the version
+pub const AUDIT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStatus {
+    Passed,
+    Warning,
+    Failed,
+    NotFound,
+}
+
+impl AuditStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            AuditStatus::Passed => "Passed",
+            AuditStatus::Warning => "Warning",
+            AuditStatus::Failed => "Failed",
+            AuditStatus::NotFound => "Not found",
+        }
+    }
+
+    /// Passed and warning sets run; the rest don't
+    pub fn is_playable(self) -> bool {
+        matches!(self, AuditStatus::Passed | AuditStatus::Warning)
+    }
+}
+
+impl From<&VerifyStatus> for AuditStatus {
+    fn from(status: &VerifyStatus) -> Self {
+        match status {
+            VerifyStatus::Passed => AuditStatus::Passed,
+            VerifyStatus::Warning => AuditStatus::Warning,
+            VerifyStatus::Failed => AuditStatus::Failed,
+            VerifyStatus::NotFound => AuditStatus::NotFound,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAuditStatus {
+    Good,
+    Incorrect,
+    Missing,
+    NoDump,
+}
+
+impl FileAuditStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            FileAuditStatus::Good => "good",
+            FileAuditStatus::Incorrect => "incorrect",
+            FileAuditStatus::Missing => "missing",
+            FileAuditStatus::NoDump => "no dump",
+        }
+    }
+}
+
+impl From<FileCheckStatus> for FileAuditStatus {
+    fn from(status: FileCheckStatus) -> Self {
+        match status {
+            FileCheckStatus::Good => FileAuditStatus::Good,
+            FileCheckStatus::Incorrect => FileAuditStatus::Incorrect,
+            FileCheckStatus::Missing => FileAuditStatus::Missing,
+            FileCheckStatus::NoDump => FileAuditStatus::NoDump,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileAudit {
+    pub name: String,
+    pub crc: Option<String>,
+    pub status: FileAuditStatus,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl From<&FileCheck> for FileAudit {
+    fn from(check: &FileCheck) -> Self {
+        Self {
+            name: check.name.clone(),
+            crc: check.crc.clone(),
+            status: check.status.into(),
+            expected: check.expected.clone(),
+            actual: check.actual.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameAudit {
+    pub name: String,
+    pub description: String,
+    pub status: AuditStatus,
+    #[serde(default)]
+    pub missing_files: Vec<String>,
+    #[serde(default)]
+    pub incorrect_files: Vec<String>,
+    pub chd_status: Option<String>,
+    #[serde(default)]
+    pub files: Vec<FileAudit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditExport {
+    pub schema: String,
+    pub schema_version: u32,
+    pub generated_at: String,
+    /// Computer the audit ran on, to tell exports apart
+    pub host: String,
+    pub mame_version: String,
+    /// What the sets were checked against (MAME data or a DAT)
+    pub reference: String,
+    /// Sorted by set name
+    pub games: Vec<GameAudit>,
+}
+
+impl AuditExport {
+    pub fn from_results(
+        results: &[VerificationResult],
+        mame_version: &str,
+        reference: &str,
+    ) -> Self {
+        let mut games: Vec<GameAudit> = results
+            .iter()
+            .map(|result| GameAudit {
+                name: result.game_name.clone(),
+                description: result.description.clone(),
+                status: (&result.status).into(),
+                missing_files: result.missing_files.clone(),
+                incorrect_files: result.incorrect_files.clone(),
+                chd_status: result.chd_status.clone(),
+                files: result.files.iter().map(FileAudit::from).collect(),
+            })
+            .collect();
+        games.sort_by(|a, b| a.name.cmp(&b.name));
+        games.dedup_by(|a, b| a.name == b.name);
+
+        Self {
+            schema: AUDIT_SCHEMA.to_string(),
+            schema_version: AUDIT_SCHEMA_VERSION,
+            generated_at: chrono::Local::now().to_rfc3339(),
+            host: host_name(),
+            mame_version: mame_version.to_string(),
+            reference: reference.to_string(),
+            games,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_json()?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents =
+            fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let export: Self = serde_json::from_slice(&contents)
+            .with_context(|| format!("{} is not an audit export", path.display()))?;
+        if export.schema != AUDIT_SCHEMA {
+            bail!("{} is not an audit export", path.display());
+        }
+        if export.schema_version > AUDIT_SCHEMA_VERSION {
+            bail!(
+                "{} uses audit schema version {}, newer than this version understands ({})",
+                path.display(),
+                export.schema_version,
+                AUDIT_SCHEMA_VERSION
+            );
+        }
+        Ok(export)
+    }
+
+    /// Short name for the export in the comparison view
+    pub fn label(&self) -> String {
+        let date = self.generated_at.get(..10).unwrap_or(&self.generated_at);
+        format!("{} ({})", self.host, date)
+    }
+}
+
+/// Where a set differs between two exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    LeftOnly,  // Playable on the left machine only
+    RightOnly, // Playable on the right machine only
+    Different, // Same playability, different status or files
+}
+
+impl DiffKind {
+    pub const ALL: [DiffKind; 3] = [DiffKind::LeftOnly, DiffKind::RightOnly, DiffKind::Different];
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileDiff {
+    pub name: String,
+    pub left: Option<FileAuditStatus>,
+    pub right: Option<FileAuditStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffRow {
+    pub name: String,
+    pub description: String,
+    /// None when the set is not in that export
+    pub left: Option<AuditStatus>,
+    pub right: Option<AuditStatus>,
+    pub files: Vec<FileDiff>,
+}
+
+impl DiffRow {
+    pub fn kind(&self) -> DiffKind {
+        let left = self.left.is_some_and(AuditStatus::is_playable);
+        let right = self.right.is_some_and(AuditStatus::is_playable);
+        match (left, right) {
+            (true, false) => DiffKind::LeftOnly,
+            (false, true) => DiffKind::RightOnly,
+            _ => DiffKind::Different,
+        }
+    }
+}
+
+/// Every set whose status or files differ between the two exports, by name
+pub fn diff_exports(left: &AuditExport, right: &AuditExport) -> Vec<DiffRow> {
+    let left_games: BTreeMap<&str, &GameAudit> =
+        left.games.iter().map(|g| (g.name.as_str(), g)).collect();
+    let right_games: BTreeMap<&str, &GameAudit> =
+        right.games.iter().map(|g| (g.name.as_str(), g)).collect();
+    let names: BTreeSet<&str> = left_games
+        .keys()
+        .chain(right_games.keys())
+        .copied()
+        .collect();
+
+    let mut rows = Vec::new();
+    for name in names {
+        let l = left_games.get(name).copied();
+        let r = right_games.get(name).copied();
+        let files = diff_files(l, r);
+        let left_status = l.map(|g| g.status);
+        let right_status = r.map(|g| g.status);
+        if left_status == right_status && files.is_empty() {
+            continue;
+        }
+        rows.push(DiffRow {
+            name: name.to_string(),
+            description: l.or(r).map(|g| g.description.clone()).unwrap_or_default(),
+            left: left_status,
+            right: right_status,
+            files,
+        });
+    }
+    rows
+}
+
+fn diff_files(left: Option<&GameAudit>, right: Option<&GameAudit>) -> Vec<FileDiff> {
+    let (Some(left), Some(right)) = (left, right) else {
+        return Vec::new();
+    };
+    let statuses = |game: &GameAudit| -> BTreeMap<String, FileAuditStatus> {
+        game.files
+            .iter()
+            .map(|file| (file.name.to_lowercase(), file.status))
+            .collect()
+    };
+    let left = statuses(left);
+    let right = statuses(right);
+    let names: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+
+    names
+        .into_iter()
+        .filter(|name| left.get(*name) != right.get(*name))
+        .map(|name| FileDiff {
+            name: name.clone(),
+            left: left.get(name).copied(),
+            right: right.get(name).copied(),
+        })
+        .collect()
+}
+
+fn host_name() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(
+        name: &str,
+        status: VerifyStatus,
+        files: &[(&str, FileCheckStatus)],
+    ) -> VerificationResult {
+        VerificationResult {
+            game_name: name.to_string(),
+            description: name.to_string(),
+            status,
+            missing_files: Vec::new(),
+            incorrect_files: Vec::new(),
+            extra_files: Vec::new(),
+            chd_status: None,
+            files: files
+                .iter()
+                .map(|(file, status)| FileCheck {
+                    name: file.to_string(),
+                    crc: Some("12345678".to_string()),
+                    status: *status,
+                    expected: None,
+                    actual: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn exports_round_trip_and_diff_by_set_and_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let left = AuditExport::from_results(
+            &[
+                result(
+                    "pacman",
+                    VerifyStatus::Passed,
+                    &[("pacman.6e", FileCheckStatus::Good)],
+                ),
+                result(
+                    "galaga",
+                    VerifyStatus::Failed,
+                    &[("gg1.bin", FileCheckStatus::Missing)],
+                ),
+                result("dkong", VerifyStatus::Passed, &[]),
+            ],
+            "0.280",
+            "MAME 0.280 datfile",
+        );
+        let path = dir.path().join("left.json");
+        left.save(&path).unwrap();
+        let left = AuditExport::load(&path).unwrap();
+        assert_eq!(left.schema_version, AUDIT_SCHEMA_VERSION);
+        assert_eq!(left.games[0].name, "dkong");
+
+        let json: serde_json::Value = serde_json::from_str(&left.to_json().unwrap()).unwrap();
+        assert_eq!(json["games"][1]["status"], "failed");
+        assert_eq!(json["games"][1]["files"][0]["status"], "missing");
+
+        let right = AuditExport::from_results(
+            &[
+                result(
+                    "pacman",
+                    VerifyStatus::Failed,
+                    &[("pacman.6e", FileCheckStatus::Incorrect)],
+                ),
+                result(
+                    "galaga",
+                    VerifyStatus::Passed,
+                    &[("gg1.bin", FileCheckStatus::Good)],
+                ),
+                result("dkong", VerifyStatus::Passed, &[]),
+                result("mspacman", VerifyStatus::NotFound, &[]),
+            ],
+            "0.280",
+            "MAME 0.280 datfile",
+        );
+        let rows = diff_exports(&left, &right);
+        let kinds: Vec<(&str, DiffKind)> = rows
+            .iter()
+            .map(|row| (row.name.as_str(), row.kind()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("galaga", DiffKind::RightOnly),
+                ("mspacman", DiffKind::Different),
+                ("pacman", DiffKind::LeftOnly),
+            ]
+        );
+        assert_eq!(rows[2].files[0].right, Some(FileAuditStatus::Incorrect));
+
+        fs::write(&path, r#"{"schema": "something-else"}"#).unwrap();
+        assert!(AuditExport::load(&path).is_err());
+    }
+}
@@ -0,0 +1,422 @@
+// src/utils/rom_utils/auditor.rs
+//! Offline ROM auditor: checks ROM archives against the -listxml manifests
+//! without spawning `mame -verifyroms` for every set.
+use super::RomLoader;
+use crate::models::{AppConfig, DiskEntry, DumpStatus, Game, MachineRomSet, RomEntry};
+use crate::ui::components::rom_verify::{
+    FileCheck, FileCheckStatus, VerificationResult, VerifyStatus,
+};
+use crate::utils::archive::ArchiveEntry;
+use crate::utils::chd;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Audits machines against the ROM files found in the configured ROM paths.
+///
+/// Archive listings are read once per set and reused, so auditing a parent
+/// and all its clones only opens the parent archive a single time.
+pub struct RomAuditor {
+    loader: RomLoader,
+    machines: HashMap<String, MachineRomSet>,
+    listings: HashMap<String, Option<Vec<ArchiveEntry>>>,
+}
+
+/// How a single manifest ROM compared against the files on disk
+enum RomMatch {
+    Good,
+    Incorrect(String), // What was found instead
+    Missing,
+}
+
+impl RomAuditor {
+    pub fn new(rom_dirs: Vec<PathBuf>, games: &[Game]) -> Self {
+        let machines = games
+            .iter()
+            .map(|game| (game.name.clone(), game.rom_set.clone()))
+            .collect();
+
+        Self {
+            loader: RomLoader::new(rom_dirs),
+            machines,
+            listings: HashMap::new(),
+        }
+    }
+
+    /// Auditor over the same ROM paths MAME is launched with
+    pub fn from_config(config: &AppConfig, games: &[Game]) -> Self {
+        Self::new(super::configured_rom_dirs(config), games)
+    }
+
+    pub fn audit(&mut self, game: &Game) -> VerificationResult {
+        let rom_set = self
+            .machines
+            .get(&game.name)
+            .cloned()
+            .unwrap_or_else(|| game.rom_set.clone());
+        let chain = self.parent_chain(&game.name, &rom_set);
+
+        let mut missing_files = Vec::new();
+        let mut incorrect_files = Vec::new();
+        let mut has_bad_dump = false;
+        let mut needs_best_available = false;
+        let mut found_any = false;
+        let mut checked_any = false;
+        let mut files = Vec::new();
+
+        for rom in &rom_set.roms {
+            let mut check = FileCheck {
+                name: rom.name.clone(),
+                crc: rom.crc.clone(),
+                status: FileCheckStatus::NoDump,
+                expected: None,
+                actual: None,
+            };
+            if rom.status == DumpStatus::NoDump {
+                needs_best_available = true;
+                files.push(check);
+                continue;
+            }
+            checked_any = true;
+
+            let rom_match = self.match_rom(rom, &game.name, &chain);
+            check.status = match &rom_match {
+                RomMatch::Good => FileCheckStatus::Good,
+                RomMatch::Incorrect(_) => FileCheckStatus::Incorrect,
+                RomMatch::Missing => FileCheckStatus::Missing,
+            };
+            match rom_match {
+                RomMatch::Good => found_any = true,
+                RomMatch::Incorrect(_) if rom.status == DumpStatus::BadDump => {
+                    found_any = true;
+                    has_bad_dump = true;
+                }
+                RomMatch::Incorrect(found) => {
+                    found_any = true;
+                    incorrect_files.push(rom.name.clone());
+                    check.expected = Some(describe_rom(rom.crc.as_deref(), rom.size));
+                    check.actual = Some(found);
+                }
+                RomMatch::Missing if rom.optional => {}
+                RomMatch::Missing => missing_files.push(rom.name.clone()),
+            }
+            if rom.status == DumpStatus::BadDump {
+                needs_best_available = true;
+            }
+            files.push(check);
+        }
+
+        // Device ROMs live in the device's own set, but merged collections
+        // sometimes carry them inside the machine archive as well.
+        for device in &rom_set.device_refs {
+            let Some(device_set) = self.machines.get(device).cloned() else {
+                continue;
+            };
+            let mut device_chain = self.parent_chain(device, &device_set);
+            device_chain.extend(chain.iter().cloned());
+
+            for rom in device_set.dumped_roms() {
+                match self.match_rom(rom, device, &device_chain) {
+                    RomMatch::Good => {}
+                    RomMatch::Incorrect(_) if rom.status == DumpStatus::BadDump => {
+                        has_bad_dump = true
+                    }
+                    RomMatch::Incorrect(_) => {
+                        incorrect_files.push(format!("{}/{}", device, rom.name))
+                    }
+                    RomMatch::Missing if rom.optional => {}
+                    RomMatch::Missing => missing_files.push(format!("{}/{}", device, rom.name)),
+                }
+            }
+        }
+
+        let mut missing_disks = Vec::new();
+        let mut incorrect_disks = Vec::new();
+        for disk in &rom_set.disks {
+            let mut check = FileCheck {
+                name: disk.name.clone(),
+                crc: None,
+                status: FileCheckStatus::NoDump,
+                expected: None,
+                actual: None,
+            };
+            if disk.status != DumpStatus::NoDump {
+                match self.match_disk(disk, &chain) {
+                    RomMatch::Good => check.status = FileCheckStatus::Good,
+                    RomMatch::Incorrect(found) => {
+                        check.status = FileCheckStatus::Incorrect;
+                        check.expected = disk.sha1.as_ref().map(|sha1| format!("SHA1({})", sha1));
+                        check.actual = Some(found);
+                        if !disk.optional {
+                            incorrect_disks.push(disk.name.as_str());
+                        }
+                    }
+                    RomMatch::Missing => {
+                        check.status = FileCheckStatus::Missing;
+                        if !disk.optional {
+                            missing_disks.push(disk.name.as_str());
+                        }
+                    }
+                }
+            }
+            files.push(check);
+        }
+        let mut chd_problems = Vec::new();
+        if !missing_disks.is_empty() {
+            chd_problems.push(format!("CHD not found: {}", missing_disks.join(", ")));
+        }
+        if !incorrect_disks.is_empty() {
+            chd_problems.push(format!("CHD incorrect: {}", incorrect_disks.join(", ")));
+        }
+        let chd_status = (!chd_problems.is_empty()).then(|| chd_problems.join("; "));
+
+        let status = if checked_any && !found_any {
+            VerifyStatus::NotFound
+        } else if !missing_files.is_empty() || !incorrect_files.is_empty() || chd_status.is_some() {
+            VerifyStatus::Failed
+        } else if has_bad_dump || needs_best_available {
+            VerifyStatus::Warning
+        } else {
+            VerifyStatus::Passed
+        };
+
+        VerificationResult {
+            game_name: game.name.clone(),
+            description: game.description.clone(),
+            status,
+            missing_files,
+            incorrect_files,
+            extra_files: Vec::new(),
+            chd_status,
+            files,
+        }
+    }
+
+    /// The set itself followed by every set it inherits ROMs from (parent, BIOS)
+    fn parent_chain(&self, name: &str, rom_set: &MachineRomSet) -> Vec<String> {
+        let mut chain = vec![name.to_string()];
+        let mut next = rom_set.romof.clone().or_else(|| rom_set.cloneof.clone());
+
+        while let Some(parent) = next {
+            if chain.contains(&parent) {
+                break;
+            }
+            next = self
+                .machines
+                .get(&parent)
+                .and_then(|set| set.romof.clone().or_else(|| set.cloneof.clone()));
+            chain.push(parent);
+        }
+
+        chain
+    }
+
+    fn match_rom(&mut self, rom: &RomEntry, owner: &str, chain: &[String]) -> RomMatch {
+        let expected_crc = rom
+            .crc
+            .as_deref()
+            .and_then(|crc| u32::from_str_radix(crc, 16).ok());
+        let mut name_found = None;
+
+        for set in chain {
+            // Inside a parent archive the ROM goes by its merge name.
+            let name = if set == owner {
+                rom.name.as_str()
+            } else {
+                rom.merge.as_deref().unwrap_or(&rom.name)
+            };
+            let Some(files) = self.listing(set) else {
+                continue;
+            };
+
+            if let Some(crc) = expected_crc
+                && files.iter().any(|f| f.crc == crc && f.size == rom.size)
+            {
+                return RomMatch::Good;
+            }
+            if let Some(file) = files.iter().find(|f| f.name.eq_ignore_ascii_case(name)) {
+                if expected_crc.is_none() && file.size == rom.size {
+                    return RomMatch::Good;
+                }
+                if name_found.is_none() {
+                    name_found = Some(describe_rom(Some(&format!("{:08x}", file.crc)), file.size));
+                }
+            }
+        }
+
+        if let Some(found) = name_found {
+            RomMatch::Incorrect(found)
+        } else {
+            RomMatch::Missing
+        }
+    }
+
+    /// A disk is good when its CHD header carries the SHA1 from -listxml.
+    /// Disks without a known SHA1 only need to exist.
+    fn match_disk(&self, disk: &DiskEntry, chain: &[String]) -> RomMatch {
+        let merged_name = disk.merge.as_deref().unwrap_or(&disk.name);
+        let Some(path) = self.loader.locate_disk(chain, &[&disk.name, merged_name]) else {
+            return RomMatch::Missing;
+        };
+        let Some(expected) = &disk.sha1 else {
+            return RomMatch::Good;
+        };
+
+        match chd::read_header(&path) {
+            Ok(header) if &header.sha1 == expected => RomMatch::Good,
+            Ok(header) => RomMatch::Incorrect(format!("SHA1({})", header.sha1)),
+            Err(e) => RomMatch::Incorrect(format!("{:#}", e)),
+        }
+    }
+
+    fn listing(&mut self, set: &str) -> Option<&[ArchiveEntry]> {
+        if !self.listings.contains_key(set) {
+            let listing = self.loader.set_entries(set);
+            self.listings.insert(set.to_string(), listing);
+        }
+        self.listings.get(set)?.as_deref()
+    }
+}
+
+/// `CRC(1a2b3c4d) 4096 bytes`, the way MAME reports hashes
+fn describe_rom(crc: Option<&str>, size: u64) -> String {
+    match crc {
+        Some(crc) => format!("CRC({}) {} bytes", crc, size),
+        None => format!("{} bytes", size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DiskEntry;
+    use crate::utils::rom_utils::test_support::{game, rom, write_zip};
+    use std::fs;
+    use std::path::Path;
+
+    fn write_7z(dir: &Path, set: &str, files: &[(&str, &[u8])]) {
+        let source = tempfile::tempdir().unwrap();
+        for (name, data) in files {
+            fs::write(source.path().join(name), data).unwrap();
+        }
+        sevenz_rust::compress_to_path(source.path(), dir.join(format!("{}.7z", set))).unwrap();
+    }
+
+    #[test]
+    fn audits_split_sets_with_parent_bios_and_devices() {
+        let dir = tempfile::tempdir().unwrap();
+        write_zip(dir.path(), "neogeo", &[("sp-s2.sp1", b"bios")]);
+        write_zip(dir.path(), "parent", &[("p1.bin", b"parent rom")]);
+        write_zip(dir.path(), "clone", &[("c1.bin", b"clone rom")]);
+        write_7z(dir.path(), "z80dev", &[("z80.bin", b"device")]);
+
+        let bios = MachineRomSet {
+            roms: vec![rom("sp-s2.sp1", b"bios")],
+            ..Default::default()
+        };
+        let device = MachineRomSet {
+            roms: vec![rom("z80.bin", b"device")],
+            ..Default::default()
+        };
+        let parent = MachineRomSet {
+            romof: Some("neogeo".to_string()),
+            roms: vec![
+                RomEntry {
+                    merge: Some("sp-s2.sp1".to_string()),
+                    ..rom("sp-s2.sp1", b"bios")
+                },
+                rom("p1.bin", b"parent rom"),
+            ],
+            device_refs: vec!["z80dev".to_string()],
+            ..Default::default()
+        };
+        let clone = MachineRomSet {
+            cloneof: Some("parent".to_string()),
+            romof: Some("parent".to_string()),
+            roms: vec![
+                RomEntry {
+                    merge: Some("p1.bin".to_string()),
+                    ..rom("p1.bin", b"parent rom")
+                },
+                rom("c1.bin", b"clone rom"),
+                rom("c2.bin", b"not dumped here"),
+            ],
+            ..Default::default()
+        };
+
+        let games = vec![
+            game("neogeo", bios),
+            game("z80dev", device),
+            game("parent", parent),
+            game("clone", clone),
+        ];
+        let mut auditor = RomAuditor::new(vec![dir.path().to_path_buf()], &games);
+
+        let result = auditor.audit(&games[2]);
+        assert_eq!(result.status, VerifyStatus::Passed);
+
+        let result = auditor.audit(&games[3]);
+        assert_eq!(result.status, VerifyStatus::Failed);
+        assert_eq!(result.missing_files, vec!["c2.bin".to_string()]);
+    }
+
+    #[test]
+    fn reports_incorrect_not_found_and_missing_chd() {
+        let dir = tempfile::tempdir().unwrap();
+        write_zip(dir.path(), "wrong", &[("a.bin", b"corrupted")]);
+        fs::create_dir(dir.path().join("hdd")).unwrap();
+        fs::write(dir.path().join("hdd").join("a.bin"), b"good").unwrap();
+
+        let wrong = MachineRomSet {
+            roms: vec![rom("a.bin", b"good")],
+            ..Default::default()
+        };
+        let missing = MachineRomSet {
+            roms: vec![rom("a.bin", b"good")],
+            ..Default::default()
+        };
+        let hdd = MachineRomSet {
+            roms: vec![rom("a.bin", b"good")],
+            disks: vec![DiskEntry {
+                name: "hdd".to_string(),
+                sha1: Some("ab".repeat(20)),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let games = vec![
+            game("wrong", wrong),
+            game("missing", missing),
+            game("hdd", hdd),
+        ];
+        let mut auditor = RomAuditor::new(vec![dir.path().to_path_buf()], &games);
+
+        let result = auditor.audit(&games[0]);
+        assert_eq!(result.status, VerifyStatus::Failed);
+        assert_eq!(result.incorrect_files, vec!["a.bin".to_string()]);
+        assert_eq!(result.files[0].status, FileCheckStatus::Incorrect);
+        assert!(
+            result.files[0]
+                .actual
+                .as_deref()
+                .unwrap()
+                .starts_with("CRC(")
+        );
+
+        assert_eq!(auditor.audit(&games[1]).status, VerifyStatus::NotFound);
+
+        let result = auditor.audit(&games[2]);
+        assert_eq!(result.status, VerifyStatus::Failed);
+        assert_eq!(result.chd_status.as_deref(), Some("CHD not found: hdd"));
+
+        // An outdated CHD carries a different SHA1 in its header
+        let chd_path = dir.path().join("hdd").join("hdd.chd");
+        fs::write(&chd_path, chd::test_header(5, 1 << 20, [0xcd; 20])).unwrap();
+        let result = auditor.audit(&games[2]);
+        assert_eq!(result.status, VerifyStatus::Failed);
+        assert_eq!(result.chd_status.as_deref(), Some("CHD incorrect: hdd"));
+
+        fs::write(&chd_path, chd::test_header(4, 1 << 20, [0xab; 20])).unwrap();
+        assert_eq!(auditor.audit(&games[2]).status, VerifyStatus::Passed);
+    }
+}
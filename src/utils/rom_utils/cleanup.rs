@@ -0,0 +1,715 @@
+// src/utils/rom_utils/cleanup.rs
+//! Finds files in the ROM directories that no machine needs: archives and
+//! CHDs named after no machine, files a set does not use, and the same set
+//! stored more than once. Only the top level of each ROM directory holds
+//! sets; software-list media in `<list>/` folders is left alone. Chosen
+//! files are moved to a quarantine folder with an undo manifest; nothing is
+//! ever deleted.
+use super::{RomSource, list_folder};
+use crate::models::Game;
+use crate::utils::archive::{self, ARCHIVE_EXTENSIONS};
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use walkdir::WalkDir;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CleanupKind {
+    Orphan,    // Named after no machine or disk
+    Unneeded,  // Inside a set, but not used by it
+    Duplicate, // Another copy of a set that MAME finds first elsewhere
+}
+
+impl CleanupKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CleanupKind::Orphan => "Orphan",
+            CleanupKind::Unneeded => "Unneeded",
+            CleanupKind::Duplicate => "Duplicate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanupItem {
+    pub kind: CleanupKind,
+    pub path: PathBuf,
+    /// Entry inside `path`; archive entries are reported but cannot be moved
+    /// on their own (rebuilding the set drops them)
+    pub entry: Option<String>,
+    pub detail: String,
+}
+
+impl CleanupItem {
+    pub fn movable(&self) -> bool {
+        self.entry.is_none()
+    }
+
+    pub fn display_path(&self) -> String {
+        match &self.entry {
+            Some(entry) => format!("{}/{}", self.path.display(), entry),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CleanupReport {
+    pub items: Vec<CleanupItem>,
+    pub files_checked: usize,
+    pub errors: Vec<String>,
+}
+
+/// One file moved into quarantine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineMove {
+    pub kind: CleanupKind,
+    pub original: PathBuf,
+    pub quarantined: PathBuf,
+}
+
+/// Written next to the quarantined files so a move can be undone
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineManifest {
+    pub created: String,
+    pub moves: Vec<QuarantineMove>,
+    #[serde(default)]
+    pub restored: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreSummary {
+    pub restored: usize,
+    pub skipped: Vec<String>,
+}
+
+/// ROMs, CRCs and disks an archive or folder of a set may legitimately hold
+#[derive(Default)]
+struct SetNeeds {
+    crcs: HashSet<u32>,
+    names: HashSet<String>,
+    disks: HashSet<String>,
+}
+
+/// Every place an archive of each set name was found, in the order MAME
+/// looks: rompath order, then `ARCHIVE_EXTENSIONS` order within a directory
+#[derive(Default)]
+struct ArchiveLocations {
+    sets: Vec<(String, Vec<PathBuf>)>,
+    index: HashMap<String, usize>,
+}
+
+impl ArchiveLocations {
+    fn add(&mut self, stem: &str, path: PathBuf) {
+        match self.index.get(stem) {
+            Some(&slot) => self.sets[slot].1.push(path),
+            None => {
+                self.index.insert(stem.to_string(), self.sets.len());
+                self.sets.push((stem.to_string(), vec![path]));
+            }
+        }
+    }
+}
+
+/// Scans the ROM directories against the machine list
+pub struct CleanupScanner<'a> {
+    machines: HashMap<String, &'a Game>,
+    clones: HashMap<&'a str, Vec<&'a Game>>,
+    disk_names: HashSet<String>,
+    /// Folders named after these hold software-list media, not sets
+    software_lists: HashSet<String>,
+    software_paths: Vec<PathBuf>,
+    report: CleanupReport,
+}
+
+impl<'a> CleanupScanner<'a> {
+    pub fn new(games: &'a [Game]) -> Self {
+        let mut clones: HashMap<&str, Vec<&Game>> = HashMap::new();
+        for game in games {
+            if let Some(parent) = &game.rom_set.cloneof {
+                clones.entry(parent.as_str()).or_default().push(game);
+            }
+        }
+        Self {
+            machines: games
+                .iter()
+                .map(|game| (game.name.to_lowercase(), game))
+                .collect(),
+            clones,
+            disk_names: games
+                .iter()
+                .flat_map(|game| &game.rom_set.disks)
+                .map(|disk| disk.name.to_lowercase())
+                .collect(),
+            software_lists: games
+                .iter()
+                .flat_map(|game| &game.rom_set.software_lists)
+                .map(|list| list.name.to_lowercase())
+                .collect(),
+            software_paths: Vec::new(),
+            report: CleanupReport::default(),
+        }
+    }
+
+    /// Software-list media folders to leave alone, e.g. when they sit
+    /// inside a ROM directory
+    pub fn with_software_paths(mut self, software_paths: &[PathBuf]) -> Self {
+        self.software_paths = software_paths.to_vec();
+        self
+    }
+
+    /// Check every ROM directory. Directories earlier in the list win, as in
+    /// MAME's rompath, so later copies of a set are the duplicates.
+    pub fn scan(mut self, rom_dirs: &[PathBuf]) -> CleanupReport {
+        // A software path that is also a ROM directory holds sets as well;
+        // its list folders are caught by name instead
+        self.software_paths.retain(|path| !rom_dirs.contains(path));
+
+        let mut archives = ArchiveLocations::default();
+        for dir in rom_dirs {
+            if !self.is_software_path(dir) {
+                self.scan_dir(dir, &mut archives);
+            }
+        }
+
+        for (stem, paths) in archives.sets {
+            let Some(game) = self.machines.get(&stem).copied() else {
+                for path in paths {
+                    self.report.items.push(CleanupItem {
+                        kind: CleanupKind::Orphan,
+                        path,
+                        entry: None,
+                        detail: "Named after no machine".to_string(),
+                    });
+                }
+                continue;
+            };
+
+            let mut paths = paths.into_iter();
+            let Some(first) = paths.next() else {
+                continue;
+            };
+            self.check_archive(game, &first);
+            for path in paths {
+                self.report.items.push(CleanupItem {
+                    kind: CleanupKind::Duplicate,
+                    path,
+                    entry: None,
+                    detail: format!("MAME loads {} first", first.display()),
+                });
+            }
+        }
+
+        self.report
+    }
+
+    fn is_software_path(&self, path: &Path) -> bool {
+        self.software_paths
+            .iter()
+            .any(|root| path.starts_with(root))
+    }
+
+    /// Archives and CHDs at the top of a ROM directory are sets and disks,
+    /// as are folders named after a machine. Anything deeper is not loaded
+    /// by MAME as a set, so it is never reported.
+    fn scan_dir(&mut self, dir: &Path, archives: &mut ArchiveLocations) {
+        let mut entries: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+            Err(e) => {
+                self.report
+                    .errors
+                    .push(format!("Failed to read {}: {}", dir.display(), e));
+                return;
+            }
+        };
+        // A set's archives go in MAME's lookup order (pacman.zip before
+        // pacman.7z), so the one it loads is never flagged as the duplicate
+        entries.sort_by_cached_key(|path| {
+            let ext = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase);
+            let rank = ARCHIVE_EXTENSIONS
+                .iter()
+                .position(|known| ext.as_deref() == Some(*known))
+                .unwrap_or(ARCHIVE_EXTENSIONS.len());
+            (path.with_extension(""), rank)
+        });
+
+        for path in entries {
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let lower = name.to_lowercase();
+
+            if path.is_dir() {
+                if self.software_lists.contains(&lower) || self.is_software_path(&path) {
+                    continue;
+                }
+                if let Some(game) = self.machines.get(&lower).copied() {
+                    self.check_set_folder(game, &path);
+                }
+                continue;
+            }
+
+            let Some((stem, ext)) = lower.rsplit_once('.') else {
+                continue;
+            };
+            if ARCHIVE_EXTENSIONS.contains(&ext) {
+                self.report.files_checked += 1;
+                archives.add(stem, path);
+            } else if ext == "chd" {
+                self.report.files_checked += 1;
+                if !self.disk_names.contains(stem) {
+                    self.report.items.push(CleanupItem {
+                        kind: CleanupKind::Orphan,
+                        path,
+                        entry: None,
+                        detail: "Disk of no machine".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_archive(&mut self, game: &Game, path: &Path) {
+        let entries = match archive::list_entries(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.report.errors.push(format!("{:#}", e));
+                return;
+            }
+        };
+        let needs = self.needs(game);
+        for entry in entries {
+            if !needs.crcs.contains(&entry.crc) && !needs.names.contains(&entry.name.to_lowercase())
+            {
+                self.report.items.push(CleanupItem {
+                    kind: CleanupKind::Unneeded,
+                    path: path.to_path_buf(),
+                    entry: Some(entry.name),
+                    detail: format!("Not used by {}", game.name),
+                });
+            }
+        }
+    }
+
+    /// Loose set folders: unused files and CHDs can be moved one by one
+    fn check_set_folder(&mut self, game: &Game, folder: &Path) {
+        let needs = self.needs(game);
+
+        match list_folder(folder) {
+            Ok(files) => {
+                for file in files {
+                    self.report.files_checked += 1;
+                    let RomSource::File(path) = file.source else {
+                        continue;
+                    };
+                    if !needs.crcs.contains(&file.entry.crc)
+                        && !needs.names.contains(&file.entry.name.to_lowercase())
+                    {
+                        self.report.items.push(CleanupItem {
+                            kind: CleanupKind::Unneeded,
+                            path,
+                            entry: None,
+                            detail: format!("Not used by {}", game.name),
+                        });
+                    }
+                }
+            }
+            Err(e) => self.report.errors.push(format!("{:#}", e)),
+        }
+
+        for entry in WalkDir::new(folder)
+            .min_depth(1)
+            .max_depth(1)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if !path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("chd"))
+            {
+                continue;
+            }
+            self.report.files_checked += 1;
+            let stem = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default()
+                .to_lowercase();
+            if !needs.disks.contains(&stem) {
+                self.report.items.push(CleanupItem {
+                    kind: CleanupKind::Unneeded,
+                    path: path.to_path_buf(),
+                    entry: None,
+                    detail: format!("Disk not used by {}", game.name),
+                });
+            }
+        }
+    }
+
+    /// Files a set may hold in any layout: its own ROMs, its clones' (merged),
+    /// its parent and BIOS chain and its devices' (non-merged)
+    fn needs(&self, game: &Game) -> SetNeeds {
+        let mut needs = SetNeeds::default();
+        let mut sets: Vec<&Game> = vec![game];
+        sets.extend(self.clones.get(game.name.as_str()).into_iter().flatten());
+
+        // Follow romof and device links upwards; clones of the parent are not needed
+        let mut seen = HashSet::new();
+        let mut index = 0;
+        while let Some(&set) = sets.get(index) {
+            index += 1;
+            if !seen.insert(set.name.as_str()) {
+                continue;
+            }
+            for rom in &set.rom_set.roms {
+                needs.names.insert(rom.name.to_lowercase());
+                if let Some(crc) = rom
+                    .crc
+                    .as_deref()
+                    .and_then(|crc| u32::from_str_radix(crc, 16).ok())
+                {
+                    needs.crcs.insert(crc);
+                }
+            }
+            needs.disks.extend(
+                set.rom_set
+                    .disks
+                    .iter()
+                    .map(|disk| disk.name.to_lowercase()),
+            );
+            sets.extend(
+                set.rom_set
+                    .romof
+                    .iter()
+                    .chain(&set.rom_set.device_refs)
+                    .filter_map(|name| self.machines.get(&name.to_lowercase()).copied()),
+            );
+        }
+        needs
+    }
+}
+
+/// Run the cleanup scan on a background thread
+pub fn spawn_cleanup_scan(
+    rom_dirs: Vec<PathBuf>,
+    software_paths: Vec<PathBuf>,
+    games: Vec<Game>,
+) -> mpsc::Receiver<CleanupReport> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let report = CleanupScanner::new(&games)
+            .with_software_paths(&software_paths)
+            .scan(&rom_dirs);
+        let _ = tx.send(report);
+    });
+    rx
+}
+
+/// Move the chosen files into `<quarantine>/<timestamp>/`, keeping their
+/// path below the ROM directory, and write the undo manifest. Files moved
+/// before an error are still recorded.
+pub fn quarantine(
+    items: &[&CleanupItem],
+    rom_dirs: &[PathBuf],
+    quarantine_dir: &Path,
+) -> Result<(PathBuf, QuarantineManifest)> {
+    // Quarantined files inside a ROM directory would still be found by MAME
+    if let Some(dir) = rom_dirs.iter().find(|dir| quarantine_dir.starts_with(dir)) {
+        bail!(
+            "The quarantine folder must not be inside ROM directory {}",
+            dir.display()
+        );
+    }
+    let now = chrono::Local::now();
+    let batch_dir = quarantine_dir.join(now.format("%Y%m%d-%H%M%S").to_string());
+    if batch_dir.exists() {
+        bail!("{} already exists, try again", batch_dir.display());
+    }
+    fs::create_dir_all(&batch_dir)
+        .with_context(|| format!("Failed to create {}", batch_dir.display()))?;
+
+    let mut manifest = QuarantineManifest {
+        created: now.to_rfc3339(),
+        moves: Vec::new(),
+        restored: false,
+    };
+    let manifest_path = batch_dir.join(MANIFEST_FILE);
+
+    let mut result = Ok(());
+    for item in items.iter().filter(|item| item.movable()) {
+        let relative = match rom_dirs
+            .iter()
+            .enumerate()
+            .find_map(|(index, dir)| Some((index, item.path.strip_prefix(dir).ok()?)))
+        {
+            // Numbered per ROM directory so equal names from two directories never collide
+            Some((index, relative)) => PathBuf::from(index.to_string()).join(relative),
+            None => {
+                result = Err(anyhow::anyhow!(
+                    "{} is outside the ROM directories",
+                    item.path.display()
+                ));
+                break;
+            }
+        };
+        let target = batch_dir.join(relative);
+        if let Err(e) = move_path(&item.path, &target) {
+            result = Err(e);
+            break;
+        }
+        manifest.moves.push(QuarantineMove {
+            kind: item.kind,
+            original: item.path.clone(),
+            quarantined: target,
+        });
+    }
+
+    write_manifest(&manifest_path, &manifest)?;
+    result.map(|()| (manifest_path, manifest))
+}
+
+/// Quarantine batches not restored yet, newest first
+pub fn list_manifests(quarantine_dir: &Path) -> Vec<(PathBuf, QuarantineManifest)> {
+    let Ok(entries) = fs::read_dir(quarantine_dir) else {
+        return Vec::new();
+    };
+    let mut manifests: Vec<(PathBuf, QuarantineManifest)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path().join(MANIFEST_FILE))
+        .filter_map(|path| {
+            let manifest = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+            Some((path, manifest))
+        })
+        .filter(|(_, manifest): &(PathBuf, QuarantineManifest)| !manifest.restored)
+        .collect();
+    manifests.sort_by(|a, b| b.1.created.cmp(&a.1.created));
+    manifests
+}
+
+/// Move every file of a batch back. Files whose original path is taken
+/// again are left in quarantine and reported.
+pub fn restore(manifest_path: &Path) -> Result<RestoreSummary> {
+    let data = fs::read(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let mut manifest: QuarantineManifest = serde_json::from_slice(&data)
+        .with_context(|| format!("Invalid manifest {}", manifest_path.display()))?;
+
+    let mut summary = RestoreSummary::default();
+    let mut remaining = Vec::new();
+    for entry in manifest.moves {
+        if entry.original.exists() {
+            summary.skipped.push(format!(
+                "{} exists again, left in quarantine",
+                entry.original.display()
+            ));
+            remaining.push(entry);
+            continue;
+        }
+        match move_path(&entry.quarantined, &entry.original) {
+            Ok(()) => summary.restored += 1,
+            Err(e) => {
+                summary.skipped.push(format!("{:#}", e));
+                remaining.push(entry);
+            }
+        }
+    }
+
+    manifest.restored = remaining.is_empty();
+    manifest.moves = remaining;
+    write_manifest(manifest_path, &manifest)?;
+    Ok(summary)
+}
+
+fn write_manifest(path: &Path, manifest: &QuarantineManifest) -> Result<()> {
+    fs::write(path, serde_json::to_vec_pretty(manifest)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Rename, or copy then remove the source when the quarantine folder is on
+/// another filesystem
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        bail!("{} already exists", to.display());
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    fs::copy(from, to)
+        .with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    let copied = fs::metadata(to)?.len();
+    if copied != fs::metadata(from)?.len() {
+        bail!("Copy of {} is incomplete", from.display());
+    }
+    fs::remove_file(from).with_context(|| format!("Failed to move {}", from.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DiskEntry, SoftwareListRef, SoftwareListStatus};
+    use crate::utils::rom_utils::test_support::{family_game, rom, write_zip};
+
+    #[test]
+    fn finds_orphans_unneeded_files_and_duplicates_and_quarantines_them() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let quarantine_dir = tempfile::tempdir().unwrap();
+        let rom_dirs = vec![first.path().to_path_buf(), second.path().to_path_buf()];
+
+        let mut pacman = family_game("pacman", None, vec![rom("pacman.6e", b"parent")]);
+        pacman.rom_set.disks.push(DiskEntry {
+            name: "pacdisk".to_string(),
+            ..Default::default()
+        });
+        let games = vec![
+            pacman,
+            family_game("puckman", Some("pacman"), vec![rom("puckman.6e", b"clone")]),
+        ];
+
+        // Merged pacman.zip holds the clone's ROM too, plus a stray readme
+        write_zip(
+            first.path(),
+            "pacman",
+            &[
+                ("pacman.6e", b"parent"),
+                ("puckman.6e", b"clone"),
+                ("readme.txt", b"junk"),
+            ],
+        );
+        write_zip(first.path(), "oldname", &[("x", b"x")]);
+        fs::create_dir(first.path().join("pacman")).unwrap();
+        fs::write(first.path().join("pacman").join("pacdisk.chd"), b"disk").unwrap();
+        fs::write(first.path().join("pacman").join("stale.chd"), b"disk").unwrap();
+        write_zip(second.path(), "pacman", &[("pacman.6e", b"parent")]);
+        fs::write(second.path().join("notes.txt"), b"not a rom file").unwrap();
+
+        let report = CleanupScanner::new(&games).scan(&rom_dirs);
+        let found: Vec<(CleanupKind, String)> = report
+            .items
+            .iter()
+            .map(|item| {
+                let path = item.display_path();
+                let relative = path
+                    .strip_prefix(&*first.path().to_string_lossy())
+                    .or_else(|| path.strip_prefix(&*second.path().to_string_lossy()))
+                    .unwrap()
+                    .to_string();
+                (item.kind, relative)
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (CleanupKind::Unneeded, "/pacman/stale.chd".to_string()),
+                (CleanupKind::Orphan, "/oldname.zip".to_string()),
+                (CleanupKind::Unneeded, "/pacman.zip/readme.txt".to_string()),
+                (CleanupKind::Duplicate, "/pacman.zip".to_string()),
+            ]
+        );
+        assert_eq!(report.items[3].path, second.path().join("pacman.zip"));
+        assert!(!report.items[2].movable());
+
+        let chosen: Vec<&CleanupItem> = report.items.iter().collect();
+        let (manifest_path, manifest) =
+            quarantine(&chosen, &rom_dirs, quarantine_dir.path()).unwrap();
+        assert_eq!(manifest.moves.len(), 3);
+        assert!(!first.path().join("oldname.zip").exists());
+        assert!(!second.path().join("pacman.zip").exists());
+        assert!(first.path().join("pacman.zip").exists());
+        assert!(
+            manifest
+                .moves
+                .iter()
+                .all(|entry| entry.quarantined.exists())
+        );
+        assert_eq!(list_manifests(quarantine_dir.path()).len(), 1);
+
+        let summary = restore(&manifest_path).unwrap();
+        assert_eq!(summary.restored, 3);
+        assert!(summary.skipped.is_empty());
+        assert!(first.path().join("oldname.zip").exists());
+        assert!(second.path().join("pacman.zip").exists());
+        assert!(first.path().join("pacman").join("stale.chd").exists());
+        assert!(list_manifests(quarantine_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn keeps_the_zip_mame_loads_before_a_7z() {
+        let roms = tempfile::tempdir().unwrap();
+        let games = vec![family_game(
+            "pacman",
+            None,
+            vec![rom("pacman.6e", b"parent")],
+        )];
+        write_zip(roms.path(), "pacman", &[("pacman.6e", b"parent")]);
+        fs::write(roms.path().join("pacman.7z"), b"older copy").unwrap();
+
+        let report = CleanupScanner::new(&games).scan(&[roms.path().to_path_buf()]);
+        assert_eq!(report.items.len(), 1);
+        assert_eq!(report.items[0].kind, CleanupKind::Duplicate);
+        assert_eq!(report.items[0].path, roms.path().join("pacman.7z"));
+        assert!(report.items[0].detail.ends_with("pacman.zip first"));
+    }
+
+    #[test]
+    fn leaves_software_list_media_alone() {
+        let roms = tempfile::tempdir().unwrap();
+        let dir = |name: &str| {
+            let path = roms.path().join(name);
+            fs::create_dir(&path).unwrap();
+            path
+        };
+        let mut nes = family_game("nes", None, vec![rom("nes.bin", b"bios")]);
+        nes.rom_set.software_lists.push(SoftwareListRef {
+            name: "nes".to_string(),
+            status: SoftwareListStatus::Original,
+            filter: None,
+        });
+        let games = vec![
+            nes,
+            family_game("megadriv", None, vec![rom("md.bin", b"bios")]),
+            family_game("pacman", None, vec![rom("pacman.6e", b"parent")]),
+        ];
+
+        // roms/nes/ is both the nes machine's folder and the nes list's
+        write_zip(&dir("nes"), "smb", &[("smb.nes", b"smb")]);
+        // The same software in a second list folder is no duplicate
+        write_zip(&dir("famicom"), "smb", &[("smb.nes", b"smb")]);
+        // A machine-named folder configured as a software path
+        let megadriv = dir("megadriv");
+        write_zip(&megadriv, "sonic", &[("sonic.md", b"sonic")]);
+        // A real set folder is still checked
+        fs::write(dir("pacman").join("stale.bin"), b"junk").unwrap();
+
+        let report = CleanupScanner::new(&games)
+            .with_software_paths(&[megadriv])
+            .scan(&[roms.path().to_path_buf()]);
+        let found: Vec<(CleanupKind, PathBuf)> = report
+            .items
+            .iter()
+            .map(|item| (item.kind, item.path.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(
+                CleanupKind::Unneeded,
+                roms.path().join("pacman").join("stale.bin")
+            )]
+        );
+    }
+}
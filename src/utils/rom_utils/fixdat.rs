@@ -0,0 +1,283 @@
+// src/utils/rom_utils/fixdat.rs
+//! Logiqx XML "fixdat" listing exactly the ROMs and disks verification found
+//! missing or bad, so other ROM tools can go fetch them.
+use crate::models::{DiskEntry, Game, RomEntry};
+use crate::ui::components::rom_verify::{VerificationResult, VerifyStatus};
+use quick_xml::escape::escape;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Which verified games go into a fixdat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FixdatScope {
+    #[default]
+    All,
+    CurrentFilter,
+    Favorites,
+}
+
+impl FixdatScope {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FixdatScope::All => "All games",
+            FixdatScope::CurrentFilter => "Current filter",
+            FixdatScope::Favorites => "Favorites only",
+        }
+    }
+}
+
+/// Build a Logiqx datafile from verification results. Only games listed in
+/// `include` are written when it is given; passed and warning results are skipped.
+pub fn generate_fixdat(
+    dat_name: &str,
+    results: &[VerificationResult],
+    games: &[Game],
+    include: Option<&HashSet<String>>,
+) -> String {
+    let by_name: HashMap<&str, &Game> = games.iter().map(|g| (g.name.as_str(), g)).collect();
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\"?>\n");
+    xml.push_str("<!DOCTYPE datafile PUBLIC \"-//Logiqx//DTD ROM Management Datafile//EN\" \"http://www.logiqx.com/Dats/datafile.dtd\">\n");
+    xml.push_str("<datafile>\n");
+    xml.push_str("\t<header>\n");
+    let _ = writeln!(xml, "\t\t<name>fix_{}</name>", escape(dat_name));
+    let _ = writeln!(
+        xml,
+        "\t\t<description>fix_{} (missing and incorrect files)</description>",
+        escape(dat_name)
+    );
+    let _ = writeln!(
+        xml,
+        "\t\t<version>{}</version>",
+        chrono::Local::now().format("%Y-%m-%d %H:%M")
+    );
+    xml.push_str("\t\t<author>MAMEUIx</author>\n");
+    xml.push_str("\t</header>\n");
+
+    let mut results: Vec<&VerificationResult> = results
+        .iter()
+        .filter(|r| matches!(r.status, VerifyStatus::Failed | VerifyStatus::NotFound))
+        .filter(|r| include.is_none_or(|names| names.contains(&r.game_name)))
+        .collect();
+    results.sort_by(|a, b| a.game_name.cmp(&b.game_name));
+
+    for result in results {
+        let Some(game) = by_name.get(result.game_name.as_str()) else {
+            continue;
+        };
+        let roms = wanted_roms(result, game, &by_name);
+        let disks = wanted_disks(result, game);
+        if roms.is_empty() && disks.is_empty() {
+            continue;
+        }
+
+        let mut attrs = format!("name=\"{}\"", escape(&game.name));
+        if let Some(parent) = &game.rom_set.cloneof {
+            let _ = write!(attrs, " cloneof=\"{}\"", escape(parent));
+        }
+        if let Some(romof) = &game.rom_set.romof {
+            let _ = write!(attrs, " romof=\"{}\"", escape(romof));
+        }
+        let _ = writeln!(xml, "\t<game {}>", attrs);
+        let _ = writeln!(
+            xml,
+            "\t\t<description>{}</description>",
+            escape(&game.description)
+        );
+        for rom in roms {
+            let _ = writeln!(xml, "\t\t{}", rom_element(rom));
+        }
+        for disk in disks {
+            let _ = writeln!(xml, "\t\t{}", disk_element(disk));
+        }
+        xml.push_str("\t</game>\n");
+    }
+
+    xml.push_str("</datafile>\n");
+    xml
+}
+
+/// Manifest entries for the files a result reported as missing or incorrect
+fn wanted_roms<'a>(
+    result: &VerificationResult,
+    game: &'a Game,
+    by_name: &HashMap<&str, &'a Game>,
+) -> Vec<&'a RomEntry> {
+    let dumped = || game.rom_set.dumped_roms().filter(|rom| !rom.optional);
+
+    // MAME only says "romset not found" without naming files
+    if result.status == VerifyStatus::NotFound
+        && result.missing_files.is_empty()
+        && result.incorrect_files.is_empty()
+    {
+        return dumped().collect();
+    }
+
+    let mut roms = Vec::new();
+    for file in result.missing_files.iter().chain(&result.incorrect_files) {
+        // Device ROMs are reported as "<device>/<rom>"
+        let found = match file.split_once('/') {
+            Some((device, name)) => by_name
+                .get(device)
+                .and_then(|device| device.rom_set.dumped_roms().find(|rom| rom.name == name)),
+            None => dumped().find(|rom| &rom.name == file),
+        };
+        if let Some(rom) = found
+            && !roms.contains(&rom)
+        {
+            roms.push(rom);
+        }
+    }
+    roms
+}
+
+fn wanted_disks<'a>(result: &VerificationResult, game: &'a Game) -> Vec<&'a DiskEntry> {
+    let Some(chd_status) = &result.chd_status else {
+        return Vec::new();
+    };
+    let disks: Vec<&DiskEntry> = game
+        .rom_set
+        .dumped_disks()
+        .filter(|disk| !disk.optional)
+        .collect();
+
+    // Prefer the disks named in the status; -verifyroms does not always name them
+    let named_in_status: Vec<&str> = chd_status
+        .split(';')
+        .filter_map(|problem| problem.split_once(':'))
+        .flat_map(|(_, names)| names.split(','))
+        .map(str::trim)
+        .collect();
+    let named: Vec<&DiskEntry> = disks
+        .iter()
+        .copied()
+        .filter(|disk| named_in_status.contains(&disk.name.as_str()))
+        .collect();
+    if named.is_empty() { disks } else { named }
+}
+
+fn rom_element(rom: &RomEntry) -> String {
+    let mut element = format!("<rom name=\"{}\" size=\"{}\"", escape(&rom.name), rom.size);
+    if let Some(crc) = &rom.crc {
+        let _ = write!(element, " crc=\"{}\"", crc);
+    }
+    if let Some(sha1) = &rom.sha1 {
+        let _ = write!(element, " sha1=\"{}\"", sha1);
+    }
+    element.push_str("/>");
+    element
+}
+
+fn disk_element(disk: &DiskEntry) -> String {
+    let mut element = format!("<disk name=\"{}\"", escape(&disk.name));
+    if let Some(sha1) = &disk.sha1 {
+        let _ = write!(element, " sha1=\"{}\"", sha1);
+    }
+    element.push_str("/>");
+    element
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MachineRomSet;
+    use crate::utils::rom_utils::test_support;
+
+    fn rom(name: &str, crc: &str) -> RomEntry {
+        RomEntry {
+            name: name.to_string(),
+            size: 4096,
+            crc: Some(crc.to_string()),
+            sha1: Some(format!("{}sha", crc)),
+            ..Default::default()
+        }
+    }
+
+    fn game(name: &str, rom_set: MachineRomSet) -> Game {
+        Game {
+            description: format!("{} & co", name),
+            ..test_support::game(name, rom_set)
+        }
+    }
+
+    fn result(name: &str, status: VerifyStatus, missing: &[&str], chd: bool) -> VerificationResult {
+        VerificationResult {
+            game_name: name.to_string(),
+            description: String::new(),
+            status,
+            missing_files: missing.iter().map(|s| s.to_string()).collect(),
+            incorrect_files: Vec::new(),
+            extra_files: Vec::new(),
+            chd_status: chd.then(|| "CHD not found: kinst".to_string()),
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn lists_only_missing_entries_in_scope() {
+        let games = vec![
+            game(
+                "pacman",
+                MachineRomSet {
+                    roms: vec![rom("a.bin", "11111111"), rom("b.bin", "22222222")],
+                    ..Default::default()
+                },
+            ),
+            game(
+                "kinst",
+                MachineRomSet {
+                    roms: vec![rom("k.bin", "33333333")],
+                    disks: vec![
+                        DiskEntry {
+                            name: "kinst".to_string(),
+                            sha1: Some("deadbeef".to_string()),
+                            ..Default::default()
+                        },
+                        DiskEntry {
+                            name: "kins".to_string(),
+                            sha1: Some("cafef00d".to_string()),
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+            ),
+            game("good", MachineRomSet::default()),
+        ];
+        let results = vec![
+            result("pacman", VerifyStatus::Failed, &["b.bin"], false),
+            result("kinst", VerifyStatus::Failed, &[], true),
+            result("good", VerifyStatus::Passed, &[], false),
+        ];
+
+        let xml = generate_fixdat("MAME", &results, &games, None);
+        assert!(xml.contains("<name>fix_MAME</name>"));
+        assert!(xml.contains("<description>pacman &amp; co</description>"));
+        assert!(
+            xml.contains(
+                "<rom name=\"b.bin\" size=\"4096\" crc=\"22222222\" sha1=\"22222222sha\"/>"
+            )
+        );
+        assert!(!xml.contains("a.bin"));
+        assert!(xml.contains("<disk name=\"kinst\" sha1=\"deadbeef\"/>"));
+        // Only the disk named in the status, not one whose name is a prefix of it
+        assert!(!xml.contains("<disk name=\"kins\""));
+        assert!(!xml.contains("k.bin"));
+        assert!(!xml.contains("name=\"good\""));
+
+        let favorites: HashSet<String> = ["kinst".to_string()].into();
+        let xml = generate_fixdat("MAME", &results, &games, Some(&favorites));
+        assert!(!xml.contains("name=\"pacman\""));
+        assert!(xml.contains("<game name=\"kinst\">"));
+
+        // The fixdat is well-formed XML
+        let mut reader = quick_xml::Reader::from_str(&xml);
+        loop {
+            match reader.read_event().unwrap() {
+                quick_xml::events::Event::Eof => break,
+                _ => continue,
+            }
+        }
+    }
+}
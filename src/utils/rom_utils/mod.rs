@@ -1,16 +1,162 @@
 // src/rom_utils/mod.rs
-use crate::models::{Game, RomStatus};
-use std::collections::HashMap;
-use std::path::PathBuf; // PERBAIKAN: Hapus 'Path' karena tidak digunakan
+use crate::models::{AppConfig, Game, RomStatus};
+use crate::utils::archive::{self, ARCHIVE_EXTENSIONS, ArchiveEntry};
+use crate::utils::chd;
+use anyhow::{Context, Result};
+use scan_cache::{ArchiveListings, ScanDelta};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use walkdir::WalkDir;
 
+pub mod audit_export;
+pub mod auditor;
+pub mod cleanup;
+pub mod fixdat;
+pub mod rebuilder;
+pub mod samples;
+pub mod scan_cache;
+pub mod set_type;
+pub mod software_audit;
+#[cfg(test)]
+mod test_support;
+pub mod verify_history;
+
+pub use auditor::RomAuditor;
+pub use scan_cache::RomScanCache;
+pub use set_type::{RomSetDetection, detect_rom_set_type};
+
 pub struct RomLoader {
     rom_dirs: Vec<PathBuf>,
+    /// Archive listings from the scan cache, used instead of reopening archives
+    listings: Option<Arc<ArchiveListings>>,
+}
+
+/// Where a ROM file physically lives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RomSource {
+    Archive(PathBuf), // Entry inside a zip/7z
+    File(PathBuf),    // Loose file in a set folder
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocatedRom {
+    pub source: RomSource,
+    pub entry: ArchiveEntry,
 }
 
 impl RomLoader {
     pub fn new(rom_dirs: Vec<PathBuf>) -> Self {
-        Self { rom_dirs }
+        Self {
+            rom_dirs,
+            listings: None,
+        }
+    }
+
+    pub fn with_listings(mut self, listings: Arc<ArchiveListings>) -> Self {
+        self.listings = Some(listings);
+        self
+    }
+
+    pub fn rom_dirs(&self) -> &[PathBuf] {
+        &self.rom_dirs
+    }
+
+    /// Files of a set across every ROM directory: `<set>.zip`, `<set>.7z`
+    /// or a loose `<set>/` folder. Returns None when the set is nowhere.
+    pub fn set_entries(&self, set_name: &str) -> Option<Vec<ArchiveEntry>> {
+        self.locate_set_files(set_name)
+            .map(|files| files.into_iter().map(|file| file.entry).collect())
+    }
+
+    /// Like `set_entries`, but remembers where each file came from so it
+    /// can be read back (used by the rebuilder)
+    pub fn locate_set_files(&self, set_name: &str) -> Option<Vec<LocatedRom>> {
+        let mut files = Vec::new();
+        let mut found = false;
+
+        for dir in &self.rom_dirs {
+            for path in archive::find_archives(dir, set_name) {
+                let listing = match self.listings.as_ref().and_then(|l| l.get(&path)) {
+                    Some(entries) => Ok(entries.clone()),
+                    None => archive::list_entries(&path),
+                };
+                match listing {
+                    Ok(listing) => {
+                        files.extend(listing.into_iter().map(|entry| LocatedRom {
+                            source: RomSource::Archive(path.clone()),
+                            entry,
+                        }));
+                        found = true;
+                    }
+                    Err(e) => eprintln!("Skipping unreadable archive: {:#}", e),
+                }
+            }
+
+            let folder = dir.join(set_name);
+            if folder.is_dir() {
+                match list_folder(&folder) {
+                    Ok(listing) => {
+                        files.extend(listing);
+                        found = true;
+                    }
+                    Err(e) => eprintln!("Skipping unreadable ROM folder: {:#}", e),
+                }
+            }
+        }
+
+        found.then_some(files)
+    }
+
+    /// Find a disk image along a set chain: `<dir>/<set>/<name>.chd` for the
+    /// set and each parent, then `<dir>/<name>.chd`
+    pub fn locate_disk(&self, chain: &[String], names: &[&str]) -> Option<PathBuf> {
+        self.rom_dirs.iter().find_map(|dir| {
+            chain
+                .iter()
+                .flat_map(|set| {
+                    names
+                        .iter()
+                        .map(move |name| dir.join(set).join(format!("{}.chd", name)))
+                })
+                .chain(names.iter().map(|name| dir.join(format!("{}.chd", name))))
+                .find(|path| path.is_file())
+        })
+    }
+
+    /// Status of a game whose ROMs were found, judged by its disks. Disks
+    /// with a known SHA1 are checked against the SHA1 in their CHD header.
+    fn chd_status(&self, game: &Game, available_chds: &HashMap<String, PathBuf>) -> RomStatus {
+        if !game.requires_chd {
+            return RomStatus::Available;
+        }
+        let Some(chd_name) = &game.chd_name else {
+            return RomStatus::ChdRequired;
+        };
+        if !available_chds.contains_key(&chd_name.to_lowercase()) {
+            return RomStatus::ChdMissing;
+        }
+
+        let mut chain = vec![game.name.clone()];
+        chain.extend(game.parent.clone());
+        for disk in game.rom_set.dumped_disks() {
+            let Some(expected) = &disk.sha1 else {
+                continue;
+            };
+            // The set folder wins over a same-named disk elsewhere in the tree
+            let path = self
+                .locate_disk(&chain, &[disk.name.as_str()])
+                .or_else(|| available_chds.get(&disk.name.to_lowercase()).cloned());
+            if let Some(path) = path
+                && !chd::read_header(&path).is_ok_and(|header| &header.sha1 == expected)
+            {
+                return RomStatus::ChdIncorrect;
+            }
+        }
+
+        RomStatus::Available
     }
 
     /// Load ROM dengan scanning directories dan mencocokkan dengan metadata MAME
@@ -52,6 +198,7 @@ impl RomLoader {
         let mut missing_count = 0;
         let mut chd_required_count = 0;
         let mut chd_missing_count = 0;
+        let mut chd_incorrect_count = 0;
 
         // Process games dalam chunks untuk menghindari memory pressure
         for (game_name, mut game) in metadata {
@@ -59,26 +206,13 @@ impl RomLoader {
             let rom_key = game_name.to_lowercase();
 
             if available_roms.contains_key(&rom_key) {
-                // ROM is available, now check if it needs CHD
-                if game.requires_chd {
-                    // Debug output for CHD detection
-
-                    // Check if CHD is available
-                    if let Some(chd_name) = &game.chd_name {
-                        if available_chds.contains_key(&chd_name.to_lowercase()) {
-                            game.status = RomStatus::Available;
-                            found_count += 1;
-                        } else {
-                            game.status = RomStatus::ChdMissing;
-                            chd_missing_count += 1;
-                        }
-                    } else {
-                        game.status = RomStatus::ChdRequired;
-                        chd_required_count += 1;
-                    }
-                } else {
-                    game.status = RomStatus::Available;
-                    found_count += 1;
+                // ROM is available, now check its CHDs
+                game.status = self.chd_status(&game, &available_chds);
+                match game.status {
+                    RomStatus::ChdMissing => chd_missing_count += 1,
+                    RomStatus::ChdIncorrect => chd_incorrect_count += 1,
+                    RomStatus::ChdRequired => chd_required_count += 1,
+                    _ => found_count += 1,
                 }
             } else {
                 game.status = RomStatus::Missing;
@@ -93,6 +227,7 @@ impl RomLoader {
         println!("  - Missing: {} games", missing_count);
         println!("  - CHD Required: {} games", chd_required_count);
         println!("  - CHD Missing: {} games", chd_missing_count);
+        println!("  - CHD Incorrect: {} games", chd_incorrect_count);
         println!("  - Total: {} games", games.len());
 
         // Sort games berdasarkan description untuk display konsisten
@@ -101,6 +236,95 @@ impl RomLoader {
         games
     }
 
+    /// Rescan through the fingerprint cache: only archives whose size or
+    /// mtime changed are re-listed, and games whose files are untouched keep
+    /// the status cached from the previous run.
+    pub fn load_roms_incremental<F>(
+        &self,
+        metadata: HashMap<String, Game>,
+        cache: &mut RomScanCache,
+        progress_callback: F,
+    ) -> Vec<Game>
+    where
+        F: FnMut(usize, usize),
+    {
+        let delta = cache.refresh(progress_callback);
+        println!(
+            "ROM scan cache: {} files re-read, {} unchanged, {} removed",
+            delta.rescanned, delta.unchanged, delta.removed
+        );
+
+        let (available_roms, available_chds) = cached_availability(cache);
+
+        let mut reused = 0;
+        let mut games: Vec<Game> = metadata
+            .into_values()
+            .map(|mut game| {
+                // Restored from the verification history once the scan is done
+                game.verification_status = None;
+                if !delta.touches(&game)
+                    && let Some(cached) = cache.status(&game)
+                {
+                    game.status = cached.status;
+                    reused += 1;
+                } else {
+                    game.status = if available_roms.contains(&game.name.to_lowercase()) {
+                        self.chd_status(&game, &available_chds)
+                    } else {
+                        RomStatus::Missing
+                    };
+                }
+                game
+            })
+            .collect();
+        println!(
+            "ROM scan: {} of {} game statuses reused from cache",
+            reused,
+            games.len()
+        );
+
+        cache.record_statuses(&games);
+        games.sort_by(|a, b| a.description.cmp(&b.description));
+        games
+    }
+
+    /// Recompute the games a watcher delta touches, from the refreshed cache.
+    /// Returns the indices of games whose status or verification changed.
+    pub fn refresh_statuses(
+        &self,
+        games: &mut [Game],
+        cache: &RomScanCache,
+        delta: &ScanDelta,
+        merged: bool,
+    ) -> Vec<usize> {
+        let (available_roms, available_chds) = cached_availability(cache);
+        let mut changed = Vec::new();
+
+        for (idx, game) in games.iter_mut().enumerate() {
+            if !delta.touches(game) {
+                continue;
+            }
+            let status = if available_roms.contains(&game.name.to_lowercase()) {
+                self.chd_status(game, &available_chds)
+            } else {
+                RomStatus::Missing
+            };
+            if status != game.status || game.verification_status.is_some() {
+                game.status = status;
+                // Files changed, so an earlier verification no longer applies
+                game.verification_status = None;
+                changed.push(idx);
+            }
+        }
+
+        if merged {
+            // Clones merged into a parent follow the parent's archive
+            set_type::mark_merged_clones(self, games, &changed);
+        }
+
+        changed
+    }
+
     /// Load ROM dengan progress callback
     /// Versi ini memungkinkan UI menampilkan progress updates
     pub fn load_roms_with_progress<F>(
@@ -142,6 +366,7 @@ impl RomLoader {
         let mut missing_count = 0;
         let mut chd_required_count = 0;
         let mut chd_missing_count = 0;
+        let mut chd_incorrect_count = 0;
 
         let total_games = metadata.len();
         let mut processed = 0;
@@ -150,22 +375,12 @@ impl RomLoader {
             let rom_key = game_name.to_lowercase();
 
             if available_roms.contains_key(&rom_key) {
-                if game.requires_chd {
-                    if let Some(chd_name) = &game.chd_name {
-                        if available_chds.contains_key(&chd_name.to_lowercase()) {
-                            game.status = RomStatus::Available;
-                            found_count += 1;
-                        } else {
-                            game.status = RomStatus::ChdMissing;
-                            chd_missing_count += 1;
-                        }
-                    } else {
-                        game.status = RomStatus::ChdRequired;
-                        chd_required_count += 1;
-                    }
-                } else {
-                    game.status = RomStatus::Available;
-                    found_count += 1;
+                game.status = self.chd_status(&game, &available_chds);
+                match game.status {
+                    RomStatus::ChdMissing => chd_missing_count += 1,
+                    RomStatus::ChdIncorrect => chd_incorrect_count += 1,
+                    RomStatus::ChdRequired => chd_required_count += 1,
+                    _ => found_count += 1,
                 }
             } else {
                 game.status = RomStatus::Missing;
@@ -189,6 +404,7 @@ impl RomLoader {
         println!("  - Missing: {} games", missing_count);
         println!("  - CHD Required: {} games", chd_required_count);
         println!("  - CHD Missing: {} games", chd_missing_count);
+        println!("  - CHD Incorrect: {} games", chd_incorrect_count);
 
         // Sort untuk display
         games.sort_by(|a, b| a.description.cmp(&b.description));
@@ -201,7 +417,7 @@ impl RomLoader {
         let mut rom_files = Vec::new();
 
         // Common ROM file extensions
-        let rom_extensions = ["zip", "7z", "chd"];
+        let rom_extensions: Vec<&str> = ARCHIVE_EXTENSIONS.iter().copied().chain(["chd"]).collect();
 
         for rom_dir in &self.rom_dirs {
             if !rom_dir.exists() {
@@ -259,7 +475,7 @@ impl RomLoader {
         F: FnMut(usize, usize) + Send,
     {
         let mut rom_files = Vec::new();
-        let rom_extensions = ["zip", "7z", "chd"];
+        let rom_extensions: Vec<&str> = ARCHIVE_EXTENSIONS.iter().copied().chain(["chd"]).collect();
         let mut files_scanned = 0;
 
         for rom_dir in &self.rom_dirs {
@@ -467,6 +683,87 @@ impl RomLoader {
     }
 }
 
+/// Set names and CHDs (by lowercase stem) present in the cached file list
+fn cached_availability(cache: &RomScanCache) -> (HashSet<String>, HashMap<String, PathBuf>) {
+    let mut available_roms = HashSet::new();
+    let mut available_chds = HashMap::new();
+    for path in cache.rom_files() {
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("chd"))
+        {
+            available_chds.insert(stem.to_lowercase(), path.to_path_buf());
+        } else {
+            available_roms.insert(stem.to_lowercase());
+        }
+    }
+    (available_roms, available_chds)
+}
+
+/// ROM directories MAME is pointed at (primary plus extra), skipping any that are gone
+pub fn configured_rom_dirs(config: &AppConfig) -> Vec<PathBuf> {
+    config
+        .rom_paths
+        .iter()
+        .chain(config.extra_rom_dirs.iter())
+        .filter(|dir| dir.is_dir())
+        .cloned()
+        .collect()
+}
+
+/// List loose ROM files in a set folder, hashing each one
+fn list_folder(path: &Path) -> Result<Vec<LocatedRom>> {
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+
+    for entry in fs::read_dir(path).with_context(|| format!("Failed to read {}", path.display()))? {
+        let entry = entry?;
+        let file_path = entry.path();
+        // CHDs are matched by name, hashing them would take forever
+        if !file_path.is_file()
+            || file_path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("chd"))
+        {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        let (size, crc) = crc32_file(&file_path)?;
+        entries.push(LocatedRom {
+            source: RomSource::File(file_path),
+            entry: ArchiveEntry { name, size, crc },
+        });
+    }
+
+    Ok(entries)
+}
+
+fn crc32_file(path: &Path) -> Result<(u64, u32)> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buffer = [0u8; 64 * 1024];
+    let mut size = 0u64;
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+    }
+
+    Ok((size, hasher.finalize()))
+}
+
 /// Debug function untuk analisa ROM directories
 pub fn debug_rom_directories(rom_dirs: &[PathBuf]) {
     println!("\n=== ROM Directory Analysis ===");
@@ -0,0 +1,606 @@
+// src/utils/rom_utils/rebuilder.rs
+//! Rebuilds the ROM collection into merged, split or non-merged sets in a
+//! separate output directory. Source files are never modified.
+use super::{LocatedRom, RomLoader, RomSource};
+use crate::models::{Game, MachineRomSet, RomEntry, RomSetType};
+use crate::utils::archive;
+use anyhow::{Context, Result, bail};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+
+#[derive(Debug, Clone)]
+pub struct RebuildOptions {
+    pub target: RomSetType,
+    pub output_dir: PathBuf,
+    pub dry_run: bool, // Only report the planned moves
+}
+
+/// Progress messages from a rebuild job, in the style of `LoadingMessage`
+#[derive(Debug, Clone)]
+pub enum RebuildMessage {
+    Started(usize),                 // Number of sets to write
+    Progress(usize, usize, String), // (done, total, current set)
+    Planned(Vec<String>),           // Dry-run listing of planned moves
+    Complete(RebuildSummary),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RebuildSummary {
+    pub sets_written: usize,
+    pub files_written: usize,
+    pub disks_copied: usize,
+    pub missing_files: usize,
+    pub errors: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// One file of an output set and where it will be taken from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedFile {
+    pub name: String, // Name inside the output archive
+    pub size: u64,
+    pub crc: Option<u32>,
+    pub source: Option<LocatedRom>, // None when no copy was found
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedDisk {
+    pub name: String,
+    pub source: Option<PathBuf>,
+}
+
+/// An output archive (`<output>/<name>.zip`) plus its CHD folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedSet {
+    pub name: String,
+    pub files: Vec<PlannedFile>,
+    pub disks: Vec<PlannedDisk>,
+}
+
+impl PlannedSet {
+    fn has_sources(&self) -> bool {
+        self.files.iter().any(|f| f.source.is_some())
+            || self.disks.iter().any(|d| d.source.is_some())
+    }
+
+    /// Human-readable lines for the dry-run listing
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for file in &self.files {
+            lines.push(match &file.source {
+                Some(located) => format!(
+                    "{}.zip/{} <- {}",
+                    self.name,
+                    file.name,
+                    describe_source(located)
+                ),
+                None => format!("{}.zip/{} <- MISSING", self.name, file.name),
+            });
+        }
+        for disk in &self.disks {
+            lines.push(match &disk.source {
+                Some(path) => format!("{}/{}.chd <- {}", self.name, disk.name, path.display()),
+                None => format!("{}/{}.chd <- MISSING", self.name, disk.name),
+            });
+        }
+        lines
+    }
+}
+
+fn describe_source(located: &LocatedRom) -> String {
+    match &located.source {
+        RomSource::Archive(path) => format!("{}:{}", path.display(), located.entry.name),
+        RomSource::File(path) => path.display().to_string(),
+    }
+}
+
+/// Builds a plan for the target layout and writes it out
+pub struct RomRebuilder {
+    loader: RomLoader,
+    machines: Vec<(String, MachineRomSet)>,
+    by_name: HashMap<String, usize>,
+    sources: HashMap<String, Option<Vec<LocatedRom>>>,
+}
+
+impl RomRebuilder {
+    pub fn new(rom_dirs: Vec<PathBuf>, games: &[Game]) -> Self {
+        let mut machines: Vec<(String, MachineRomSet)> = games
+            .iter()
+            .map(|game| (game.name.clone(), game.rom_set.clone()))
+            .collect();
+        machines.sort_by(|a, b| a.0.cmp(&b.0));
+        let by_name = machines
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| (name.clone(), index))
+            .collect();
+
+        Self {
+            loader: RomLoader::new(rom_dirs),
+            machines,
+            by_name,
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Every output set the target layout calls for that has at least one
+    /// file available somewhere in the ROM paths
+    pub fn plan(&mut self, target: RomSetType) -> Vec<PlannedSet> {
+        let mut clones: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, (_, rom_set)) in self.machines.iter().enumerate() {
+            if let Some(parent) = &rom_set.cloneof
+                && self.by_name.contains_key(parent)
+            {
+                clones.entry(parent.clone()).or_default().push(index);
+            }
+        }
+
+        let mut planned = Vec::new();
+        for index in 0..self.machines.len() {
+            let (name, rom_set) = self.machines[index].clone();
+            let is_clone = rom_set
+                .cloneof
+                .as_ref()
+                .is_some_and(|parent| self.by_name.contains_key(parent));
+
+            // (owner machine, ROM)
+            let mut wanted: Vec<(String, RomEntry)> = Vec::new();
+            let mut disks: Vec<(String, String)> = Vec::new();
+            match target {
+                RomSetType::NonMerged => {
+                    for rom in rom_set.dumped_roms() {
+                        wanted.push((name.clone(), rom.clone()));
+                    }
+                    for disk in rom_set.dumped_disks() {
+                        disks.push((name.clone(), disk.name.clone()));
+                    }
+                }
+                RomSetType::Merged if is_clone => continue,
+                RomSetType::Merged => {
+                    let family = std::iter::once(index)
+                        .chain(clones.get(&name).into_iter().flatten().copied());
+                    for member in family {
+                        let (member_name, member_set) = &self.machines[member];
+                        for rom in member_set.dumped_roms().filter(|rom| rom.merge.is_none()) {
+                            wanted.push((member_name.clone(), rom.clone()));
+                        }
+                        for disk in member_set.dumped_disks().filter(|d| d.merge.is_none()) {
+                            disks.push((member_name.clone(), disk.name.clone()));
+                        }
+                    }
+                }
+                RomSetType::Split | RomSetType::Unknown => {
+                    for rom in rom_set.dumped_roms().filter(|rom| rom.merge.is_none()) {
+                        wanted.push((name.clone(), rom.clone()));
+                    }
+                    for disk in rom_set.dumped_disks().filter(|d| d.merge.is_none()) {
+                        disks.push((name.clone(), disk.name.clone()));
+                    }
+                }
+            }
+
+            let set = self.plan_set(&name, wanted, disks);
+            if set.has_sources() {
+                planned.push(set);
+            }
+        }
+
+        planned
+    }
+
+    fn plan_set(
+        &mut self,
+        name: &str,
+        wanted: Vec<(String, RomEntry)>,
+        disks: Vec<(String, String)>,
+    ) -> PlannedSet {
+        let mut files: Vec<PlannedFile> = Vec::new();
+        let mut seen_crcs = HashSet::new();
+
+        for (owner, rom) in wanted {
+            let crc = rom
+                .crc
+                .as_deref()
+                .and_then(|crc| u32::from_str_radix(crc, 16).ok());
+            // Identical dumps shared by several clones are stored once
+            if let Some(crc) = crc
+                && !seen_crcs.insert((crc, rom.size))
+                && owner != name
+            {
+                continue;
+            }
+            // Different dumps under the same name go in a per-clone folder
+            let mut entry_name = rom.name.clone();
+            if files
+                .iter()
+                .any(|f| f.name.eq_ignore_ascii_case(&entry_name))
+            {
+                entry_name = format!("{}/{}", owner, rom.name);
+            }
+
+            let source = self.find_source(&owner, &rom, crc);
+            files.push(PlannedFile {
+                name: entry_name,
+                size: rom.size,
+                crc,
+                source,
+            });
+        }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let disks = disks
+            .into_iter()
+            .map(|(owner, disk)| PlannedDisk {
+                source: self.find_disk(&owner, &disk),
+                name: disk,
+            })
+            .collect();
+
+        PlannedSet {
+            name: name.to_string(),
+            files,
+            disks,
+        }
+    }
+
+    /// Sets that may hold a machine's files: itself, then parent and BIOS
+    fn chain(&self, name: &str) -> Vec<String> {
+        let mut chain = vec![name.to_string()];
+        let mut next = self.romof(name);
+        while let Some(parent) = next {
+            if chain.contains(&parent) {
+                break;
+            }
+            next = self.romof(&parent);
+            chain.push(parent);
+        }
+        chain
+    }
+
+    fn romof(&self, name: &str) -> Option<String> {
+        let (_, rom_set) = &self.machines[*self.by_name.get(name)?];
+        rom_set.romof.clone().or_else(|| rom_set.cloneof.clone())
+    }
+
+    fn find_source(&mut self, owner: &str, rom: &RomEntry, crc: Option<u32>) -> Option<LocatedRom> {
+        for set in self.chain(owner) {
+            if !self.sources.contains_key(&set) {
+                let located = self.loader.locate_set_files(&set);
+                self.sources.insert(set.clone(), located);
+            }
+            let Some(Some(files)) = self.sources.get(&set) else {
+                continue;
+            };
+
+            let found = match crc {
+                Some(crc) => files
+                    .iter()
+                    .find(|f| f.entry.crc == crc && f.entry.size == rom.size),
+                None => files.iter().find(|f| {
+                    f.entry.name.eq_ignore_ascii_case(&rom.name) && f.entry.size == rom.size
+                }),
+            };
+            if let Some(found) = found {
+                return Some(found.clone());
+            }
+        }
+        None
+    }
+
+    fn find_disk(&self, owner: &str, disk: &str) -> Option<PathBuf> {
+        let file_name = format!("{}.chd", disk);
+        let chain = self.chain(owner);
+        self.loader.rom_dirs().iter().find_map(|dir| {
+            chain
+                .iter()
+                .map(|set| dir.join(set).join(&file_name))
+                .chain(std::iter::once(dir.join(&file_name)))
+                .find(|path| path.is_file())
+        })
+    }
+
+    /// Plan and, unless this is a dry run, write every set into the output directory
+    pub fn rebuild<F>(
+        &mut self,
+        options: &RebuildOptions,
+        mut progress: F,
+    ) -> Result<RebuildSummary>
+    where
+        F: FnMut(RebuildMessage),
+    {
+        let output_dir = &options.output_dir;
+        for rom_dir in self.loader.rom_dirs() {
+            if same_dir(rom_dir, output_dir) {
+                bail!(
+                    "Output directory {} is also a ROM path; choose an empty folder",
+                    output_dir.display()
+                );
+            }
+        }
+
+        let plan = self.plan(options.target);
+        let mut summary = RebuildSummary {
+            dry_run: options.dry_run,
+            missing_files: plan
+                .iter()
+                .flat_map(|set| &set.files)
+                .filter(|file| file.source.is_none())
+                .count(),
+            ..Default::default()
+        };
+        progress(RebuildMessage::Started(plan.len()));
+
+        if options.dry_run {
+            progress(RebuildMessage::Planned(
+                plan.iter().flat_map(PlannedSet::describe).collect(),
+            ));
+            return Ok(summary);
+        }
+
+        fs::create_dir_all(output_dir)
+            .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+
+        for (done, set) in plan.iter().enumerate() {
+            progress(RebuildMessage::Progress(done, plan.len(), set.name.clone()));
+            match write_set(set, output_dir) {
+                Ok((files, disks)) => {
+                    summary.sets_written += 1;
+                    summary.files_written += files;
+                    summary.disks_copied += disks;
+                }
+                Err(e) => summary.errors.push(format!("{}: {:#}", set.name, e)),
+            }
+        }
+        progress(RebuildMessage::Progress(
+            plan.len(),
+            plan.len(),
+            String::new(),
+        ));
+
+        Ok(summary)
+    }
+}
+
+/// Run a rebuild on a background thread and stream its progress
+pub fn spawn_rebuild(
+    rom_dirs: Vec<PathBuf>,
+    games: Vec<Game>,
+    options: RebuildOptions,
+) -> mpsc::Receiver<RebuildMessage> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut rebuilder = RomRebuilder::new(rom_dirs, &games);
+        let progress_tx = tx.clone();
+        match rebuilder.rebuild(&options, |msg| {
+            let _ = progress_tx.send(msg);
+        }) {
+            Ok(summary) => {
+                let _ = tx.send(RebuildMessage::Complete(summary));
+            }
+            Err(e) => {
+                let _ = tx.send(RebuildMessage::Failed(format!("{:#}", e)));
+            }
+        }
+    });
+
+    rx
+}
+
+/// Write one output set; returns (files written, disks copied)
+fn write_set(set: &PlannedSet, output_dir: &Path) -> Result<(usize, usize)> {
+    let mut data_by_crc: HashMap<(u32, u64), Vec<u8>> = HashMap::new();
+    let mut by_name: HashMap<String, Vec<u8>> = HashMap::new();
+
+    // Read each source archive once, pulling every file this set needs from it
+    let mut archives: HashMap<&Path, Vec<&LocatedRom>> = HashMap::new();
+    for located in set.files.iter().filter_map(|f| f.source.as_ref()) {
+        match &located.source {
+            RomSource::Archive(path) => archives.entry(path.as_path()).or_default().push(located),
+            RomSource::File(path) => {
+                let data =
+                    fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+                data_by_crc.insert((located.entry.crc, located.entry.size), data.clone());
+                by_name.insert(located.entry.name.clone(), data);
+            }
+        }
+    }
+    for (path, wanted) in archives {
+        for (entry, data) in
+            archive::read_entries(path, |entry| wanted.iter().any(|w| &w.entry == entry))?
+        {
+            data_by_crc.insert((entry.crc, entry.size), data.clone());
+            by_name.insert(entry.name, data);
+        }
+    }
+
+    let mut files_written = 0;
+    let files: Vec<(&PlannedFile, &Vec<u8>)> = set
+        .files
+        .iter()
+        .filter_map(|file| {
+            let located = file.source.as_ref()?;
+            let data = data_by_crc
+                .get(&(located.entry.crc, located.entry.size))
+                .or_else(|| by_name.get(&located.entry.name))?;
+            Some((file, data))
+        })
+        .collect();
+
+    if !files.is_empty() {
+        let zip_path = output_dir.join(format!("{}.zip", set.name));
+        let tmp_path = zip_path.with_extension("zip.tmp");
+        let mut writer = zip::ZipWriter::new(
+            File::create(&tmp_path)
+                .with_context(|| format!("Failed to create {}", tmp_path.display()))?,
+        );
+        for (file, data) in files {
+            writer.start_file(file.name.as_str(), zip::write::SimpleFileOptions::default())?;
+            writer.write_all(data)?;
+            files_written += 1;
+        }
+        writer.finish()?;
+        fs::rename(&tmp_path, &zip_path)
+            .with_context(|| format!("Failed to replace {}", zip_path.display()))?;
+    }
+
+    let mut disks_copied = 0;
+    for disk in &set.disks {
+        let Some(source) = &disk.source else {
+            continue;
+        };
+        let target_dir = output_dir.join(&set.name);
+        fs::create_dir_all(&target_dir)?;
+        fs::copy(source, target_dir.join(format!("{}.chd", disk.name)))
+            .with_context(|| format!("Failed to copy {}", source.display()))?;
+        disks_copied += 1;
+    }
+
+    Ok((files_written, disks_copied))
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::rom_utils::test_support::{family_game, merged_rom, rom};
+
+    fn zip_names(path: &Path) -> Vec<String> {
+        let mut names: Vec<String> = zip::ZipArchive::new(File::open(path).unwrap())
+            .unwrap()
+            .file_names()
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn sample_collection() -> (tempfile::TempDir, Vec<Game>) {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = zip::ZipWriter::new(File::create(dir.path().join("pacman.zip")).unwrap());
+        for (name, data) in [("main.bin", &b"parent"[..]), ("gfx.bin", b"gfx")] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap();
+
+        fs::create_dir(dir.path().join("puckman")).unwrap();
+        fs::write(dir.path().join("puckman").join("main.bin"), b"clone").unwrap();
+
+        let games = vec![
+            family_game(
+                "pacman",
+                None,
+                vec![rom("main.bin", b"parent"), rom("gfx.bin", b"gfx")],
+            ),
+            family_game(
+                "puckman",
+                Some("pacman"),
+                vec![
+                    rom("main.bin", b"clone"),
+                    merged_rom("gfx.bin", b"gfx", "gfx.bin"),
+                ],
+            ),
+        ];
+        (dir, games)
+    }
+
+    #[test]
+    fn rebuilds_each_layout() {
+        let (roms, games) = sample_collection();
+
+        let expected = [
+            (
+                RomSetType::Merged,
+                vec![("pacman", vec!["gfx.bin", "main.bin", "puckman/main.bin"])],
+            ),
+            (
+                RomSetType::Split,
+                vec![
+                    ("pacman", vec!["gfx.bin", "main.bin"]),
+                    ("puckman", vec!["main.bin"]),
+                ],
+            ),
+            (
+                RomSetType::NonMerged,
+                vec![
+                    ("pacman", vec!["gfx.bin", "main.bin"]),
+                    ("puckman", vec!["gfx.bin", "main.bin"]),
+                ],
+            ),
+        ];
+
+        for (target, sets) in expected {
+            let output = tempfile::tempdir().unwrap();
+            let mut rebuilder = RomRebuilder::new(vec![roms.path().to_path_buf()], &games);
+            let options = RebuildOptions {
+                target,
+                output_dir: output.path().to_path_buf(),
+                dry_run: false,
+            };
+            let summary = rebuilder.rebuild(&options, |_| {}).unwrap();
+            assert!(summary.errors.is_empty(), "{:?}", summary.errors);
+            assert_eq!(summary.sets_written, sets.len(), "{:?}", target);
+
+            for (set, names) in sets {
+                assert_eq!(
+                    zip_names(&output.path().join(format!("{}.zip", set))),
+                    names
+                );
+            }
+            if target == RomSetType::Merged {
+                assert!(!output.path().join("puckman.zip").exists());
+            }
+        }
+    }
+
+    #[test]
+    fn dry_run_lists_moves_without_writing() {
+        let (roms, games) = sample_collection();
+        let output = tempfile::tempdir().unwrap();
+        let mut rebuilder = RomRebuilder::new(vec![roms.path().to_path_buf()], &games);
+        let options = RebuildOptions {
+            target: RomSetType::NonMerged,
+            output_dir: output.path().join("out"),
+            dry_run: true,
+        };
+
+        let mut planned = Vec::new();
+        let summary = rebuilder
+            .rebuild(&options, |msg| {
+                if let RebuildMessage::Planned(lines) = msg {
+                    planned = lines;
+                }
+            })
+            .unwrap();
+
+        assert!(summary.dry_run);
+        assert_eq!(planned.len(), 4);
+        assert!(
+            planned
+                .iter()
+                .any(|line| line.starts_with("puckman.zip/gfx.bin <- ")
+                    && line.contains("pacman.zip:gfx.bin"))
+        );
+        assert!(!output.path().join("out").exists());
+
+        let same_dir = RebuildOptions {
+            output_dir: roms.path().to_path_buf(),
+            ..options
+        };
+        assert!(rebuilder.rebuild(&same_dir, |_| {}).is_err());
+    }
+}
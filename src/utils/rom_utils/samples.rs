@@ -0,0 +1,178 @@
+// src/utils/rom_utils/samples.rs
+//! Audits the sample sets (`<sample>` in -listxml) against the configured
+//! sample folders. MAME looks for a game's samples in `<set>.zip`, `<set>.7z`
+//! or a `<set>/` folder, trying the game's own name before its `sampleof`.
+use crate::models::{Game, SampleStatus};
+use crate::utils::archive::{self, ARCHIVE_EXTENSIONS};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sample names found on disk, keyed by lowercase set name
+pub struct SampleAuditor {
+    sets: HashMap<String, HashSet<String>>,
+}
+
+impl SampleAuditor {
+    /// Index every sample set under the given folders
+    pub fn new(sample_paths: &[PathBuf]) -> Self {
+        let mut sets: HashMap<String, HashSet<String>> = HashMap::new();
+        for dir in sample_paths {
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if let Some((set, names)) = Self::read_set(&path) {
+                    sets.entry(set).or_default().extend(names);
+                }
+            }
+        }
+        Self { sets }
+    }
+
+    fn read_set(path: &Path) -> Option<(String, Vec<String>)> {
+        if path.is_dir() {
+            let set = path.file_name()?.to_str()?.to_lowercase();
+            let names = fs::read_dir(path)
+                .ok()?
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str().map(sample_name))
+                .collect();
+            return Some((set, names));
+        }
+
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if !ARCHIVE_EXTENSIONS.contains(&extension.as_str()) {
+            return None;
+        }
+        let set = path.file_stem()?.to_str()?.to_lowercase();
+        match archive::list_entries(path) {
+            Ok(entries) => Some((
+                set,
+                entries
+                    .iter()
+                    .map(|entry| sample_name(&entry.name))
+                    .collect(),
+            )),
+            Err(e) => {
+                eprintln!("Failed to read sample set {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Sample status of one game
+    pub fn status(&self, game: &Game) -> SampleStatus {
+        let wanted = &game.rom_set.samples;
+        if wanted.is_empty() {
+            return SampleStatus::NotRequired;
+        }
+
+        let mut search = vec![game.name.to_lowercase()];
+        if let Some(sampleof) = &game.rom_set.sampleof {
+            search.push(sampleof.to_lowercase());
+        }
+        let sets: Vec<&HashSet<String>> =
+            search.iter().filter_map(|set| self.sets.get(set)).collect();
+
+        let found = wanted
+            .iter()
+            .filter(|sample| {
+                let name = sample.to_lowercase();
+                sets.iter().any(|set| set.contains(&name))
+            })
+            .count();
+
+        if found == wanted.len() {
+            SampleStatus::Available
+        } else if found > 0 {
+            SampleStatus::Incomplete
+        } else {
+            SampleStatus::Missing
+        }
+    }
+}
+
+/// File name inside a sample set without its `.wav` extension, lowercased
+fn sample_name(file_name: &str) -> String {
+    let lower = file_name.to_lowercase();
+    match lower.strip_suffix(".wav") {
+        Some(stem) => stem.to_string(),
+        None => lower,
+    }
+}
+
+/// Fill in `sample_status` for every game. Returns how many games need
+/// samples and have none or only part of them.
+pub fn audit_samples(games: &mut [Game], sample_paths: &[PathBuf]) -> usize {
+    let auditor = SampleAuditor::new(sample_paths);
+    let mut missing = 0;
+    for game in games.iter_mut() {
+        game.sample_status = auditor.status(game);
+        if game.sample_status.is_missing() {
+            missing += 1;
+        }
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MachineRomSet, RomStatus};
+    use crate::utils::rom_utils::test_support;
+    use std::fs::File;
+    use std::io::Write;
+
+    fn game(name: &str, sampleof: Option<&str>, samples: &[&str]) -> Game {
+        Game {
+            status: RomStatus::Available,
+            ..test_support::game(
+                name,
+                MachineRomSet {
+                    sampleof: sampleof.map(str::to_string),
+                    samples: samples.iter().map(|s| s.to_string()).collect(),
+                    ..Default::default()
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn finds_samples_in_archives_folders_and_sampleof_sets() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = zip::ZipWriter::new(File::create(dir.path().join("zaxxon.zip")).unwrap());
+        for name in ["03.WAV", "05.wav"] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(b"RIFF").unwrap();
+        }
+        writer.finish().unwrap();
+        fs::create_dir(dir.path().join("invaders")).unwrap();
+        fs::write(dir.path().join("invaders").join("1.wav"), b"RIFF").unwrap();
+
+        let mut games = vec![
+            game("zaxxon", None, &["03", "05"]),
+            game("szaxxon", Some("zaxxon"), &["03", "05"]),
+            game("invaders", None, &["1", "2"]),
+            game("spacfury", Some("spacfury"), &["craft"]),
+            game("pacman", None, &[]),
+        ];
+        let missing = audit_samples(&mut games, &[dir.path().to_path_buf()]);
+
+        let statuses: Vec<SampleStatus> = games.iter().map(|g| g.sample_status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                SampleStatus::Available,
+                SampleStatus::Available,
+                SampleStatus::Incomplete,
+                SampleStatus::Missing,
+                SampleStatus::NotRequired,
+            ]
+        );
+        assert_eq!(missing, 2);
+    }
+}
@@ -0,0 +1,582 @@
+// src/utils/rom_utils/scan_cache.rs
+//! Persisted fingerprints (path, size, mtime) and archive CRC listings of the
+//! ROM directories, so a rescan only re-reads the archives that changed, plus
+//! the per-game statuses those files produced. Verification results are kept
+//! by `verify_history`, which knows what they were checked against.
+use crate::models::{Game, MachineRomSet, RomStatus};
+use crate::utils::archive::{self, ARCHIVE_EXTENSIONS, ArchiveEntry, ArchiveKind};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+/// Bump whenever the cache layout changes so stale files are ignored.
+const SCAN_CACHE_FORMAT_VERSION: u32 = 2;
+
+/// CRC listings of every archive in the ROM directories, keyed by path
+pub type ArchiveListings = HashMap<PathBuf, Vec<ArchiveEntry>>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileFingerprint {
+    size: u64,
+    mtime: u64,
+    /// Archive contents; None for CHDs and unreadable archives
+    entries: Option<Vec<ArchiveEntry>>,
+}
+
+/// Status a game had after the last scan or verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedStatus {
+    pub status: RomStatus,
+    /// CRC32 of the ROM manifest the status was computed against, so a new
+    /// MAME version with different requirements invalidates it
+    manifest: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FingerprintFile {
+    format_version: u32,
+    rom_dirs: Vec<PathBuf>,
+    files: HashMap<PathBuf, FileFingerprint>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StatusFile {
+    format_version: u32,
+    rom_dirs: Vec<PathBuf>,
+    statuses: HashMap<String, CachedStatus>,
+}
+
+/// What a refresh found compared to the previous scan
+#[derive(Debug, Default)]
+pub struct ScanDelta {
+    /// Lowercase names of sets whose archives or CHDs were added, changed or removed
+    pub changed_sets: HashSet<String>,
+    pub rescanned: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    /// Nothing was cached, so every set counts as changed
+    pub full: bool,
+}
+
+impl ScanDelta {
+    /// Whether any file this game loads from changed since the last scan
+    pub fn touches(&self, game: &Game) -> bool {
+        self.full
+            || loaded_sets(game)
+                .iter()
+                .any(|name| self.changed_sets.contains(name))
+    }
+}
+
+/// Scan cache for one set of ROM directories, stored next to the listxml cache
+#[derive(Debug, Clone)]
+pub struct RomScanCache {
+    cache_dir: PathBuf,
+    rom_dirs: Vec<PathBuf>,
+    files: HashMap<PathBuf, FileFingerprint>,
+    statuses: HashMap<String, CachedStatus>,
+}
+
+impl RomScanCache {
+    /// Cache under the user's cache directory (e.g. `~/.cache/mameuix`)
+    pub fn load(rom_dirs: Vec<PathBuf>) -> Option<Self> {
+        dirs::cache_dir().map(|dir| Self::load_in(dir.join("mameuix"), rom_dirs))
+    }
+
+    /// Restore the cache for these ROM directories, starting empty when there
+    /// is none or it was written for other directories
+    pub fn load_in(cache_dir: PathBuf, rom_dirs: Vec<PathBuf>) -> Self {
+        let mut cache = Self {
+            cache_dir,
+            rom_dirs,
+            files: HashMap::new(),
+            statuses: HashMap::new(),
+        };
+
+        if let Some(file) = cache.read_json::<FingerprintFile>(&cache.file_name("romscan"))
+            && file.format_version == SCAN_CACHE_FORMAT_VERSION
+            && file.rom_dirs == cache.rom_dirs
+        {
+            cache.files = file.files;
+        }
+        if let Some(file) = cache.read_json::<StatusFile>(&cache.file_name("romstatus"))
+            && file.format_version == SCAN_CACHE_FORMAT_VERSION
+            && file.rom_dirs == cache.rom_dirs
+        {
+            cache.statuses = file.statuses;
+        }
+
+        cache
+    }
+
+    pub fn rom_dirs(&self) -> &[PathBuf] {
+        &self.rom_dirs
+    }
+
+    /// Walk the ROM directories and re-list only archives whose size or
+    /// modification time changed. Progress is reported as (files seen, 0).
+    pub fn refresh<F>(&mut self, mut progress: F) -> ScanDelta
+    where
+        F: FnMut(usize, usize),
+    {
+        let mut delta = ScanDelta {
+            full: self.files.is_empty(),
+            ..Default::default()
+        };
+        let mut seen = HashSet::new();
+        let mut files_scanned = 0;
+
+        for rom_dir in &self.rom_dirs {
+            if !rom_dir.is_dir() {
+                continue;
+            }
+
+            for entry in WalkDir::new(rom_dir)
+                .max_depth(3)
+                .follow_links(true)
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                let path = entry.path();
+                if !is_rom_file(path) {
+                    continue;
+                }
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                if !metadata.is_file() {
+                    continue;
+                }
+
+                files_scanned += 1;
+                if files_scanned % 100 == 0 {
+                    progress(files_scanned, 0);
+                }
+
+                seen.insert(path.to_path_buf());
+                update_fingerprint(&mut self.files, rom_dir, path, &metadata, &mut delta);
+            }
+        }
+
+        let removed: Vec<PathBuf> = self
+            .files
+            .keys()
+            .filter(|path| !seen.contains(*path))
+            .cloned()
+            .collect();
+        for path in removed {
+            self.files.remove(&path);
+            delta.removed += 1;
+            if let Some(rom_dir) = self.rom_dirs.iter().find(|dir| path.starts_with(dir)) {
+                delta.changed_sets.extend(set_names(rom_dir, &path));
+            }
+        }
+
+        progress(files_scanned, files_scanned);
+        delta
+    }
+
+    /// Refresh only the given paths, as reported by the folder watcher. A
+    /// path can be a ROM file or a folder that appeared or disappeared, and
+    /// paths outside the ROM directories are ignored.
+    pub fn refresh_paths(&mut self, paths: &[PathBuf]) -> ScanDelta {
+        let mut delta = ScanDelta::default();
+
+        for path in paths {
+            let Some(rom_dir) = self.rom_dirs.iter().find(|dir| path.starts_with(dir)) else {
+                continue;
+            };
+
+            let mut seen = HashSet::new();
+            if path.is_dir() {
+                let depth =
+                    3usize.saturating_sub(path.components().count() - rom_dir.components().count());
+                for entry in WalkDir::new(path)
+                    .max_depth(depth)
+                    .follow_links(true)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                {
+                    if is_rom_file(entry.path())
+                        && let Ok(metadata) = entry.metadata()
+                        && metadata.is_file()
+                    {
+                        seen.insert(entry.path().to_path_buf());
+                        update_fingerprint(
+                            &mut self.files,
+                            rom_dir,
+                            entry.path(),
+                            &metadata,
+                            &mut delta,
+                        );
+                    }
+                }
+            } else if is_rom_file(path)
+                && let Ok(metadata) = fs::metadata(path)
+                && metadata.is_file()
+            {
+                seen.insert(path.clone());
+                update_fingerprint(&mut self.files, rom_dir, path, &metadata, &mut delta);
+            }
+
+            // Anything cached at or below this path that is no longer there
+            let removed: Vec<PathBuf> = self
+                .files
+                .keys()
+                .filter(|file| file.starts_with(path) && !seen.contains(*file))
+                .cloned()
+                .collect();
+            for file in removed {
+                self.files.remove(&file);
+                delta.removed += 1;
+                delta.changed_sets.extend(set_names(rom_dir, &file));
+            }
+        }
+
+        delta
+    }
+
+    /// Every ROM archive and CHD found by the last refresh
+    pub fn rom_files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// CRC listings for `RomLoader::with_listings`
+    pub fn listings(&self) -> ArchiveListings {
+        self.files
+            .iter()
+            .filter_map(|(path, file)| Some((path.clone(), file.entries.clone()?)))
+            .collect()
+    }
+
+    /// CRC32 over the paths, sizes and modification times of the cached
+    /// archives and CHDs each game loads from, keyed by game name. Stored
+    /// verification results are only restored while it is unchanged.
+    pub fn set_fingerprints(&self, games: &[Game]) -> HashMap<String, u32> {
+        let mut by_set: HashMap<String, Vec<&PathBuf>> = HashMap::new();
+        for path in self.files.keys() {
+            if let Some(rom_dir) = self.rom_dirs.iter().find(|dir| path.starts_with(dir)) {
+                for name in set_names(rom_dir, path) {
+                    by_set.entry(name).or_default().push(path);
+                }
+            }
+        }
+        for paths in by_set.values_mut() {
+            paths.sort();
+        }
+
+        games
+            .iter()
+            .map(|game| {
+                let mut hasher = crc32fast::Hasher::new();
+                for name in loaded_sets(game) {
+                    for path in by_set.get(&name).into_iter().flatten() {
+                        let file = &self.files[*path];
+                        hasher.update(path.to_string_lossy().as_bytes());
+                        hasher.update(&file.size.to_le_bytes());
+                        hasher.update(&file.mtime.to_le_bytes());
+                    }
+                }
+                (game.name.clone(), hasher.finalize())
+            })
+            .collect()
+    }
+
+    /// Cached status of a game, if it was computed against the same manifest
+    pub fn status(&self, game: &Game) -> Option<CachedStatus> {
+        self.statuses
+            .get(&game.name)
+            .filter(|cached| cached.manifest == manifest_hash(&game.rom_set))
+            .copied()
+    }
+
+    /// Remember the current status of every game
+    pub fn record_statuses(&mut self, games: &[Game]) {
+        self.statuses = games
+            .iter()
+            .map(|game| {
+                (
+                    game.name.clone(),
+                    CachedStatus {
+                        status: game.status,
+                        manifest: manifest_hash(&game.rom_set),
+                    },
+                )
+            })
+            .collect();
+    }
+
+    /// Write fingerprints, listings and statuses
+    pub fn save(&self) -> Result<()> {
+        self.write_json(
+            &self.file_name("romscan"),
+            &FingerprintFile {
+                format_version: SCAN_CACHE_FORMAT_VERSION,
+                rom_dirs: self.rom_dirs.clone(),
+                files: self.files.clone(),
+            },
+        )?;
+        self.save_statuses()
+    }
+
+    /// Write only the statuses, e.g. after a verification run
+    pub fn save_statuses(&self) -> Result<()> {
+        self.write_json(
+            &self.file_name("romstatus"),
+            &StatusFile {
+                format_version: SCAN_CACHE_FORMAT_VERSION,
+                rom_dirs: self.rom_dirs.clone(),
+                statuses: self.statuses.clone(),
+            },
+        )
+    }
+
+    /// Files are named by a CRC32 of the ROM directories. Unlike
+    /// `DefaultHasher` it stays the same across Rust releases.
+    fn file_name(&self, prefix: &str) -> String {
+        let mut hasher = crc32fast::Hasher::new();
+        for dir in &self.rom_dirs {
+            hasher.update(dir.to_string_lossy().as_bytes());
+            hasher.update(b"\0");
+        }
+        format!("{}-{:08x}.json", prefix, hasher.finalize())
+    }
+
+    fn read_json<T: for<'de> Deserialize<'de>>(&self, file_name: &str) -> Option<T> {
+        let path = self.cache_dir.join(file_name);
+        let contents = fs::read(&path).ok()?;
+        serde_json::from_slice(&contents)
+            .map_err(|e| {
+                eprintln!(
+                    "Ignoring unreadable ROM scan cache {}: {}",
+                    path.display(),
+                    e
+                )
+            })
+            .ok()
+    }
+
+    fn write_json<T: Serialize>(&self, file_name: &str, value: &T) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir).with_context(|| {
+            format!(
+                "Failed to create cache directory {}",
+                self.cache_dir.display()
+            )
+        })?;
+
+        // Write to a temp file first so a crash mid-write never leaves a torn cache.
+        let path = self.cache_dir.join(file_name);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec(value)?)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+}
+
+fn is_rom_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ARCHIVE_EXTENSIONS
+                .iter()
+                .chain(&["chd"])
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Store the fingerprint of a ROM file, re-listing it when its size or
+/// modification time differs from the cached one
+fn update_fingerprint(
+    files: &mut HashMap<PathBuf, FileFingerprint>,
+    rom_dir: &Path,
+    path: &Path,
+    metadata: &fs::Metadata,
+    delta: &mut ScanDelta,
+) {
+    let size = metadata.len();
+    let mtime = mtime_nanos(metadata).unwrap_or(0);
+
+    if let Some(cached) = files.get(path)
+        && cached.size == size
+        && cached.mtime == mtime
+    {
+        delta.unchanged += 1;
+        return;
+    }
+
+    let entries = ArchiveKind::from_path(path).and_then(|_| {
+        archive::list_entries(path)
+            .map_err(|e| eprintln!("Skipping unreadable archive: {:#}", e))
+            .ok()
+    });
+    files.insert(
+        path.to_path_buf(),
+        FileFingerprint {
+            size,
+            mtime,
+            entries,
+        },
+    );
+    delta.rescanned += 1;
+    delta.changed_sets.extend(set_names(rom_dir, path));
+}
+
+/// Sets a file can belong to: its own stem, and the set folder it sits in
+/// (`<dir>/<set>/<disk>.chd`)
+pub fn set_names(rom_dir: &Path, path: &Path) -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+        names.push(stem.to_lowercase());
+    }
+    if let Some(parent) = path.parent()
+        && parent != rom_dir
+        && let Some(folder) = parent.file_name().and_then(|name| name.to_str())
+    {
+        names.push(folder.to_lowercase());
+    }
+    names
+}
+
+/// Lowercase names of the sets a game loads files from: its own, its
+/// parent's, its BIOS's and its disk's
+fn loaded_sets(game: &Game) -> BTreeSet<String> {
+    [
+        Some(&game.name),
+        game.parent.as_ref(),
+        game.rom_set.romof.as_ref(),
+        game.chd_name.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|name| name.to_lowercase())
+    .collect()
+}
+
+fn manifest_hash(rom_set: &MachineRomSet) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    let mut field = |bytes: &[u8]| {
+        hasher.update(bytes);
+        hasher.update(b"\0");
+    };
+    for rom in &rom_set.roms {
+        field(rom.name.as_bytes());
+        field(&rom.size.to_le_bytes());
+        field(rom.crc.as_deref().unwrap_or_default().as_bytes());
+    }
+    for disk in &rom_set.disks {
+        field(disk.name.as_bytes());
+        field(disk.sha1.as_deref().unwrap_or_default().as_bytes());
+    }
+    hasher.finalize()
+}
+
+fn mtime_nanos(metadata: &fs::Metadata) -> Option<u64> {
+    metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::VerificationStatus;
+    use crate::utils::rom_utils::RomLoader;
+    use crate::utils::rom_utils::test_support::{game, write_zip};
+
+    fn scan(cache: &mut RomScanCache) -> HashMap<String, Game> {
+        let metadata = ["pacman", "galaga"]
+            .into_iter()
+            .map(|name| (name.to_string(), game(name, MachineRomSet::default())))
+            .collect();
+        RomLoader::new(cache.rom_dirs().to_vec())
+            .load_roms_incremental(metadata, cache, |_, _| {})
+            .into_iter()
+            .map(|game| (game.name.clone(), game))
+            .collect()
+    }
+
+    #[test]
+    fn rescans_only_changed_archives_and_keeps_statuses() {
+        let roms = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let rom_dirs = vec![roms.path().to_path_buf()];
+        write_zip(roms.path(), "pacman", &[("a.bin", b"pacman")]);
+        write_zip(roms.path(), "galaga", &[("a.bin", b"galaga")]);
+
+        let mut cache = RomScanCache::load_in(cache_dir.path().to_path_buf(), rom_dirs.clone());
+        let mut games = scan(&mut cache);
+        assert_eq!(games["pacman"].status, RomStatus::Available);
+        assert_eq!(cache.listings().len(), 2);
+
+        // Statuses survive a restart; verification results are left to
+        // the verification history
+        for game in games.values_mut() {
+            game.verification_status = Some(VerificationStatus::Verified);
+        }
+        cache.record_statuses(&games.into_values().collect::<Vec<_>>());
+        cache.save().unwrap();
+
+        fs::remove_file(roms.path().join("galaga.zip")).unwrap();
+        let mut cache = RomScanCache::load_in(cache_dir.path().to_path_buf(), rom_dirs);
+        let delta = cache.clone().refresh(|_, _| {});
+        assert_eq!((delta.rescanned, delta.unchanged, delta.removed), (0, 1, 1));
+        assert!(!delta.full);
+        assert_eq!(delta.changed_sets, HashSet::from(["galaga".to_string()]));
+
+        let games = scan(&mut cache);
+        assert_eq!(games["pacman"].status, RomStatus::Available);
+        assert_eq!(games["pacman"].verification_status, None);
+        assert_eq!(games["galaga"].status, RomStatus::Missing);
+        assert_eq!(games["galaga"].verification_status, None);
+    }
+
+    #[test]
+    fn watched_paths_update_only_the_games_they_touch() {
+        let roms = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        write_zip(roms.path(), "pacman", &[("a.bin", b"pacman")]);
+
+        let mut cache = RomScanCache::load_in(
+            cache_dir.path().to_path_buf(),
+            vec![roms.path().to_path_buf()],
+        );
+        let mut games: Vec<Game> = scan(&mut cache).into_values().collect();
+        games.sort_by(|a, b| a.name.cmp(&b.name));
+        games[1].verification_status = Some(VerificationStatus::Verified);
+        assert_eq!(games[0].status, RomStatus::Missing);
+
+        // galaga.zip is copied in and pacman.zip removed while the app runs
+        let galaga = roms.path().join("galaga.zip");
+        write_zip(roms.path(), "galaga", &[("a.bin", b"galaga")]);
+        let pacman = roms.path().join("pacman.zip");
+        fs::remove_file(&pacman).unwrap();
+        let outside = PathBuf::from("/not/a/rom/dir/galaga.zip");
+
+        let delta = cache.refresh_paths(&[galaga, pacman, outside]);
+        assert_eq!((delta.rescanned, delta.removed), (1, 1));
+
+        let loader = RomLoader::new(cache.rom_dirs().to_vec());
+        let changed = loader.refresh_statuses(&mut games, &cache, &delta, false);
+        assert_eq!(changed, vec![0, 1]);
+        assert_eq!(games[0].status, RomStatus::Available);
+        assert_eq!(games[1].status, RomStatus::Missing);
+        assert_eq!(games[1].verification_status, None);
+
+        // Nothing changed since, so nothing is touched
+        let delta = cache.refresh_paths(&[roms.path().join("galaga.zip")]);
+        assert!(
+            loader
+                .refresh_statuses(&mut games, &cache, &delta, false)
+                .is_empty()
+        );
+    }
+}
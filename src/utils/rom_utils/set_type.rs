@@ -0,0 +1,359 @@
+// src/utils/rom_utils/set_type.rs
+//! Detects how a ROM collection is built (merged, split or non-merged) by
+//! looking inside a sample of parent and clone archives.
+use super::RomLoader;
+use crate::models::{Game, MachineRomSet, RomSetType};
+use std::collections::{HashMap, HashSet};
+
+/// Parent/clone families inspected by default. Enough for a stable vote
+/// without opening thousands of archives on a slow drive.
+pub const DEFAULT_SAMPLE_SIZE: usize = 64;
+
+/// Outcome of inspecting the collection on disk
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RomSetDetection {
+    pub set_type: RomSetType,
+    pub merged: usize,
+    pub split: usize,
+    pub non_merged: usize,
+    /// More than one layout was found in meaningful numbers
+    pub mixed: bool,
+}
+
+impl RomSetDetection {
+    pub fn sampled(&self) -> usize {
+        self.merged + self.split + self.non_merged
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{:?}{} ({} merged, {} split, {} non-merged of {} sampled families)",
+            self.set_type,
+            if self.mixed { ", mixed" } else { "" },
+            self.merged,
+            self.split,
+            self.non_merged,
+            self.sampled()
+        )
+    }
+}
+
+/// Sample parent/clone families and vote on the layout each one shows:
+/// clone-only ROMs inside the parent archive mean merged, a clone archive
+/// that also carries the parent's ROMs means non-merged, and a clone archive
+/// with only its own ROMs means split. The sample is spread evenly over the
+/// whole list rather than taken from its start.
+pub fn detect_rom_set_type(
+    loader: &RomLoader,
+    games: &[Game],
+    sample_size: usize,
+) -> RomSetDetection {
+    let by_name: HashMap<&str, &Game> = games.iter().map(|g| (g.name.as_str(), g)).collect();
+
+    // Only clones that both share and own ROMs can tell the layouts apart
+    let mut families: Vec<(&Game, &Game)> = games
+        .iter()
+        .filter(|clone| {
+            clone.rom_set.roms.iter().any(|rom| rom.merge.is_some())
+                && clone.rom_set.roms.iter().any(|rom| rom.merge.is_none())
+        })
+        .filter_map(|clone| {
+            let parent = by_name.get(clone.rom_set.cloneof.as_deref()?)?;
+            Some((*parent, clone))
+        })
+        .collect();
+    families.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+
+    let mut detection = RomSetDetection::default();
+    let mut sampled_parents = HashSet::new();
+
+    // Every step-th family first, then the ones in between, so families
+    // skipped for lack of archives are replaced from across the list too
+    let step = (families.len() / sample_size.max(1)).max(1);
+    let spread = (0..step).flat_map(|offset| families.iter().skip(offset).step_by(step));
+
+    for &(parent, clone) in spread {
+        if detection.sampled() >= sample_size {
+            break;
+        }
+        // One clone per parent keeps big families from dominating the vote
+        if sampled_parents.contains(&parent.name) {
+            continue;
+        }
+        let Some(parent_files) = loader.set_entries(&parent.name) else {
+            continue;
+        };
+
+        let parent_crcs: HashSet<u32> = parent_files.iter().map(|f| f.crc).collect();
+        let own_crcs = clone_only_crcs(&clone.rom_set, &parent.rom_set);
+        if !own_crcs.is_empty() && own_crcs.iter().all(|crc| parent_crcs.contains(crc)) {
+            detection.merged += 1;
+        } else if let Some(clone_files) = loader.set_entries(&clone.name) {
+            let clone_crcs: HashSet<u32> = clone_files.iter().map(|f| f.crc).collect();
+            let carries_shared = clone
+                .rom_set
+                .roms
+                .iter()
+                .filter(|rom| rom.merge.is_some())
+                .filter_map(|rom| rom_crc(rom.crc.as_deref()))
+                .any(|crc| clone_crcs.contains(&crc));
+            if carries_shared {
+                detection.non_merged += 1;
+            } else {
+                detection.split += 1;
+            }
+        } else {
+            // Parent without its clones says nothing about the layout
+            continue;
+        }
+        sampled_parents.insert(parent.name.clone());
+    }
+
+    let votes = [
+        (RomSetType::Merged, detection.merged),
+        (RomSetType::Split, detection.split),
+        (RomSetType::NonMerged, detection.non_merged),
+    ];
+    let total = detection.sampled();
+    if let Some(&(set_type, count)) = votes.iter().max_by_key(|(_, count)| *count)
+        && count > 0
+    {
+        detection.set_type = set_type;
+        // Stray sets are common; call it mixed once another layout passes 10%
+        detection.mixed = votes
+            .iter()
+            .any(|&(other, n)| other != set_type && n > 0 && n * 10 >= total);
+    }
+
+    detection
+}
+
+/// CRCs of ROMs that belong to the clone alone (not shared with the parent)
+fn clone_only_crcs(clone: &MachineRomSet, parent: &MachineRomSet) -> Vec<u32> {
+    let parent_crcs: HashSet<u32> = parent
+        .roms
+        .iter()
+        .filter_map(|rom| rom_crc(rom.crc.as_deref()))
+        .collect();
+
+    clone
+        .roms
+        .iter()
+        .filter(|rom| rom.merge.is_none())
+        .filter_map(|rom| rom_crc(rom.crc.as_deref()))
+        .filter(|crc| !parent_crcs.contains(crc))
+        .collect()
+}
+
+fn rom_crc(crc: Option<&str>) -> Option<u32> {
+    u32::from_str_radix(crc?, 16).ok()
+}
+
+/// In a merged collection clones have no archive of their own. Mark them
+/// available when the parent archive is present and holds the clone's own
+/// ROMs, since MAME loads them from it.
+pub fn apply_merged_availability(loader: &RomLoader, games: &mut [Game]) {
+    let indices: Vec<usize> = (0..games.len()).collect();
+    mark_merged_clones(loader, games, &indices);
+}
+
+/// `apply_merged_availability` for only the games at `indices`, e.g. the
+/// ones a folder change touched
+pub fn mark_merged_clones(loader: &RomLoader, games: &mut [Game], indices: &[usize]) {
+    use crate::models::RomStatus;
+
+    let available_parents: HashSet<String> = games
+        .iter()
+        .filter(|g| !g.is_clone && matches!(g.status, RomStatus::Available))
+        .map(|g| g.name.clone())
+        .collect();
+    let mut parent_crcs: HashMap<String, Option<HashSet<u32>>> = HashMap::new();
+
+    for &idx in indices {
+        let game = &games[idx];
+        if !game.is_clone || !matches!(game.status, RomStatus::Missing) {
+            continue;
+        }
+        let Some(parent) = game
+            .parent
+            .as_ref()
+            .filter(|parent| available_parents.contains(*parent))
+        else {
+            continue;
+        };
+        let Some(crcs) = parent_crcs
+            .entry(parent.clone())
+            .or_insert_with(|| {
+                loader
+                    .set_entries(parent)
+                    .map(|files| files.iter().map(|f| f.crc).collect())
+            })
+            .as_ref()
+        else {
+            continue;
+        };
+        // ROMs shared with the parent (`merge`) are checked with the parent
+        let holds_own_roms = game
+            .rom_set
+            .roms
+            .iter()
+            .filter(|rom| rom.merge.is_none())
+            .filter_map(|rom| rom_crc(rom.crc.as_deref()))
+            .all(|crc| crcs.contains(&crc));
+        if holds_own_roms {
+            games[idx].status = RomStatus::Available;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::rom_utils::test_support::{family_game, merged_rom, rom, write_zip};
+
+    fn family(index: usize) -> Vec<Game> {
+        let parent = format!("game{}", index);
+        vec![
+            family_game(&parent, None, vec![rom("p.bin", b"parent")]),
+            family_game(
+                &format!("{}a", parent),
+                Some(&parent),
+                vec![
+                    merged_rom("p.bin", b"parent", "p.bin"),
+                    rom("c.bin", format!("clone {}", index).as_bytes()),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn detects_layout_from_archive_contents() {
+        let games: Vec<Game> = (0..4).flat_map(family).collect();
+
+        let layouts: [(&str, RomSetType); 3] = [
+            ("merged", RomSetType::Merged),
+            ("split", RomSetType::Split),
+            ("nonmerged", RomSetType::NonMerged),
+        ];
+        for (layout, expected) in layouts {
+            let dir = tempfile::tempdir().unwrap();
+            for index in 0..4 {
+                let parent = format!("game{}", index);
+                let clone_rom = format!("clone {}", index);
+                match layout {
+                    "merged" => write_zip(
+                        dir.path(),
+                        &parent,
+                        &[("p.bin", b"parent"), ("c.bin", clone_rom.as_bytes())],
+                    ),
+                    "split" => {
+                        write_zip(dir.path(), &parent, &[("p.bin", b"parent")]);
+                        write_zip(
+                            dir.path(),
+                            &format!("{}a", parent),
+                            &[("c.bin", clone_rom.as_bytes())],
+                        );
+                    }
+                    _ => {
+                        write_zip(dir.path(), &parent, &[("p.bin", b"parent")]);
+                        write_zip(
+                            dir.path(),
+                            &format!("{}a", parent),
+                            &[("p.bin", b"parent"), ("c.bin", clone_rom.as_bytes())],
+                        );
+                    }
+                }
+            }
+
+            let loader = RomLoader::new(vec![dir.path().to_path_buf()]);
+            let detection = detect_rom_set_type(&loader, &games, DEFAULT_SAMPLE_SIZE);
+            assert_eq!(detection.set_type, expected, "{}", layout);
+            assert_eq!(detection.sampled(), 4);
+            assert!(!detection.mixed);
+        }
+    }
+
+    #[test]
+    fn flags_mixed_collections() {
+        let games: Vec<Game> = (0..4).flat_map(family).collect();
+        let dir = tempfile::tempdir().unwrap();
+        write_zip(
+            dir.path(),
+            "game0",
+            &[("p.bin", b"parent"), ("c.bin", b"clone 0")],
+        );
+        for index in 1..4 {
+            write_zip(
+                dir.path(),
+                &format!("game{}", index),
+                &[("p.bin", b"parent")],
+            );
+            write_zip(
+                dir.path(),
+                &format!("game{}a", index),
+                &[("c.bin", format!("clone {}", index).as_bytes())],
+            );
+        }
+
+        let loader = RomLoader::new(vec![dir.path().to_path_buf()]);
+        let detection = detect_rom_set_type(&loader, &games, DEFAULT_SAMPLE_SIZE);
+        assert_eq!(detection.set_type, RomSetType::Split);
+        assert_eq!(detection.merged, 1);
+        assert!(detection.mixed);
+    }
+
+    #[test]
+    fn samples_across_the_whole_list() {
+        let games: Vec<Game> = (0..4).flat_map(family).collect();
+        let dir = tempfile::tempdir().unwrap();
+        for index in 0..4 {
+            let parent = format!("game{}", index);
+            let clone_rom = format!("clone {}", index);
+            if index < 2 {
+                write_zip(
+                    dir.path(),
+                    &parent,
+                    &[("p.bin", b"parent"), ("c.bin", clone_rom.as_bytes())],
+                );
+            } else {
+                write_zip(dir.path(), &parent, &[("p.bin", b"parent")]);
+                write_zip(
+                    dir.path(),
+                    &format!("{}a", parent),
+                    &[("c.bin", clone_rom.as_bytes())],
+                );
+            }
+        }
+
+        // The first two families alone would both vote merged
+        let loader = RomLoader::new(vec![dir.path().to_path_buf()]);
+        let detection = detect_rom_set_type(&loader, &games, 2);
+        assert_eq!((detection.merged, detection.split), (1, 1));
+    }
+
+    #[test]
+    fn merged_clones_need_their_roms_in_the_parent() {
+        use crate::models::RomStatus;
+
+        let mut games: Vec<Game> = (0..2).flat_map(family).collect();
+        for game in &mut games {
+            game.status = if game.is_clone {
+                RomStatus::Missing
+            } else {
+                RomStatus::Available
+            };
+        }
+        let dir = tempfile::tempdir().unwrap();
+        write_zip(
+            dir.path(),
+            "game0",
+            &[("p.bin", b"parent"), ("c.bin", b"clone 0")],
+        );
+        write_zip(dir.path(), "game1", &[("p.bin", b"parent")]);
+
+        let loader = RomLoader::new(vec![dir.path().to_path_buf()]);
+        apply_merged_availability(&loader, &mut games);
+        assert_eq!(games[1].status, RomStatus::Available);
+        assert_eq!(games[3].status, RomStatus::Missing);
+    }
+}
@@ -0,0 +1,258 @@
+// src/utils/rom_utils/software_audit.rs
+//! Audits software-list media against the `<rom>` and `<disk>` hashes from
+//! the hash XML. Media for `<list>/<software>` lives in `<root>/<list>/` as
+//! `<software>.zip`, `<software>.7z` or a `<software>/` folder, with CHDs in
+//! that folder; clones may take files from their parent's set.
+use super::RomLoader;
+use crate::mame::SoftwareEntry;
+use crate::models::DumpStatus;
+use crate::utils::archive::ArchiveEntry;
+use crate::utils::chd;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Overall state of one software item's media
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoftwareMediaStatus {
+    Good,    // Every dumped ROM and disk matches its hash
+    Bad,     // Some files are wrong or missing
+    Missing, // Nothing of the item was found
+    NoDump,  // Every file is undumped, so there is nothing to check
+}
+
+impl SoftwareMediaStatus {
+    pub const ALL: [SoftwareMediaStatus; 4] = [
+        SoftwareMediaStatus::Good,
+        SoftwareMediaStatus::Bad,
+        SoftwareMediaStatus::Missing,
+        SoftwareMediaStatus::NoDump,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SoftwareMediaStatus::Good => "Good",
+            SoftwareMediaStatus::Bad => "Bad",
+            SoftwareMediaStatus::Missing => "Missing",
+            SoftwareMediaStatus::NoDump => "No dump",
+        }
+    }
+}
+
+/// Audit outcome of one software item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoftwareAuditResult {
+    pub status: SoftwareMediaStatus,
+    /// One line per wrong or missing file
+    pub problems: Vec<String>,
+}
+
+/// Audits software items, reading each list folder's archives only once
+pub struct SoftwareAuditor {
+    roots: Vec<PathBuf>,
+    loaders: HashMap<String, RomLoader>,
+    listings: HashMap<(String, String), Option<Vec<ArchiveEntry>>>,
+}
+
+impl SoftwareAuditor {
+    pub fn new(software_rom_paths: &[PathBuf]) -> Self {
+        Self {
+            roots: software_rom_paths.to_vec(),
+            loaders: HashMap::new(),
+            listings: HashMap::new(),
+        }
+    }
+
+    pub fn audit(&mut self, entry: &SoftwareEntry) -> SoftwareAuditResult {
+        let mut chain = vec![entry.name.clone()];
+        chain.extend(entry.clone_of.clone().or_else(|| entry.parent.clone()));
+
+        let mut problems = Vec::new();
+        let mut checked = 0;
+        let mut found = 0;
+
+        for rom in entry
+            .roms
+            .iter()
+            .filter(|rom| rom.status != DumpStatus::NoDump)
+        {
+            checked += 1;
+            let expected_crc = rom
+                .crc
+                .as_deref()
+                .and_then(|crc| u32::from_str_radix(crc, 16).ok());
+            let mut name_found = false;
+            let mut good = false;
+
+            for set in &chain {
+                let Some(files) = self.listing(&entry.list_name, set) else {
+                    continue;
+                };
+                if let Some(crc) = expected_crc
+                    && files.iter().any(|f| f.crc == crc && f.size == rom.size)
+                {
+                    good = true;
+                    break;
+                }
+                if let Some(file) = files
+                    .iter()
+                    .find(|f| f.name.eq_ignore_ascii_case(&rom.name))
+                {
+                    if expected_crc.is_none() && file.size == rom.size {
+                        good = true;
+                        break;
+                    }
+                    name_found = true;
+                }
+            }
+
+            if good {
+                found += 1;
+            } else if name_found {
+                found += 1;
+                // Known-bad dumps only have best-available hashes
+                if rom.status != DumpStatus::BadDump {
+                    problems.push(format!("{}: incorrect", rom.name));
+                }
+            } else {
+                problems.push(format!("{}: not found", rom.name));
+            }
+        }
+
+        for disk in entry
+            .disks
+            .iter()
+            .filter(|disk| disk.status != DumpStatus::NoDump)
+        {
+            checked += 1;
+            let Some(path) = self
+                .loader(&entry.list_name)
+                .locate_disk(&chain, &[disk.name.as_str()])
+            else {
+                problems.push(format!("{}.chd: not found", disk.name));
+                continue;
+            };
+            found += 1;
+            let Some(expected) = &disk.sha1 else {
+                continue;
+            };
+            match chd::read_header(&path) {
+                Ok(header) if &header.sha1 == expected => {}
+                Ok(_) if disk.status == DumpStatus::BadDump => {}
+                Ok(_) => problems.push(format!("{}.chd: SHA1 mismatch", disk.name)),
+                Err(e) => problems.push(format!("{}.chd: {:#}", disk.name, e)),
+            }
+        }
+
+        let status = if checked == 0 {
+            SoftwareMediaStatus::NoDump
+        } else if found == 0 {
+            SoftwareMediaStatus::Missing
+        } else if problems.is_empty() {
+            SoftwareMediaStatus::Good
+        } else {
+            SoftwareMediaStatus::Bad
+        };
+        SoftwareAuditResult { status, problems }
+    }
+
+    fn loader(&mut self, list_name: &str) -> &RomLoader {
+        let roots = &self.roots;
+        self.loaders
+            .entry(list_name.to_string())
+            .or_insert_with(|| {
+                let dirs = roots
+                    .iter()
+                    .map(|root| root.join(list_name))
+                    .filter(|dir| dir.is_dir())
+                    .collect();
+                RomLoader::new(dirs)
+            })
+    }
+
+    fn listing(&mut self, list_name: &str, set: &str) -> Option<&[ArchiveEntry]> {
+        let key = (list_name.to_string(), set.to_string());
+        if !self.listings.contains_key(&key) {
+            let listing = self.loader(list_name).set_entries(set);
+            self.listings.insert(key.clone(), listing);
+        }
+        self.listings.get(&key)?.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DiskEntry, RomEntry};
+    use crate::utils::rom_utils::test_support::rom;
+    use std::fs::{self, File};
+    use std::io::Write;
+
+    fn software(name: &str, clone_of: Option<&str>, roms: Vec<RomEntry>) -> SoftwareEntry {
+        SoftwareEntry {
+            list_name: "nes".to_string(),
+            name: name.to_string(),
+            clone_of: clone_of.map(str::to_string),
+            roms,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn audits_archives_clones_and_chds_per_list() {
+        let root = tempfile::tempdir().unwrap();
+        let list = root.path().join("nes");
+        fs::create_dir_all(&list).unwrap();
+        let mut writer = zip::ZipWriter::new(File::create(list.join("smb.zip")).unwrap());
+        for (name, data) in [("prg.bin", b"program".as_slice()), ("chr.bin", b"tiles")] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap();
+        fs::create_dir(list.join("disky")).unwrap();
+        fs::write(
+            list.join("disky").join("disky.chd"),
+            chd::test_header(5, 1024, [0x11; 20]),
+        )
+        .unwrap();
+
+        let mut auditor = SoftwareAuditor::new(&[root.path().to_path_buf()]);
+        let good = software(
+            "smb",
+            None,
+            vec![rom("prg.bin", b"program"), rom("chr.bin", b"tiles")],
+        );
+        assert_eq!(auditor.audit(&good).status, SoftwareMediaStatus::Good);
+
+        // The clone keeps its changed ROM itself and shares the rest
+        let clone = software(
+            "smbj",
+            Some("smb"),
+            vec![rom("prg.bin", b"program"), rom("chr.bin", b"other tiles")],
+        );
+        let result = auditor.audit(&clone);
+        assert_eq!(result.status, SoftwareMediaStatus::Bad);
+        assert_eq!(result.problems, vec!["chr.bin: incorrect"]);
+
+        let missing = software("zelda", None, vec![rom("prg.bin", b"zelda")]);
+        assert_eq!(auditor.audit(&missing).status, SoftwareMediaStatus::Missing);
+
+        let mut undumped = software("proto", None, vec![rom("prg.bin", b"")]);
+        undumped.roms[0].status = DumpStatus::NoDump;
+        assert_eq!(auditor.audit(&undumped).status, SoftwareMediaStatus::NoDump);
+
+        let mut disk = software("disky", None, Vec::new());
+        disk.disks.push(DiskEntry {
+            name: "disky".to_string(),
+            sha1: Some("11".repeat(20)),
+            ..Default::default()
+        });
+        assert_eq!(auditor.audit(&disk).status, SoftwareMediaStatus::Good);
+        disk.disks[0].sha1 = Some("22".repeat(20));
+        assert_eq!(
+            auditor.audit(&disk).problems,
+            vec!["disky.chd: SHA1 mismatch"]
+        );
+    }
+}
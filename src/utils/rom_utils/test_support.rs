@@ -0,0 +1,64 @@
+// src/utils/rom_utils/test_support.rs
+//! Fixtures shared by the ROM tool tests
+use crate::models::{Game, MachineRomSet, RomEntry};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// ROM whose size and CRC are those of `data`
+pub fn rom(name: &str, data: &[u8]) -> RomEntry {
+    RomEntry {
+        name: name.to_string(),
+        size: data.len() as u64,
+        crc: Some(format!("{:08x}", crc32fast::hash(data))),
+        ..Default::default()
+    }
+}
+
+/// ROM a clone shares with its parent under the parent's name `merge`
+pub fn merged_rom(name: &str, data: &[u8], merge: &str) -> RomEntry {
+    RomEntry {
+        merge: Some(merge.to_string()),
+        ..rom(name, data)
+    }
+}
+
+/// Game around a ROM manifest; a `cloneof` makes it a clone
+pub fn game(name: &str, rom_set: MachineRomSet) -> Game {
+    Game {
+        name: name.to_string(),
+        description: name.to_string(),
+        driver_status: "good".to_string(),
+        parent: rom_set.cloneof.clone(),
+        is_clone: rom_set.cloneof.is_some(),
+        requires_chd: !rom_set.disks.is_empty(),
+        rom_set,
+        ..Game::default()
+    }
+}
+
+/// Parent, or clone loading from `parent`, with these ROMs
+pub fn family_game(name: &str, parent: Option<&str>, roms: Vec<RomEntry>) -> Game {
+    game(
+        name,
+        MachineRomSet {
+            cloneof: parent.map(str::to_string),
+            romof: parent.map(str::to_string),
+            roms,
+            ..Default::default()
+        },
+    )
+}
+
+/// Write `<dir>/<set>.zip` holding these files
+pub fn write_zip(dir: &Path, set: &str, files: &[(&str, &[u8])]) {
+    let file = File::create(dir.join(format!("{}.zip", set))).unwrap();
+    let mut writer = zip::ZipWriter::new(file);
+    for (name, data) in files {
+        writer
+            .start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(data).unwrap();
+    }
+    writer.finish().unwrap();
+}
@@ -0,0 +1,427 @@
+// src/utils/rom_utils/verify_history.rs
+//! Verification results kept on disk between sessions. Each game's latest
+//! result is stored with when it was checked, the MAME version, reference and
+//! ROM paths it was checked against, and every run records how statuses moved
+//! compared to the previous results. A stored result is only restored when it
+//! was checked against MAME's own data and the set's files are unchanged.
+use crate::models::{AppConfig, Game};
+use crate::ui::components::rom_verify::{VerificationResult, VerifyStatus};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Bump whenever the file layout changes so stale files are ignored.
+const HISTORY_FORMAT_VERSION: u32 = 1;
+
+/// Runs kept for the history view; older ones are dropped
+const MAX_RUNS: usize = 20;
+
+/// Latest verification result of one game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredResult {
+    pub result: VerificationResult,
+    pub verified_at: String,
+    pub mame_version: String,
+    /// What the game was checked against: `mame_reference` or a loaded DAT
+    #[serde(default)]
+    pub reference: String,
+    /// `rom_fingerprint` of the ROM paths the game was verified in
+    pub rom_fingerprint: String,
+    /// `RomScanCache::set_fingerprints` value of the files the game was
+    /// verified from
+    #[serde(default)]
+    pub set_fingerprint: Option<u32>,
+}
+
+/// How a game's status moved between two audits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    NewlyBroken, // Passed before, fails now
+    NewlyFixed,  // Failed before, passes now
+    NewlyAdded,  // First result for this set
+    Changed,     // Different status on the same side (e.g. failed → not found)
+}
+
+impl ChangeKind {
+    pub const ALL: [ChangeKind; 4] = [
+        ChangeKind::NewlyBroken,
+        ChangeKind::NewlyFixed,
+        ChangeKind::NewlyAdded,
+        ChangeKind::Changed,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeKind::NewlyBroken => "Newly broken",
+            ChangeKind::NewlyFixed => "Newly fixed",
+            ChangeKind::NewlyAdded => "Newly added",
+            ChangeKind::Changed => "Changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub game_name: String,
+    pub description: String,
+    pub before: Option<VerifyStatus>,
+    pub after: VerifyStatus,
+}
+
+impl StatusChange {
+    pub fn kind(&self) -> ChangeKind {
+        match &self.before {
+            None => ChangeKind::NewlyAdded,
+            Some(before) if is_playable(before) && !is_playable(&self.after) => {
+                ChangeKind::NewlyBroken
+            }
+            Some(before) if !is_playable(before) && is_playable(&self.after) => {
+                ChangeKind::NewlyFixed
+            }
+            Some(_) => ChangeKind::Changed,
+        }
+    }
+}
+
+/// One finished verification run and what it changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRun {
+    pub finished_at: String,
+    pub mame_version: String,
+    #[serde(default)]
+    pub reference: String,
+    pub checked: usize,
+    pub changes: Vec<StatusChange>,
+}
+
+impl AuditRun {
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.kind() == kind)
+            .count()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HistoryFile {
+    format_version: u32,
+    results: HashMap<String, StoredResult>,
+    runs: Vec<AuditRun>,
+}
+
+#[derive(Debug, Default)]
+pub struct VerificationHistory {
+    path: Option<PathBuf>,
+    results: HashMap<String, StoredResult>,
+    runs: Vec<AuditRun>,
+}
+
+impl VerificationHistory {
+    /// History under the user's data directory (e.g. `~/.local/share/mameuix`)
+    pub fn load() -> Self {
+        match dirs::data_dir() {
+            Some(dir) => Self::load_from(dir.join("mameuix").join("verification_history.json")),
+            None => Self::default(),
+        }
+    }
+
+    /// Restore the history from a file, starting empty when there is none
+    pub fn load_from(path: PathBuf) -> Self {
+        let mut history = Self::default();
+        if let Ok(contents) = fs::read(&path) {
+            match serde_json::from_slice::<HistoryFile>(&contents) {
+                Ok(file) if file.format_version == HISTORY_FORMAT_VERSION => {
+                    history.results = file.results;
+                    history.runs = file.runs;
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!(
+                    "Ignoring unreadable verification history {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        history.path = Some(path);
+        history
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+
+        // Write to a temp file first so a crash mid-write never loses the history
+        let tmp_path = path.with_extension("json.tmp");
+        let file = HistoryFile {
+            format_version: HISTORY_FORMAT_VERSION,
+            results: self.results.clone(),
+            runs: self.runs.clone(),
+        };
+        fs::write(&tmp_path, serde_json::to_vec(&file)?)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Runs from oldest to newest
+    pub fn runs(&self) -> &[AuditRun] {
+        &self.runs
+    }
+
+    /// Store the results of a finished run and record what changed since
+    /// each game's previous result. `set_fingerprints` comes from
+    /// `RomScanCache::set_fingerprints` at the end of the run. Returns None
+    /// for an empty run.
+    pub fn record_run(
+        &mut self,
+        results: &[VerificationResult],
+        mame_version: &str,
+        reference: &str,
+        rom_fingerprint: &str,
+        set_fingerprints: &HashMap<String, u32>,
+    ) -> Option<&AuditRun> {
+        if results.is_empty() {
+            return None;
+        }
+
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut changes = Vec::new();
+        for result in results {
+            let before = self
+                .results
+                .get(&result.game_name)
+                .map(|stored| stored.result.status.clone());
+            if before.as_ref() != Some(&result.status) {
+                changes.push(StatusChange {
+                    game_name: result.game_name.clone(),
+                    description: result.description.clone(),
+                    before,
+                    after: result.status.clone(),
+                });
+            }
+            self.results.insert(
+                result.game_name.clone(),
+                StoredResult {
+                    result: result.clone(),
+                    verified_at: now.clone(),
+                    mame_version: mame_version.to_string(),
+                    reference: reference.to_string(),
+                    rom_fingerprint: rom_fingerprint.to_string(),
+                    set_fingerprint: set_fingerprints.get(&result.game_name).copied(),
+                },
+            );
+        }
+        changes.sort_by(|a, b| a.game_name.cmp(&b.game_name));
+
+        self.runs.push(AuditRun {
+            finished_at: now,
+            mame_version: mame_version.to_string(),
+            reference: reference.to_string(),
+            checked: results.len(),
+            changes,
+        });
+        if self.runs.len() > MAX_RUNS {
+            self.runs.drain(..self.runs.len() - MAX_RUNS);
+        }
+        self.runs.last()
+    }
+
+    /// Restore `verification_status` for games verified against this MAME
+    /// version's own data (not a loaded DAT) in these ROM paths whose files have not changed since, going
+    /// by the current `set_fingerprints`. Returns how many games were restored.
+    pub fn apply_to(
+        &self,
+        games: &mut [Game],
+        mame_version: &str,
+        rom_fingerprint: &str,
+        set_fingerprints: &HashMap<String, u32>,
+    ) -> usize {
+        let mut restored = 0;
+        for game in games.iter_mut() {
+            if let Some(stored) = self.results.get(&game.name)
+                && stored.mame_version == mame_version
+                && stored.reference == mame_reference(mame_version)
+                && stored.rom_fingerprint == rom_fingerprint
+                && stored
+                    .set_fingerprint
+                    .is_some_and(|set| set_fingerprints.get(&game.name) == Some(&set))
+            {
+                game.update_verification_status(stored.result.status.verification_status());
+                restored += 1;
+            }
+        }
+        restored
+    }
+}
+
+/// Reference of runs checked against MAME's own ROM data
+pub fn mame_reference(mame_version: &str) -> String {
+    format!("MAME {} datfile", mame_version)
+}
+
+/// Identifies the configured ROM paths, so results from other folders are
+/// not taken for the current ones. The paths themselves, one per line, so
+/// the value stays comparable across builds.
+pub fn rom_fingerprint(config: &AppConfig) -> String {
+    config
+        .rom_paths
+        .iter()
+        .chain(&config.extra_rom_dirs)
+        .map(|path| path.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Passed and warning sets run; the rest don't
+fn is_playable(status: &VerifyStatus) -> bool {
+    matches!(status, VerifyStatus::Passed | VerifyStatus::Warning)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::VerificationStatus;
+    use crate::utils::rom_utils::RomScanCache;
+    use crate::utils::rom_utils::test_support::game;
+
+    fn result(name: &str, status: VerifyStatus) -> VerificationResult {
+        VerificationResult {
+            game_name: name.to_string(),
+            description: name.to_string(),
+            status,
+            missing_files: Vec::new(),
+            incorrect_files: Vec::new(),
+            extra_files: Vec::new(),
+            chd_status: None,
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn runs_record_deltas_and_results_survive_a_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let roms = tempfile::tempdir().unwrap();
+        let rom_dirs = vec![roms.path().to_path_buf()];
+        let config = AppConfig {
+            rom_paths: rom_dirs.clone(),
+            ..Default::default()
+        };
+        let fingerprint = rom_fingerprint(&config);
+        fs::write(roms.path().join("pacman.zip"), b"pacman").unwrap();
+        fs::write(roms.path().join("galaga.zip"), b"galaga").unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let mut cache = RomScanCache::load_in(cache_dir.path().to_path_buf(), rom_dirs);
+        cache.refresh(|_, _| {});
+        let named = |names: &[&str]| -> Vec<Game> {
+            names
+                .iter()
+                .map(|name| game(name, Default::default()))
+                .collect()
+        };
+        let sets = |names: &[&str]| cache.set_fingerprints(&named(names));
+
+        let mut history = VerificationHistory::load_from(path.clone());
+        history.record_run(
+            &[
+                result("pacman", VerifyStatus::Passed),
+                result("galaga", VerifyStatus::Failed),
+                result("dkong", VerifyStatus::Failed),
+            ],
+            "0.280",
+            &mame_reference("0.280"),
+            &fingerprint,
+            &sets(&["pacman", "galaga", "dkong"]),
+        );
+        let run = history
+            .record_run(
+                &[
+                    result("pacman", VerifyStatus::Failed),
+                    result("galaga", VerifyStatus::Passed),
+                    result("dkong", VerifyStatus::NotFound),
+                    result("mspacman", VerifyStatus::Passed),
+                ],
+                "0.280",
+                &mame_reference("0.280"),
+                &fingerprint,
+                &sets(&["pacman", "galaga", "dkong", "mspacman"]),
+            )
+            .unwrap();
+        let kinds: Vec<(&str, ChangeKind)> = run
+            .changes
+            .iter()
+            .map(|change| (change.game_name.as_str(), change.kind()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("dkong", ChangeKind::Changed),
+                ("galaga", ChangeKind::NewlyFixed),
+                ("mspacman", ChangeKind::NewlyAdded),
+                ("pacman", ChangeKind::NewlyBroken),
+            ]
+        );
+        history.save().unwrap();
+
+        let reloaded = VerificationHistory::load_from(path);
+        assert_eq!(reloaded.runs().len(), 2);
+        let mut games = named(&["pacman", "galaga", "unknown"]);
+        let current = cache.set_fingerprints(&games);
+        assert_eq!(
+            reloaded.apply_to(&mut games, "0.280", &fingerprint, &current),
+            2
+        );
+        assert_eq!(
+            games[0].verification_status,
+            Some(VerificationStatus::Failed)
+        );
+        assert_eq!(
+            games[1].verification_status,
+            Some(VerificationStatus::Verified)
+        );
+        assert_eq!(games[2].verification_status, None);
+
+        // Results from another MAME version or other ROM paths stay unused
+        let mut games = named(&["pacman"]);
+        assert_eq!(
+            reloaded.apply_to(&mut games, "0.281", &fingerprint, &current),
+            0
+        );
+        assert_eq!(reloaded.apply_to(&mut games, "0.280", "other", &current), 0);
+
+        // Nor do results for sets whose files changed since
+        fs::write(roms.path().join("pacman.zip"), b"pacman, rebuilt").unwrap();
+        cache.refresh(|_, _| {});
+        let mut games = named(&["pacman", "galaga"]);
+        let current = cache.set_fingerprints(&games);
+        assert_eq!(
+            reloaded.apply_to(&mut games, "0.280", &fingerprint, &current),
+            1
+        );
+        assert_eq!(games[0].verification_status, None);
+
+        // Results checked against a loaded DAT are not MAME's verdict
+        let mut history = reloaded;
+        history.record_run(
+            &[result("galaga", VerifyStatus::Passed)],
+            "0.280",
+            "Logiqx: MAME 0.280 ROMs",
+            &fingerprint,
+            &current,
+        );
+        let mut games = named(&["galaga"]);
+        assert_eq!(
+            history.apply_to(&mut games, "0.280", &fingerprint, &current),
+            0
+        );
+    }
+}